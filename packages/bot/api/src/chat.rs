@@ -0,0 +1,11 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+// name used to refer to a chat registered with the bot
+pub type ChatKey = String;
+
+#[derive(CandidType, Serialize, Deserialize)]
+pub struct ChatRegisterRequest {
+    pub key: ChatKey,
+    pub api_key: String,
+}
\ No newline at end of file
@@ -17,4 +17,5 @@ pub enum ImageInsertResponse {
     NotAuthorized,
     ImageSizeTooBig,
     ThumbSizeTooBig,
+    TemplateNotFound,
 }
@@ -1 +1,4 @@
-pub mod insert_image;
\ No newline at end of file
+pub mod insert_image;
+pub mod upload;
+pub mod chat;
+pub mod webhook;
\ No newline at end of file
@@ -0,0 +1,70 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use crate::insert_image::ImageInsertResponse;
+
+pub type UploadId = u64;
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum UploadTarget {
+    TemplateImage { id: u32 },
+    Asset { path: String },
+}
+
+#[derive(CandidType, Serialize, Deserialize)]
+pub struct UploadStartRequest {
+    pub target: UploadTarget,
+    pub mime_type: String,
+    pub total_size: u64,
+    pub chunk_size: u32,
+}
+
+#[derive(CandidType, Serialize, Deserialize)]
+pub enum UploadStartResponse {
+    Success(UploadId),
+    NotAuthorized,
+    InvalidSize,
+    InvalidChunkSize,
+    TemplateNotFound,
+}
+
+#[derive(CandidType, Serialize, Deserialize)]
+pub struct UploadChunkRequest {
+    pub upload_id: UploadId,
+    pub index: u32,
+    pub data: Vec<u8>,
+}
+
+#[derive(CandidType, Serialize, Deserialize)]
+pub enum UploadChunkResponse {
+    Success,
+    NotFound,
+    NotAuthorized,
+    InvalidIndex,
+    InvalidChunkSize,
+}
+
+#[derive(CandidType, Serialize, Deserialize)]
+pub struct UploadCommitRequest {
+    pub upload_id: UploadId,
+    pub sha256: Vec<u8>,
+}
+
+#[derive(CandidType, Serialize, Deserialize)]
+pub enum UploadCommitResponse {
+    Success { blob_id: Option<u128>, size: u64 },
+    ImageInsertFailed(ImageInsertResponse),
+    NotFound,
+    NotAuthorized,
+    Incomplete(Vec<u32>),
+    ChecksumMismatch,
+}
+
+#[derive(CandidType, Serialize, Deserialize)]
+pub struct UploadStatus {
+    pub target: UploadTarget,
+    pub total_size: u64,
+    pub chunk_size: u32,
+    pub num_chunks: u32,
+    pub missing: Vec<u32>,
+    pub expires_at: u64,
+}
\ No newline at end of file
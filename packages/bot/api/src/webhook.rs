@@ -0,0 +1,23 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use crate::chat::ChatKey;
+
+pub type WebhookName = String;
+
+#[derive(CandidType, Serialize, Deserialize)]
+pub struct WebhookRegisterRequest {
+    pub name: WebhookName,
+    // generated when not provided
+    pub secret: Option<Vec<u8>>,
+    // chats the webhook is allowed to act on
+    pub chats: Vec<ChatKey>,
+}
+
+#[derive(CandidType, Serialize, Deserialize)]
+pub enum WebhookRegisterResponse {
+    // the secret, to be shared with the integration
+    Success(Vec<u8>),
+    NotAuthorized,
+    InvalidName,
+    UnknownChat(ChatKey),
+}
\ No newline at end of file
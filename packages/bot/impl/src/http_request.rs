@@ -1,5 +1,8 @@
-use crate::router;
-use ic_http_certification::{HttpRequest, HttpResponse};
+use crate::{
+    router::{self, blobs}, 
+    types::http::{HttpResponse, StreamingCallbackHttpResponse, StreamingToken}
+};
+use ic_http_certification::HttpRequest;
 
 #[ic_cdk::query]
 async fn http_request(
@@ -13,4 +16,11 @@ async fn http_request_update(
     request: HttpRequest
 ) -> HttpResponse {
     router::handle(request, false).await
+}
+
+#[ic_cdk::query]
+fn http_request_streaming_callback(
+    token: StreamingToken
+) -> StreamingCallbackHttpResponse {
+    blobs::next_chunk(token)
 }
\ No newline at end of file
@@ -12,11 +12,34 @@ mod queries;
 mod updates;
 
 use std::collections::BTreeMap;
-use ic_http_certification::{HttpRequest, HttpResponse};
+use candid::Principal;
+use ic_http_certification::HttpRequest;
 use icrc_ledger_types::icrc::generic_value::Value;
-use crate::types::init::InitOrUpgradeArgs;
-use bot_api::
-    insert_image::{ImageInsertRequest, ImageInsertResponse}
-;
+use crate::types::{
+    blob::BlobStoreStats, 
+    chat::ChatInfo, 
+    http::{HttpResponse, StreamingCallbackHttpResponse, StreamingToken}, 
+    init::InitOrUpgradeArgs, 
+    mint_op::{MintOp, MintOpId, MintOpResolution}, 
+    moderation::{ModerationConfig, Refusal}, 
+    nft_col::{
+        MintPhase, MintingPreview, NftCollectionConfig, 
+        NftConfigChange, NftConfigStatus
+    }, 
+    rate_limit::{RateLimitConfig, RateLimitExemption, RateLimits}, 
+    report::{AuditEntry, Report, ReportStatus, TakedownAction}, 
+    role::{Role, RoleGrant}, 
+    webhook::{WebhookDelivery, WebhookInfo}
+};
+use bot_api::{
+    chat::{ChatKey, ChatRegisterRequest}, 
+    insert_image::{ImageInsertRequest, ImageInsertResponse}, 
+    upload::{
+        UploadChunkRequest, UploadChunkResponse, 
+        UploadCommitRequest, UploadCommitResponse, UploadId, 
+        UploadStartRequest, UploadStartResponse, UploadStatus
+    }, 
+    webhook::{WebhookName, WebhookRegisterRequest, WebhookRegisterResponse}
+};
 
 ic_cdk::export_candid!();
\ No newline at end of file
@@ -1,11 +1,23 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, time::Duration};
 use ic_ledger_types::DEFAULT_SUBACCOUNT;
 use crate::{
     services::{
-        fund::{FundCanisterConfig, FundService}, meme, nft::{self}
+        cert, deposit::deposit::{DepositService, DEPOSITS_SCAN_INTERVAL}, 
+        fund::{FundCanisterConfig, FundService}, meme, 
+        mint_op::mint_op::{MintOpService, MINT_OPS_RETRY_INTERVAL}, nft::{self}, 
+        scheduler::scheduler::SchedulerService
     }, 
     state::{self, State}, 
-    types::init::InitOrUpgradeArgs, 
+    storage::{
+        blob::BlobStorage, nft_config::NftConfigStorage, 
+        role::RoleStorage, upload::UploadStorage
+    }, 
+    types::{
+        init::InitOrUpgradeArgs, 
+        nft_col::{NftConfigChange, NftConfigChangeKind}, 
+        role::Role, 
+        schedule::SCHEDULER_INTERVAL
+    }, 
     utils::rng
 };
 
@@ -18,12 +30,15 @@ const READER_WRITER_BUFFER_SIZE: usize = 10 * 1024 * 1024; // 10MB
 const MIN_CYCLES: u128 = 1_000_000_000_000;
 const FUND_CYCLES: u128 =  500_000_000_000;
 
+const BLOB_GC_INTERVAL: u64 = 60 * 60; // every hour
+const BLOB_GC_MAX_ITEMS: usize = 100;
+
 thread_local! {
     static FUND_SERVICE: RefCell<FundService> = RefCell::new(FundService::new());
 }
 
 pub(crate) fn setup(
-    state: State,
+    mut state: State,
     args: InitOrUpgradeArgs
 ) -> Result<(), String> {
     ic_wasi_polyfill::init(&[0u8; 32], &[]);
@@ -61,20 +76,84 @@ pub(crate) fn setup(
         );
     });
 
+    // reclaim blobs no longer referenced by posts, NFTs, assets or previews
+    ic_cdk_timers::set_timer_interval(
+        Duration::from_secs(BLOB_GC_INTERVAL), 
+        || {
+            let num_collected = BlobStorage::collect_garbage(BLOB_GC_MAX_ITEMS);
+            if num_collected > 0 {
+                ic_cdk::println!("info: {} unreferenced blobs collected", num_collected);
+            }
+
+            let num_expired = UploadStorage::remove_expired(ic_cdk::api::time());
+            if num_expired > 0 {
+                ic_cdk::println!("info: {} expired upload sessions removed", num_expired);
+            }
+        }
+    );
+
+    // run the jobs scheduled by the chats
+    ic_cdk_timers::set_timer_interval(
+        Duration::from_secs(SCHEDULER_INTERVAL), 
+        || ic_cdk::spawn(SchedulerService::run_due_jobs())
+    );
+
+    // retry the refunds and the fee transfers of the mints that failed
+    ic_cdk_timers::set_timer_interval(
+        Duration::from_secs(MINT_OPS_RETRY_INTERVAL), 
+        || ic_cdk::spawn(MintOpService::retry_pending())
+    );
+
+    // record the deposits to the users' wallets
+    ic_cdk_timers::set_timer_interval(
+        Duration::from_secs(DEPOSITS_SCAN_INTERVAL), 
+        || ic_cdk::spawn(DepositService::scan())
+    );
+
     // start the meme service
     meme::init(
         args.memes_json_gz, 
         args.index_tar_gz
     )?;
 
+    // the config changed at runtime is kept, unless a new one is passed
+    let nft_config = match args.meme_nft_config {
+        Some(config) => {
+            config.validate()?;
+            if state.nft_config() != Some(&config) {
+                NftConfigStorage::log_change(NftConfigChange {
+                    id: 0,
+                    kind: NftConfigChangeKind::Config(config.clone()),
+                    changed_by: args.administrator,
+                    changed_at: ic_cdk::api::time(),
+                });
+                state.set_nft_config(config.clone());
+            }
+            config
+        },
+        None => {
+            state.nft_config().cloned()
+                .ok_or_else(|| "The NFT collection config is missing".to_string())?
+        }
+    };
+
     // create the nft service
     nft::mutate(|s| s.update(
-        args.meme_nft_config,
+        nft_config,
         args.meme_nft
     ));
 
     // init random
     rng::init(state.rng_seed());
+
+    // the deployer is the first owner. Later owners are managed with '/meme admin roles', 
+    // and revoking the last one is refused, so the bot can't be locked out
+    if !RoleStorage::has_owner() {
+        RoleStorage::grant(args.administrator, &[Role::Owner], args.administrator);
+    }
+
+    // certify the responses of the blob and asset routes
+    cert::mutate(|s| s.rebuild(&state.takedowns().hidden_blobs));
     
     state::init(state);
 
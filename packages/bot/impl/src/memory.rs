@@ -1,17 +1,41 @@
 use ic_stable_structures::{
     memory_manager::{MemoryId, MemoryManager, VirtualMemory},
-    DefaultMemoryImpl,
+    DefaultMemoryImpl, Memory as _,
 };
 
 const UPGRADES: MemoryId            = MemoryId::new(0);
 const IMAGES: MemoryId              = MemoryId::new(1);
 const THUMBS: MemoryId              = MemoryId::new(2);
 const BLOBS: MemoryId               = MemoryId::new(3);
-const TEMP_BLOBS: MemoryId          = MemoryId::new(4);
+// MemoryId 4 held the legacy temp blobs ring buffer and must not be reused
 const USERS: MemoryId               = MemoryId::new(5);
 const NFTS: MemoryId                = MemoryId::new(6);
 const MEME_TO_NFT: MemoryId         = MemoryId::new(7);
 const EVENTS: MemoryId              = MemoryId::new(8);
+const TEMP_BLOBS: MemoryId          = MemoryId::new(9);
+const BLOB_METAS: MemoryId          = MemoryId::new(10);
+const BLOB_CHUNKS: MemoryId         = MemoryId::new(11);
+const UPLOADS: MemoryId             = MemoryId::new(12);
+const UPLOAD_CHUNKS: MemoryId       = MemoryId::new(13);
+const ASSETS: MemoryId              = MemoryId::new(14);
+const DERIVED_BLOBS: MemoryId       = MemoryId::new(15);
+const DERIVED_BLOBS_QUEUE: MemoryId = MemoryId::new(16);
+const CHATS: MemoryId               = MemoryId::new(17);
+const WEBHOOKS: MemoryId            = MemoryId::new(18);
+const WEBHOOK_DELIVERIES: MemoryId  = MemoryId::new(19);
+const JOBS: MemoryId                = MemoryId::new(20);
+const CHAT_SETTINGS: MemoryId       = MemoryId::new(21);
+const REFUSALS: MemoryId            = MemoryId::new(22);
+const POSTED_MEMES: MemoryId        = MemoryId::new(23);
+const REPORTS: MemoryId             = MemoryId::new(24);
+const AUDIT_LOG: MemoryId           = MemoryId::new(25);
+const ROLES: MemoryId               = MemoryId::new(26);
+const NFT_CONFIG_CHANGES: MemoryId  = MemoryId::new(27);
+const MINT_OPS: MemoryId            = MemoryId::new(28);
+const OPEN_MINT_OPS: MemoryId       = MemoryId::new(29);
+const DEPOSIT_ACCOUNTS: MemoryId    = MemoryId::new(30);
+const BLOB_GC_QUEUE: MemoryId       = MemoryId::new(31);
+const WEBHOOK_NONCES: MemoryId      = MemoryId::new(32);
 
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
 
@@ -24,6 +48,46 @@ fn get_memory(id: MemoryId) -> Memory {
     MEMORY_MANAGER.with(|m| m.get(id))
 }
 
+/// Size, in bytes, of each memory region
+pub fn region_sizes() -> Vec<(&'static str, u64)> {
+    [
+        ("upgrades", UPGRADES),
+        ("images", IMAGES),
+        ("thumbs", THUMBS),
+        ("blobs", BLOBS),
+        ("users", USERS),
+        ("nfts", NFTS),
+        ("meme_to_nft", MEME_TO_NFT),
+        ("events", EVENTS),
+        ("temp_blobs", TEMP_BLOBS),
+        ("blob_metas", BLOB_METAS),
+        ("blob_chunks", BLOB_CHUNKS),
+        ("uploads", UPLOADS),
+        ("upload_chunks", UPLOAD_CHUNKS),
+        ("assets", ASSETS),
+        ("derived_blobs", DERIVED_BLOBS),
+        ("derived_blobs_queue", DERIVED_BLOBS_QUEUE),
+        ("chats", CHATS),
+        ("webhooks", WEBHOOKS),
+        ("webhook_deliveries", WEBHOOK_DELIVERIES),
+        ("jobs", JOBS),
+        ("chat_settings", CHAT_SETTINGS),
+        ("refusals", REFUSALS),
+        ("posted_memes", POSTED_MEMES),
+        ("reports", REPORTS),
+        ("audit_log", AUDIT_LOG),
+        ("roles", ROLES),
+        ("nft_config_changes", NFT_CONFIG_CHANGES),
+        ("mint_ops", MINT_OPS),
+        ("open_mint_ops", OPEN_MINT_OPS),
+        ("deposit_accounts", DEPOSIT_ACCOUNTS),
+        ("blob_gc_queue", BLOB_GC_QUEUE),
+        ("webhook_nonces", WEBHOOK_NONCES),
+    ].into_iter()
+        .map(|(name, id)| (name, get_memory(id).size() * 65536))
+        .collect()
+}
+
 pub fn get_upgrades_memory() -> Memory {
     get_memory(UPGRADES)
 }
@@ -60,3 +124,94 @@ pub fn get_events_memory() -> Memory {
     get_memory(EVENTS)
 }
 
+pub fn get_blob_metas_memory() -> Memory {
+    get_memory(BLOB_METAS)
+}
+
+pub fn get_blob_chunks_memory() -> Memory {
+    get_memory(BLOB_CHUNKS)
+}
+
+pub fn get_uploads_memory() -> Memory {
+    get_memory(UPLOADS)
+}
+
+pub fn get_upload_chunks_memory() -> Memory {
+    get_memory(UPLOAD_CHUNKS)
+}
+
+pub fn get_assets_memory() -> Memory {
+    get_memory(ASSETS)
+}
+
+pub fn get_derived_blobs_memory() -> Memory {
+    get_memory(DERIVED_BLOBS)
+}
+
+pub fn get_derived_blobs_queue_memory() -> Memory {
+    get_memory(DERIVED_BLOBS_QUEUE)
+}
+
+pub fn get_chats_memory() -> Memory {
+    get_memory(CHATS)
+}
+
+pub fn get_webhooks_memory() -> Memory {
+    get_memory(WEBHOOKS)
+}
+
+pub fn get_webhook_deliveries_memory() -> Memory {
+    get_memory(WEBHOOK_DELIVERIES)
+}
+
+pub fn get_jobs_memory() -> Memory {
+    get_memory(JOBS)
+}
+
+pub fn get_chat_settings_memory() -> Memory {
+    get_memory(CHAT_SETTINGS)
+}
+
+pub fn get_refusals_memory() -> Memory {
+    get_memory(REFUSALS)
+}
+
+pub fn get_posted_memes_memory() -> Memory {
+    get_memory(POSTED_MEMES)
+}
+
+pub fn get_reports_memory() -> Memory {
+    get_memory(REPORTS)
+}
+
+pub fn get_audit_log_memory() -> Memory {
+    get_memory(AUDIT_LOG)
+}
+
+pub fn get_roles_memory() -> Memory {
+    get_memory(ROLES)
+}
+
+pub fn get_nft_config_changes_memory() -> Memory {
+    get_memory(NFT_CONFIG_CHANGES)
+}
+
+pub fn get_mint_ops_memory() -> Memory {
+    get_memory(MINT_OPS)
+}
+
+pub fn get_open_mint_ops_memory() -> Memory {
+    get_memory(OPEN_MINT_OPS)
+}
+
+pub fn get_deposit_accounts_memory() -> Memory {
+    get_memory(DEPOSIT_ACCOUNTS)
+}
+
+pub fn get_blob_gc_queue_memory() -> Memory {
+    get_memory(BLOB_GC_QUEUE)
+}
+
+pub fn get_webhook_nonces_memory() -> Memory {
+    get_memory(WEBHOOK_NONCES)
+}
\ No newline at end of file
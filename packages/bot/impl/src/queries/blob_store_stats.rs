@@ -0,0 +1,15 @@
+use oc_bots_sdk_canister::env;
+use crate::{
+    storage::{blob::BlobStorage, role::RoleStorage}, 
+    types::{blob::BlobStoreStats, role::Role}
+};
+
+#[ic_cdk::query]
+fn get_blob_store_stats(
+) -> Result<BlobStoreStats, String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Curator) {
+        return Err("Not authorized".to_string());
+    }
+
+    Ok(BlobStorage::stats())
+}
\ No newline at end of file
@@ -0,0 +1,23 @@
+use oc_bots_sdk_canister::env;
+use crate::{
+    storage::{chat::ChatStorage, role::RoleStorage}, 
+    types::{chat::ChatInfo, role::Role}
+};
+
+#[ic_cdk::query]
+fn list_chats(
+) -> Result<Vec<ChatInfo>, String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Owner) {
+        return Err("Not authorized".to_string());
+    }
+
+    let mut chats = vec![];
+    ChatStorage::for_each(|key, chat| {
+        chats.push(ChatInfo {
+            key: key.clone(),
+            registered_at: chat.registered_at,
+        });
+    });
+
+    Ok(chats)
+}
\ No newline at end of file
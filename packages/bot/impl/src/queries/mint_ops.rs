@@ -0,0 +1,20 @@
+use oc_bots_sdk_canister::env;
+use crate::{
+    storage::{mint_op::MintOpStorage, role::RoleStorage}, 
+    types::{mint_op::MintOp, role::Role}
+};
+
+const MAX_MINT_OPS_PER_PAGE: u32 = 100;
+
+#[ic_cdk::query]
+fn get_mint_ops(
+    open_only: bool,
+    offset: u32,
+    size: u32
+) -> Result<Vec<MintOp>, String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Treasurer) {
+        return Err("Not authorized".to_string());
+    }
+
+    Ok(MintOpStorage::list(open_only, offset as usize, size.min(MAX_MINT_OPS_PER_PAGE) as usize))
+}
\ No newline at end of file
@@ -1 +1,11 @@
-pub mod nft_events;
\ No newline at end of file
+pub mod nft_events;
+pub mod blob_store_stats;
+pub mod upload_status;
+pub mod chats;
+pub mod webhooks;
+pub mod rate_limits;
+pub mod moderation;
+pub mod reports;
+pub mod roles;
+pub mod nft_config;
+pub mod mint_ops;
\ No newline at end of file
@@ -0,0 +1,33 @@
+use oc_bots_sdk_canister::env;
+use crate::{
+    state, 
+    storage::{moderation::ModerationStorage, role::RoleStorage}, 
+    types::{
+        moderation::{ModerationConfig, Refusal}, 
+        role::Role
+    }
+};
+
+const MAX_REFUSALS_PER_PAGE: u32 = 100;
+
+#[ic_cdk::query]
+fn get_moderation_config(
+) -> Result<ModerationConfig, String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Moderator) {
+        return Err("Not authorized".to_string());
+    }
+
+    Ok(state::read(|s| s.moderation().clone()))
+}
+
+#[ic_cdk::query]
+fn get_moderation_refusals(
+    offset: u32,
+    size: u32
+) -> Result<Vec<Refusal>, String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Moderator) {
+        return Err("Not authorized".to_string());
+    }
+
+    Ok(ModerationStorage::list_refusals(offset as usize, size.min(MAX_REFUSALS_PER_PAGE) as usize))
+}
\ No newline at end of file
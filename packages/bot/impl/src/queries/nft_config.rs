@@ -0,0 +1,63 @@
+use oc_bots_sdk_canister::env;
+use crate::{
+    services::nft, 
+    state, 
+    storage::{nft_config::NftConfigStorage, role::RoleStorage}, 
+    types::{
+        nft_col::{MintingPreview, NftCollectionConfig, NftConfigChange, NftConfigStatus}, 
+        role::Role
+    }
+};
+
+const MAX_CHANGES_PER_PAGE: u32 = 100;
+const MAX_PREVIEW_SUPPLIES: usize = 100;
+
+#[ic_cdk::query]
+fn get_nft_config(
+) -> Result<NftConfigStatus, String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Treasurer) {
+        return Err("Not authorized".to_string());
+    }
+
+    Ok(NftConfigStatus {
+        config: nft::read(|s| s.config.clone()),
+        minting_pause: state::read(|s| s.minting_pause().cloned()),
+        phases: state::read(|s| s.mint_phases().to_vec()),
+        num_allowlisted: state::read(|s| s.mint_allowlist().len() as u64),
+    })
+}
+
+#[ic_cdk::query]
+fn get_nft_config_changes(
+    offset: u32,
+    size: u32
+) -> Result<Vec<NftConfigChange>, String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Treasurer) {
+        return Err("Not authorized".to_string());
+    }
+
+    Ok(NftConfigStorage::list_changes(offset as usize, size.min(MAX_CHANGES_PER_PAGE) as usize))
+}
+
+/// Previews the curves at each supply level, with the current config or another one, 
+/// at a timestamp (default: now)
+#[ic_cdk::query]
+fn preview_minting(
+    config: Option<NftCollectionConfig>,
+    supplies: Vec<u32>,
+    timestamp: Option<u64>
+) -> Result<Vec<MintingPreview>, String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Treasurer) {
+        return Err("Not authorized".to_string());
+    }
+
+    if supplies.len() > MAX_PREVIEW_SUPPLIES {
+        return Err(format!("Up to {} supply levels can be previewed", MAX_PREVIEW_SUPPLIES));
+    }
+
+    nft::read(|s| s.preview(
+        config, 
+        &supplies, 
+        timestamp.unwrap_or_else(ic_cdk::api::time)
+    ))
+}
\ No newline at end of file
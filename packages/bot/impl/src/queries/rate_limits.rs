@@ -0,0 +1,16 @@
+use oc_bots_sdk_canister::env;
+use crate::{
+    state, 
+    storage::role::RoleStorage, 
+    types::{rate_limit::RateLimits, role::Role}
+};
+
+#[ic_cdk::query]
+fn get_rate_limits(
+) -> Result<RateLimits, String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Moderator) {
+        return Err("Not authorized".to_string());
+    }
+
+    Ok(state::read(|s| s.rate_limits().clone()))
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+use oc_bots_sdk_canister::env;
+use crate::{
+    storage::{report::ReportStorage, role::RoleStorage}, 
+    types::{
+        report::{AuditEntry, Report, ReportStatus}, 
+        role::Role
+    }
+};
+
+const MAX_REPORTS_PER_PAGE: u32 = 100;
+
+#[ic_cdk::query]
+fn get_reports(
+    status: Option<ReportStatus>,
+    offset: u32,
+    size: u32
+) -> Result<Vec<Report>, String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Moderator) {
+        return Err("Not authorized".to_string());
+    }
+
+    Ok(ReportStorage::list(status, offset as usize, size.min(MAX_REPORTS_PER_PAGE) as usize))
+}
+
+#[ic_cdk::query]
+fn get_audit_log(
+    offset: u32,
+    size: u32
+) -> Result<Vec<AuditEntry>, String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Moderator) {
+        return Err("Not authorized".to_string());
+    }
+
+    Ok(ReportStorage::list_audit(offset as usize, size.min(MAX_REPORTS_PER_PAGE) as usize))
+}
\ No newline at end of file
@@ -0,0 +1,16 @@
+use candid::Principal;
+use oc_bots_sdk_canister::env;
+use crate::{
+    storage::role::RoleStorage, 
+    types::role::RoleGrant
+};
+
+#[ic_cdk::query]
+fn list_roles(
+) -> Result<Vec<(Principal, RoleGrant)>, String> {
+    if !RoleStorage::has_any_role(&env::caller()) {
+        return Err("Not authorized".to_string());
+    }
+
+    Ok(RoleStorage::list())
+}
\ No newline at end of file
@@ -0,0 +1,22 @@
+use bot_api::upload::{UploadId, UploadStatus};
+use oc_bots_sdk_canister::env;
+use crate::storage::upload::UploadStorage;
+
+#[ic_cdk::query]
+fn get_upload_status(
+    upload_id: UploadId
+) -> Option<UploadStatus> {
+    let session = UploadStorage::load(upload_id)?;
+    if session.owner != env::caller() {
+        return None;
+    }
+
+    Some(UploadStatus {
+        missing: UploadStorage::missing_chunks(upload_id, &session),
+        num_chunks: session.num_chunks(),
+        target: session.target,
+        total_size: session.total_size,
+        chunk_size: session.chunk_size,
+        expires_at: session.expires_at,
+    })
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+use oc_bots_sdk_canister::env;
+use crate::{
+    storage::{role::RoleStorage, webhook::WebhookStorage}, 
+    types::{
+        role::Role, 
+        webhook::{WebhookDelivery, WebhookInfo}
+    }
+};
+
+#[ic_cdk::query]
+fn list_webhooks(
+) -> Result<Vec<WebhookInfo>, String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Owner) {
+        return Err("Not authorized".to_string());
+    }
+
+    let mut webhooks = vec![];
+    WebhookStorage::for_each(|name, webhook| {
+        webhooks.push(WebhookInfo {
+            name: name.clone(),
+            chats: webhook.chats.clone(),
+            enabled: webhook.enabled,
+            created_at: webhook.created_at,
+        });
+    });
+
+    Ok(webhooks)
+}
+
+#[ic_cdk::query]
+fn get_webhook_deliveries(
+    offset: u32,
+    size: u32
+) -> Result<Vec<WebhookDelivery>, String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Owner) {
+        return Err("Not authorized".to_string());
+    }
+
+    Ok(WebhookStorage::list_deliveries(offset as _, size.min(100) as _))
+}
\ No newline at end of file
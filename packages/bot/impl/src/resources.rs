@@ -1,2 +1,6 @@
 pub const ROBOTO_TTF: &[u8] = include_bytes!("../resources/Roboto-CondensedBold.ttf");
 pub const LOGO: &[u8] = include_bytes!("../resources/logo.png");
+
+// served in place of the NFT images taken down
+pub const NFT_PLACEHOLDER: &[u8] = LOGO;
+pub const NFT_PLACEHOLDER_MIME_TYPE: &str = "image/png";
\ No newline at end of file
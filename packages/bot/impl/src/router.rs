@@ -1,11 +1,17 @@
-use ic_http_certification::{HttpRequest, HttpResponse};
+use ic_http_certification::HttpRequest;
 use oc_bots_sdk_canister::{HttpMethod::*, HttpRouter};
 use std::sync::LazyLock;
+use crate::{
+    services::cert::{self, ResponseKind}, 
+    types::http::HttpResponse
+};
 
 mod definition;
 mod commands;
-mod blobs;
+pub mod blobs;
 mod assets;
+mod metrics;
+mod webhooks;
 
 static ROUTER: LazyLock<HttpRouter> = LazyLock::new(init_router);
 
@@ -13,10 +19,8 @@ fn init_router(
 ) -> HttpRouter {
     HttpRouter::default()
         .route("/execute_command", POST, commands::execute)
-        //.route("/webhook/*", POST, webhooks::execute)
-        //.route("/metrics", GET, metrics::get)
-        .route("/blobs/*", GET, blobs::get)
-        .route("/assets/*", GET, assets::get)
+        .route("/webhook/*", POST, webhooks::execute)
+        .route("/metrics", GET, metrics::get)
         .fallback(definition::get)
 }
 
@@ -24,5 +28,28 @@ pub async fn handle(
     request: HttpRequest, 
     query: bool
 ) -> HttpResponse {
-    ROUTER.handle(request, query).await
+    // blobs and assets are served here, as they need the request headers and 
+    // responses that can be streamed and certified
+    if request.method.eq_ignore_ascii_case("GET") {
+        if let Ok(path) = request.get_path() {
+            if path.starts_with("/blobs/") {
+                return blobs::get(&request, &path, query);
+            }
+            else if path.starts_with("/assets/") {
+                return assets::get(&request, &path, query);
+            }
+        }
+    }
+
+    let path = request.get_path().ok();
+    let mut response = ROUTER.handle(request, query).await;
+
+    // dynamic responses can't be certified, so the gateway is told to skip verifying them
+    if query {
+        if let Some(path) = path {
+            cert::read(|s| s.add_certificate_headers(&path, ResponseKind::Skip, &mut response));
+        }
+    }
+
+    response.into()
 }
\ No newline at end of file
@@ -1,20 +1,56 @@
-use oc_bots_sdk_canister::{HttpRequest, HttpResponse};
+use ic_http_certification::HttpRequest;
+use sha2::{Digest, Sha256};
 use crate::{
+    services::cert::ResponseKind, 
     storage::asset::AssetStorage, 
-    types::asset::AssetPath
+    types::{
+        asset::{AssetPath, AssetSource}, 
+        http
+    }
+};
+use super::blobs::{
+    self, certified, header, matches_etag, 
+    not_found, response, IMMUTABLE_CACHE_CONTROL
 };
 
-pub async fn get(
-    request: HttpRequest
-) -> HttpResponse {
-    let Ok(asset_id) = request.path.trim_start_matches("/assets/").parse::<AssetPath>();
+// uploaded assets can be replaced by the administrator, so they must be revalidated
+const ASSET_CACHE_CONTROL: &str = "public, max-age=3600, must-revalidate";
+
+pub fn get(
+    request: &HttpRequest,
+    path: &str,
+    query: bool
+) -> http::HttpResponse {
+    let asset_path: AssetPath = path.trim_start_matches("/assets/").to_string();
+
+    match AssetStorage::resolve(&asset_path) {
+        Some(AssetSource::Blob(blob_id)) => {
+            let cache_control = if asset_path.starts_with("nfts/") {
+                IMMUTABLE_CACHE_CONTROL
+            }
+            else {
+                ASSET_CACHE_CONTROL
+            };
+            
+            blobs::serve(request, path, blob_id, cache_control, query)
+        },
+        Some(AssetSource::Static(asset)) => {
+            let etag = format!("\"{}\"", hex::encode(Sha256::digest(&asset.data)));
+            let headers = vec![
+                header("Content-Type", &asset.mime_type),
+                header("Cache-Control", ASSET_CACHE_CONTROL),
+                header("ETag", &etag),
+            ];
 
-    match AssetStorage::load(asset_id) {
-        Some(asset) => {
-            HttpResponse::new(200, asset.data, &asset.mime_type)
+            if matches_etag(request, &etag) {
+                certified(path, ResponseKind::Full(304), response(304, headers, vec![]), query).into()
+            }
+            else {
+                certified(path, ResponseKind::Full(200), response(200, headers, asset.data), query).into()
+            }
         },
         None => {
-            HttpResponse::not_found()
+            not_found()
         }
     }
-}
+}
\ No newline at end of file
@@ -1,22 +1,366 @@
-use oc_bots_sdk_canister::{HttpRequest, HttpResponse};
+use ic_http_certification::{HttpRequest, HttpResponse};
 use crate::{
-    storage::blob::BlobStorage, 
-    types::blob::BlobId
+    services::{
+        cert::{self, ResponseKind}, 
+        rate_limit::RateLimitService
+    }, 
+    state, 
+    storage::{
+        blob::BlobStorage, 
+        derived_blob::DerivedBlobStorage, 
+        temp_blob::TempBlobStorage
+    }, 
+    types::{
+        blob::{BlobId, BlobMeta}, 
+        derived_blob::{ImageTransform, TransformFormat, TRANSFORM_SIZES}, 
+        http::{self, StreamingCallback, StreamingStrategy, StreamingToken}, 
+        temp_blob::TEMP_BLOB_ID_LIMIT
+    }, 
+    utils::{
+        http::{get_header, get_query_param, parse_range}, 
+        image
+    }
 };
 
-pub async fn get(
-    request: HttpRequest
-) -> HttpResponse {
-    let Ok(blob_id) = request.path.trim_start_matches("/blobs/").parse::<BlobId>() else {
-        return HttpResponse::not_found();
+// permanent blobs are content addressed, so they never change
+pub(super) const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+// derived blobs are not chunked, so they must fit in a single message
+const MAX_DERIVED_BLOB_SIZE: usize = 1536 * 1024;
+
+pub fn get(
+    request: &HttpRequest,
+    path: &str,
+    query: bool
+) -> http::HttpResponse {
+    let Ok(blob_id) = path.trim_start_matches("/blobs/").parse::<BlobId>() else {
+        return not_found();
     };
 
-    match BlobStorage::load(blob_id) {
-        Some(blob) => {
-            HttpResponse::new(200, blob.data, &blob.mime_type)
+    serve(request, path, blob_id, IMMUTABLE_CACHE_CONTROL, query)
+}
+
+pub fn serve(
+    request: &HttpRequest,
+    path: &str,
+    blob_id: BlobId,
+    cache_control: &str,
+    query: bool
+) -> http::HttpResponse {
+    if blob_id < TEMP_BLOB_ID_LIMIT {
+        return serve_temp(path, blob_id, query);
+    }
+
+    // taken down by the administrator
+    if state::read(|s| s.takedowns().hidden_blobs.contains(&blob_id)) {
+        return not_found();
+    }
+
+    let meta = match BlobStorage::load_meta(blob_id) {
+        Some(meta) => {
+            meta
+        },
+        // legacy blobs not indexed yet. Indexing certifies the blob, which a query 
+        // call can't persist, so the request is upgraded to an update call
+        None if BlobStorage::is_legacy(blob_id) => {
+            if query {
+                return http::HttpResponse {
+                    upgrade: Some(true),
+                    ..response(200, vec![], vec![]).into()
+                };
+            }
+
+            match BlobStorage::index_legacy_blob(blob_id) {
+                Some(meta) => meta,
+                None => return not_found(),
+            }
         },
         None => {
-            HttpResponse::not_found()
+            return not_found();
+        }
+    };
+
+    match parse_transform(request) {
+        Ok(Some(transform)) => {
+            return serve_derived(request, path, blob_id, &meta, &transform, cache_control, query);
+        },
+        Ok(None) => {
+        },
+        Err(err) => {
+            return response(400, vec![header("Content-Type", "text/plain")], err.into_bytes()).into();
         }
     }
+
+    let etag = format!("\"{}\"", hex::encode(meta.hash));
+    let mut headers = vec![
+        header("Content-Type", &meta.mime_type),
+        header("Cache-Control", cache_control),
+        header("ETag", &etag),
+    ];
+
+    // only blobs with certified chunks can be served partially
+    if meta.chunk_hashes.is_some() {
+        headers.push(header("Accept-Ranges", "bytes"));
+    }
+
+    if matches_etag(request, &etag) {
+        return certified(path, ResponseKind::Full(304), response(304, headers, vec![]), query).into();
+    }
+
+    let size = meta.size as u64;
+
+    // only whole chunks are certified, so a range is served only when it matches one 
+    // exactly. Any other range is ignored and the blob is served in full
+    let range = get_header(request, "Range")
+        .and_then(|value| parse_range(value, size))
+        .and_then(|(start, end)| meta.chunk_index(start, end).map(|index| (index, start, end)));
+
+    if let Some((index, start, end)) = range {
+        let Some(data) = BlobStorage::load_chunk(blob_id, index) else {
+            return not_found();
+        };
+
+        headers.push(header("Content-Range", &meta.content_range(start, end)));
+        headers.push(header("Content-Length", &data.len().to_string()));
+        return certified(path, ResponseKind::Range(index), response(206, headers, data), query).into();
+    }
+
+    // the first chunk goes in the body, the remaining ones are pulled by the gateway
+    let Some(body) = BlobStorage::load_chunk(blob_id, 0) else {
+        return not_found();
+    };
+
+    headers.push(header("Content-Length", &size.to_string()));
+    let mut res: http::HttpResponse = certified(path, ResponseKind::Full(200), response(200, headers, body), query).into();
+
+    if meta.chunks > 1 {
+        res.streaming_strategy = Some(StreamingStrategy::Callback { 
+            callback: StreamingCallback::new(
+                ic_cdk::id(), 
+                "http_request_streaming_callback".to_string()
+            ), 
+            token: StreamingToken { 
+                blob_id, 
+                index: 1 
+            } 
+        });
+    }
+
+    res
 }
+
+pub fn next_chunk(
+    token: StreamingToken
+) -> http::StreamingCallbackHttpResponse {
+    let num_chunks = BlobStorage::load_meta(token.blob_id)
+        .map(|m| m.chunks)
+        .unwrap_or(0);
+
+    let body = BlobStorage::load_chunk(token.blob_id, token.index)
+        .unwrap_or_default();
+
+    let next = token.index + 1;
+    http::StreamingCallbackHttpResponse { 
+        body, 
+        token: if next < num_chunks {
+            Some(StreamingToken { 
+                blob_id: token.blob_id, 
+                index: next 
+            })
+        }
+        else {
+            None
+        }
+    }
+}
+
+/// Serves a resized/re-encoded version of a blob. Transforming is too expensive 
+/// for a query call (and neither its result nor its certification could be kept), 
+/// so on a cache miss the request is upgraded to an update call
+fn serve_derived(
+    request: &HttpRequest,
+    path: &str,
+    blob_id: BlobId,
+    meta: &BlobMeta,
+    transform: &ImageTransform,
+    cache_control: &str,
+    query: bool
+) -> http::HttpResponse {
+    // only blobs kept by posts, NFTs or assets can be transformed, not previews or released ones
+    if !meta.is_referenced() {
+        return not_found();
+    }
+
+    let key = transform.key();
+    let etag = format!("\"{}-{:x}\"", hex::encode(meta.hash), key);
+
+    let cached = DerivedBlobStorage::load(blob_id, transform);
+    let certified_here = cert::read(|c| c.is_certified(path, ResponseKind::Derived(key, 200)));
+    
+    // the cached blob is certified on each path it's requested through
+    if query && (cached.is_none() || !certified_here) {
+        return http::HttpResponse {
+            upgrade: Some(true),
+            ..response(200, vec![], vec![]).into()
+        };
+    }
+
+    let blob = match cached {
+        Some(derived) => {
+            if !certified_here {
+                cert::mutate(|c| c.certify_derived(
+                    path, 
+                    (blob_id, key), 
+                    &derived.blob.mime_type, 
+                    derived.hash
+                ));
+            }
+            derived.blob
+        },
+        None => {
+            if let Err(wait) = RateLimitService::check_transform() {
+                return response(429, vec![header("Retry-After", &wait.to_string())], vec![]).into();
+            }
+
+            let Some(source) = BlobStorage::load(blob_id) else {
+                return not_found();
+            };
+
+            let blob = match image::transform(&source.data, &source.mime_type, transform) {
+                Ok(blob) => blob,
+                Err(err) => {
+                    return response(415, vec![header("Content-Type", "text/plain")], err.into_bytes()).into();
+                }
+            };
+
+            if blob.data.len() > MAX_DERIVED_BLOB_SIZE {
+                return response(413, vec![], vec![]).into();
+            }
+
+            let hash = DerivedBlobStorage::save(blob_id, transform, blob.clone());
+            cert::mutate(|c| c.certify_derived(path, (blob_id, key), &blob.mime_type, hash));
+            blob
+        }
+    };
+
+    let mut headers = vec![
+        header("Content-Type", &blob.mime_type),
+        header("Cache-Control", cache_control),
+        header("ETag", &etag),
+    ];
+
+    if matches_etag(request, &etag) {
+        return certified(path, ResponseKind::Derived(key, 304), response(304, headers, vec![]), query).into();
+    }
+
+    headers.push(header("Content-Length", &blob.data.len().to_string()));
+    certified(path, ResponseKind::Derived(key, 200), response(200, headers, blob.data), query).into()
+}
+
+fn parse_transform(
+    request: &HttpRequest
+) -> Result<Option<ImageTransform>, String> {
+    let dimension = |name: &str| -> Result<Option<u32>, String> {
+        get_query_param(request, name)
+            .map(|value| match value.parse::<u32>() {
+                Ok(value) if TRANSFORM_SIZES.contains(&value) => Ok(value),
+                _ => Err(format!(
+                    "{} must be one of {}", 
+                    name, 
+                    TRANSFORM_SIZES.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(", ")
+                )),
+            })
+            .transpose()
+    };
+
+    let width = dimension("w")?;
+    let height = dimension("h")?;
+    let format = get_query_param(request, "fmt")
+        .map(|value| TransformFormat::from_param(value)
+            .ok_or_else(|| format!("unsupported format: {}", value))
+        )
+        .transpose()?;
+
+    if width.is_none() && height.is_none() && format.is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(ImageTransform {
+        width,
+        height,
+        format,
+    }))
+}
+
+pub(super) fn matches_etag(
+    request: &HttpRequest,
+    etag: &str
+) -> bool {
+    get_header(request, "If-None-Match")
+        .map(|value| value.split(',').any(|tag| tag.trim() == etag || tag.trim() == "*"))
+        .unwrap_or(false)
+}
+
+fn serve_temp(
+    path: &str,
+    blob_id: BlobId,
+    query: bool
+) -> http::HttpResponse {
+    match TempBlobStorage::load(blob_id) {
+        Ok(temp) => {
+            // temp blobs expire, so they must not be cached for longer than their ttl
+            let max_age = temp.created_at.saturating_add(temp.ttl)
+                .saturating_sub(ic_cdk::api::time()) / 1_000_000_000;
+
+            certified(path, ResponseKind::Full(200), response(
+                200, 
+                vec![
+                    header("Content-Type", &temp.blob.mime_type),
+                    header("Cache-Control", &format!("private, max-age={}", max_age)),
+                ], 
+                temp.blob.data
+            ), query).into()
+        },
+        Err(_) => {
+            not_found()
+        }
+    }
+}
+
+pub(super) fn certified(
+    path: &str,
+    kind: ResponseKind,
+    mut response: HttpResponse,
+    query: bool
+) -> HttpResponse {
+    // only query calls have access to the data certificate
+    if query {
+        cert::read(|s| s.add_certificate_headers(path, kind, &mut response));
+    }
+
+    response
+}
+
+pub(super) fn response(
+    status_code: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>
+) -> HttpResponse {
+    HttpResponse {
+        status_code,
+        headers,
+        body,
+        upgrade: None,
+    }
+}
+
+pub(super) fn header(
+    name: &str,
+    value: &str
+) -> (String, String) {
+    (name.to_string(), value.to_string())
+}
+
+pub(super) fn not_found(
+) -> http::HttpResponse {
+    response(404, vec![], vec![]).into()
+}
\ No newline at end of file
@@ -10,10 +10,18 @@ use oc_bots_sdk_canister::{HttpRequest, HttpResponse};
 use std::sync::LazyLock;
 
 mod cli;
+mod settings;
+mod sync_api_key;
+mod typed;
 
 static COMMANDS: LazyLock<CommandHandlerRegistry<CanisterRuntime>> = LazyLock::new(|| {
-    CommandHandlerRegistry::new(OPENCHAT_CLIENT_FACTORY.clone())
-        .register(MemeCli)
+    typed::commands().into_iter()
+        .fold(
+            CommandHandlerRegistry::new(OPENCHAT_CLIENT_FACTORY.clone())
+                .register(MemeCli),
+            |registry, command| registry.register(command)
+        )
+        .on_sync_api_key(Box::new(sync_api_key::callback))
 });
 
 pub fn definitions(
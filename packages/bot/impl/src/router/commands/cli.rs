@@ -1,10 +1,7 @@
 use std::{collections::HashSet, io::Cursor, sync::LazyLock, u32};
 use async_trait::async_trait;
 use candid::Principal;
-use clap::Parser;
-use ic_ledger_types::{
-    AccountIdentifier, DEFAULT_FEE, DEFAULT_SUBACCOUNT
-};
+use clap::{CommandFactory, FromArgMatches};
 use oc_bots_sdk::{
     api::{
         command::{
@@ -16,42 +13,67 @@ use oc_bots_sdk::{
         actions::{
             chat_details, chat_events::{
                 self, EventsSelectionCriteria, EventsWindowArgs
-            }, send_message, ActionArgsBuilder
+            }, members, send_message, ActionArgsBuilder
         }, 
         client::Client
     }, 
     types::{
         ActionContext, BlobReference, BotCommandContext, BotCommandScope, 
-        Chat, ImageContent, MessageContentInitial
+        Chat, ImageContent, MemberType, MessageContentInitial, UserId
     }
 };
 use oc_bots_sdk_canister::{env, CanisterRuntime};
 use crate::{
     services::{
         meme::{self, MemeService},
-        nft::{self, NftService, TOKENS_PER_PAGE}, 
-        wallet::wallet::WalletService
+        mint_op::mint_op::MintOpService, 
+        moderation::moderation::ModerationService, 
+        nft::{self, MintError, MintLockKey, NftService, TOKENS_PER_PAGE}, 
+        rate_limit::rate_limit::{RateLimitError, RateLimitService}, 
+        token::token::TokenService, 
+        wallet::wallet::{WalletError, WalletService}
     }, 
     state, 
     storage::{
         blob::BlobStorage, 
+        chat::ChatStorage, 
         nft::NftStorage, 
+        nft_config::NftConfigStorage, 
+        report::ReportStorage, 
+        role::RoleStorage, 
+        schedule::ScheduleStorage, 
+        temp_blob::TempBlobStorage, 
         user::UserStorage
     }, 
     types::{
-        blob::Blob, 
+        blob::{Blob, BlobRefKind}, 
+        chat::{ChatSettings, NsfwStrictness}, 
         cli::{self, Cli, Commands}, 
         image::{IMAGE_FORMAT, IMAGE_HEIGHT, IMAGE_WIDTH}, 
         meme::MemeId, 
+        mint_op::{MintOpStep, MintPayment}, 
+        moderation::ModerationStage, 
         nft::{Nft, NftId}, 
-        user::{UserMeme, UserMint, UserPost, UserTransaction}
+        nft_col::{NftConfigChangeKind, TEAM_FEE_P_DECIMALS, TEAM_FEE_P_DENOMINATOR}, 
+        report::{PostedMeme, ReportTarget}, 
+        role::Role, 
+        schedule::{
+            next_occurrence, Job, JobKind, 
+            DAY, HOUR, MAX_JOBS_PER_CHAT, WEEK
+        }, 
+        temp_blob::TempBlobError, 
+        token::{format_decimal, parse_decimal, Token, TokenKind, ICP_DECIMALS}, 
+        user::{TxReason, UserMeme, UserMint, UserPost, UserTransaction}
     }, 
     utils::{
+        i18n::{localize_command, tr, Locale}, 
         image::{create_thumbnail, rgba8_to_rgb8}, 
-        oc::{get_chat_user_profile, get_user_pub_profile}, 
-        out_font::OutlinedFont
+        oc::{chat_key, get_chat_user_profile, get_user_pub_profile}, 
+        out_font::OutlinedFont, 
+        time::format_utc
     }
 };
+use super::settings::ChatSettingsCommand;
 
 static DEFINITION: LazyLock<BotCommandDefinition> = LazyLock::new(MemeCli::definition);
 
@@ -77,127 +99,218 @@ impl CommandHandler<CanisterRuntime> for MemeCli {
             &format!("/meme {}", client.context().command.arg::<String>("args"))
         ).unwrap();
 
+        let settings = match &client.context().scope {
+            BotCommandScope::Chat(chat_scope) => ChatStorage::load_settings(&chat_key(&chat_scope.chat)),
+            _ => ChatSettings::default(),
+        };
+
+        // the help is rendered in the language of the chat or of the user
+        match localize_command(Cli::command(), Self::locale(&settings, &client), "meme")
+            .try_get_matches_from(args)
+            .and_then(|matches| Cli::from_arg_matches(&matches)) {
+            Ok(cli) => {
+                Self::dispatch(cli.command, &client).await
+            },
+            Err(err) => {
+                state::mutate(|s| s.metrics_mut().record_command("invalid", true));
+                Self::reply(Err(render_error(err)), &client)
+            },
+        }
+    }
+}
+
+/// Renders the help or the parsing error returned by clap as html
+pub(super) fn render_error(
+    err: clap::Error
+) -> String {
+    match err.kind() {
+        clap::error::ErrorKind::DisplayVersion => {
+            err.to_string()
+        },
+        _ => {
+            ansi_to_html::Converter::new()
+                .convert(
+                    &err.render()
+                        .ansi()
+                        .to_string()
+                ).unwrap()
+        }
+    }
+}
+
+impl MemeCli {
+    /// Runs a command, parsed either by the CLI or from the typed arguments of one of the 
+    /// '/meme_*' commands, after enforcing the chat policy
+    pub(super) async fn dispatch(
+        command: Commands,
+        client: &Client<CanisterRuntime, BotCommandContext>
+    ) -> Result<SuccessResult, String> {
         let user_id = Principal::from_text(
             client.context().command.initiator.to_string()
         ).unwrap();
 
         let BotCommandScope::Chat(chat_scope) = &client.context().scope else {
-            return Err("This command can only be used in a chat".to_string());
+            return Err(tr(Self::locale(&ChatSettings::default(), client), "chat_only", &[]));
         };
 
         let chat = chat_scope.chat;
+        let settings = ChatStorage::load_settings(&chat_key(&chat));
+        let locale = Self::locale(&settings, client);
+        let command_name = command.name();
 
-        let res = match Cli::try_parse_from(args) {
-            Ok(cli) => {
-                match cli.command {
-                    Commands::Search { query, page } => {
-                        Self::search_meme(query, page.max(1) - 1, &client)
-                    },
-                    Commands::Gen { id, captions } => {
-                        Self::gen_meme(id, captions, user_id, &client)
-                    },
-                    Commands::Suggest { id, mood, topic } => {
-                        Self::suggest_meme(
-                            id, 
-                            mood.unwrap_or("funny".to_string()), 
-                            topic.unwrap_or("crypto".to_string()), 
-                            user_id, 
-                            &client
-                        ).await
-                    },
-                    Commands::Post { id } => {
-                        Self::post_meme(id, user_id, &client)
-                    },
-                    Commands::Wallet (command) => {
-                        match command {
-                            cli::Wallet::Balance => {
-                                Self::wallet_balance(user_id, &client)
-                                    .await
-                            },
-                            cli::Wallet::Address => {
-                                Self::wallet_address(user_id, &client)
-                                    .await
-                            },
-                            cli::Wallet::Withdraw { to, amount } => {
-                                Self::wallet_withdraw(user_id, to, amount, &client)
-                                    .await
-                            },
-                            cli::Wallet::Logs { page } => {
-                                Self::wallet_logs(
+        // enforce the chat policy and the rate limits before dispatching
+        let res = if let Err(err) = Self::check_policy(&command, &settings, locale) {
+            Err(err)
+        }
+        else if let Err(err) = RateLimitService::check(user_id, &chat_key(&chat), command.rate_limit_class()) {
+            state::mutate(|s| s.metrics_mut().record_rate_limited());
+            Err(match err {
+                RateLimitError::User(secs) => tr(locale, "rate_limited.user", &[&secs]),
+                RateLimitError::Chat(secs) => tr(locale, "rate_limited.chat", &[&secs]),
+            })
+        }
+        else {
+            match command {
+                Commands::Search { query, page } => {
+                    Self::search_meme(query, page.max(1) - 1, locale, client)
+                },
+                Commands::Gen { id, captions } => {
+                    Self::gen_meme(id, captions, user_id, &chat, settings.nsfw, locale, client)
+                        .await
+                },
+                Commands::Suggest { id, mood, topic } => {
+                    Self::suggest_meme(
+                        id, 
+                        mood.or(settings.default_mood).unwrap_or("funny".to_string()), 
+                        topic.or(settings.default_topic).unwrap_or("crypto".to_string()), 
+                        settings.language, 
+                        user_id, 
+                        &chat, 
+                        settings.nsfw, 
+                        locale, 
+                        client
+                    ).await
+                },
+                Commands::Post { id } => {
+                    Self::post_meme(id, user_id, &chat, locale, client)
+                },
+                Commands::Report { id, reason } => {
+                    Self::report(id, reason, user_id, &chat, locale, client)
+                },
+                Commands::Wallet (command) => {
+                    match command {
+                        cli::Wallet::Balance { token } => {
+                            Self::wallet_balance(token, user_id, locale, client)
+                                .await
+                        },
+                        cli::Wallet::Address { token } => {
+                            Self::wallet_address(token, user_id, locale, client)
+                                .await
+                        },
+                        cli::Wallet::Withdraw { to, amount, all, token } => {
+                            Self::wallet_withdraw(token, user_id, to, amount, all, locale, client)
+                                .await
+                        },
+                        cli::Wallet::Logs { page, token } => {
+                            Self::wallet_logs(
+                                token,
+                                user_id,
+                                page.max(1) - 1,
+                                locale,
+                                client
+                            ).await
+                        },
+                    }
+                },
+                Commands::Nft (command) => {
+                    match command {
+                        cli::Nft::Mint { id } => {
+                            Self::nft_mint(id, user_id, &chat, settings.nsfw, locale, client)
+                                .await
+                        },
+                        cli::Nft::Balance { owner } => {
+                            Self::nft_balance_of(
+                                owner.map(|t| Principal::from_text(t).unwrap())
+                                    .or(Some(user_id)).unwrap(), 
+                                locale, 
+                                client
+                            ).await
+                        },
+                        cli::Nft::Tokens { owner, page } => {
+                            Self::nft_tokens_of(
+                                owner.map(|t| Principal::from_text(t).unwrap())
+                                    .or(Some(user_id)).unwrap(), 
+                                page.max(1) - 1,
+                                locale,
+                                client
+                            ).await
+                        },
+                        cli::Nft::Status { } => { 
+                            Self::nft_status(
+                                locale,
+                                client
+                            ).await
+                        },
+                        cli::Nft::Transfer { id, to } => { 
+                            if let Ok(to) = Principal::from_text(to.clone()) {
+                                Self::nft_transfer_from(
                                     user_id,
-                                    page.max(1) - 1,
-                                    &client
-                                )
-                            },
-                        }
-                    },
-                    Commands::Nft (command) => {
-                        match command {
-                            cli::Nft::Mint { id } => {
-                                Self::nft_mint(id, user_id, &chat, &client)
-                                    .await
-                            },
-                            cli::Nft::Balance { owner } => {
-                                Self::nft_balance_of(
-                                    owner.map(|t| Principal::from_text(t).unwrap())
-                                        .or(Some(user_id)).unwrap(), 
-                                    &client
+                                    id,
+                                    to,
+                                    locale,
+                                    client
                                 ).await
-                            },
-                            cli::Nft::Tokens { owner, page } => {
-                                Self::nft_tokens_of(
-                                    owner.map(|t| Principal::from_text(t).unwrap())
-                                        .or(Some(user_id)).unwrap(), 
-                                    page.max(1) - 1,
-                                    &client
-                                ).await
-                            },
-                            cli::Nft::Status { } => { 
-                                Self::nft_status(
-                                    &client
-                                ).await
-                            },
-                            cli::Nft::Transfer { id, to } => { 
-                                if let Ok(to) = Principal::from_text(to.clone()) {
-                                    Self::nft_transfer_from(
-                                        user_id,
-                                        id,
-                                        to,
-                                        &client
-                                    ).await
-                                }
-                                else {
-                                    Err(format!("Invalid principal for 'to': {}", to))
-                                }
-                            },
-                            cli::Nft::Logs { page } => {
-                                Self::nft_logs(
-                                    user_id,
-                                    page.max(1) - 1,
-                                    &client
-                                )
-                            },
-                        }
-                    },
-                }
-            },
-            Err(err) => {
-                Err(match err.kind() {
-                    clap::error::ErrorKind::DisplayVersion => {
-                        err.to_string()
-                    },
-                    _ => {
-                        ansi_to_html::Converter::new()
-                            .convert(
-                                &err.render()
-                                    .ansi()
-                                    .to_string()
-                            ).unwrap()
+                            }
+                            else {
+                                Err(tr(locale, "invalid_principal", &[&to]))
+                            }
+                        },
+                        cli::Nft::Logs { page } => {
+                            Self::nft_logs(
+                                user_id,
+                                page.max(1) - 1,
+                                locale,
+                                client
+                            )
+                        },
                     }
-                })
-            },
+                },
+                Commands::Schedule (command) => {
+                    // anyone can list the jobs, but only the chat admins can change them
+                    if !matches!(command, cli::Schedule::List) && 
+                        !Self::is_chat_admin(user_id, client).await {
+                        Err(tr(locale, "not_authorized", &[]))
+                    }
+                    else {
+                        Self::schedule(command, user_id, &chat, locale, client)
+                    }
+                },
+                Commands::Settings (command) => {
+                    // anyone can see the settings, but only the chat admins can change them
+                    if !matches!(command, cli::Settings::Show) && 
+                        !Self::is_chat_admin(user_id, client).await {
+                        Err(tr(locale, "not_authorized", &[]))
+                    }
+                    else {
+                        Self::settings(command, user_id, &chat, locale, client)
+                    }
+                },
+                Commands::Admin (command) => {
+                    Self::admin(command, user_id, locale, client)
+                },
+            }
         };
 
+        state::mutate(|s| s.metrics_mut().record_command(command_name, res.is_err()));
+
+        Self::reply(res, client)
+    }
+
+    /// Errors are shown to the user as ephemeral messages
+    pub(super) fn reply(
+        res: Result<SuccessResult, String>,
+        client: &Client<CanisterRuntime, BotCommandContext>
+    ) -> Result<SuccessResult, String> {
         match res {
             Ok(success_res) => {
                 Ok(success_res)
@@ -214,12 +327,48 @@ impl CommandHandler<CanisterRuntime> for MemeCli {
             }
         }
     }
-}
 
-impl MemeCli {
+    /// Locale of the responses to a command, outside of a chat only the language of the user counts
+    pub(super) fn command_locale(
+        client: &Client<CanisterRuntime, BotCommandContext>
+    ) -> Locale {
+        let settings = match &client.context().scope {
+            BotCommandScope::Chat(chat_scope) => ChatStorage::load_settings(&chat_key(&chat_scope.chat)),
+            _ => ChatSettings::default(),
+        };
+
+        Self::locale(&settings, client)
+    }
+
+    /// The locale set by the chat admins takes precedence over the language of the user
+    fn locale(
+        settings: &ChatSettings,
+        client: &Client<CanisterRuntime, BotCommandContext>
+    ) -> Locale {
+        settings.locale.as_deref()
+            .and_then(Locale::from_code)
+            .or_else(|| client.context().command.meta.as_ref()
+                .and_then(|meta| Locale::from_code(&meta.language))
+            )
+            .unwrap_or_default()
+    }
+
+    fn check_policy(
+        command: &Commands,
+        settings: &ChatSettings,
+        locale: Locale
+    ) -> Result<(), String> {
+        if settings.is_command_disabled(command.name()) {
+            return Err(tr(locale, "command_disabled", &[&command.name()]));
+        }
+
+        Ok(())
+    }
+
     fn search_meme(
         query: String,
         page: usize, 
+        locale: Locale,
         client: &Client<CanisterRuntime, BotCommandContext>
     ) -> Result<SuccessResult, String> {
         // find the templates that match the query used
@@ -254,7 +403,7 @@ impl MemeCli {
             let blob_id = BlobStorage::save(Blob {
                 data: jpeg,
                 mime_type: IMG_FORMAT.to_mime_type().to_string()
-            }, true);
+            }, BlobRefKind::Preview);
 
             // return a message to user only
             Ok(EphemeralMessageBuilder::new(
@@ -262,7 +411,7 @@ impl MemeCli {
                     mime_type: IMG_FORMAT.to_mime_type().to_string(),
                     width: preview.width(),
                     height: preview.height(),
-                    caption: Some(format!("Page {}/{}", page.min(num_pages-1)+1, num_pages)),
+                    caption: Some(tr(locale, "page", &[&(page.min(num_pages-1)+1), &num_pages])),
                     blob_reference: Some(BlobReference {
                         canister_id: env::canister_id(),
                         blob_id,
@@ -273,46 +422,55 @@ impl MemeCli {
             ).build().into())
         }
         else {
-            Err("No memes found :/. Try again!".to_string())
+            Err(tr(locale, "search.not_found", &[]))
         }
     }
 
-    fn gen_meme(
+    async fn gen_meme(
         tpl_id: u32,
         captions: Vec<String>,
         user_id: Principal,
+        chat: &Chat,
+        nsfw: NsfwStrictness,
+        locale: Locale,
         client: &Client<CanisterRuntime, BotCommandContext>
     ) -> Result<SuccessResult, String> {
         if let Some(tpl) = meme::read(|s| 
             s.load(&tpl_id).cloned()
         ) {
+            // moderate the captions before rendering them
+            if ModerationService::check(
+                &captions, 
+                nsfw, 
+                ModerationStage::Render, 
+                Some(user_id), 
+                Some(chat_key(chat))
+            ).await.is_err() {
+                return Err(tr(locale, "moderation.refused", &[]));
+            }
+
             // gen the image
-            let img = OutlinedFont::roboto(|font| {
-                rgba8_to_rgb8(&MemeService::gen_image(
-                    &tpl, 
-                    &captions.iter().map(|t| t.to_uppercase()).collect(), 
-                    &font
-                ).unwrap())
-            });
+            let (jpeg, width, height) = MemeService::gen_jpeg(&tpl, &captions)?;
 
-            let mut jpeg: Vec<u8> = Vec::new();
-            img.write_to(&mut Cursor::new(&mut jpeg), IMG_FORMAT)
-                .map_err(|e| e.to_string())?;
+            state::mutate(|s| s.meme_tpl_usage_mut().record(
+                tpl_id, 
+                ic_cdk::api::time() / WEEK
+            ));
 
             let thumbnail_data = create_thumbnail(
                 &jpeg, 
-                img.width() / 5,
-                img.height() / 5,
+                width / 5,
+                height / 5,
                 IMG_FORMAT
             )?;
 
             // add meme generated to user DB
             let mut user = UserStorage::load(&user_id);
 
-            let temp_blob_id = BlobStorage::save(Blob {
+            let temp_blob_id = TempBlobStorage::save(Blob {
                 data: jpeg,
                 mime_type: IMG_FORMAT.to_mime_type().to_string()
-            }, true);
+            }, user_id);
 
             let meme_id = meme::mutate(|s| {
                 s.calc_id(
@@ -326,6 +484,7 @@ impl MemeCli {
                 UserMeme::new(
                     meme_id.clone(),
                     temp_blob_id,
+                    captions
                 )
             );
             user.memes.last = Some(meme_id.clone());
@@ -335,9 +494,9 @@ impl MemeCli {
             Ok(EphemeralMessageBuilder::new(
                 MessageContentInitial::Image(ImageContent {
                     mime_type: IMG_FORMAT.to_mime_type().to_string(),
-                    width: img.width(),
-                    height: img.height(),
-                    caption: Some(format!("meme id: **{}**", meme_id)),
+                    width,
+                    height,
+                    caption: Some(tr(locale, "meme.id", &[&meme_id])),
                     blob_reference: Some(BlobReference {
                         canister_id: env::canister_id(),
                         blob_id: temp_blob_id,
@@ -348,7 +507,7 @@ impl MemeCli {
             ).build().into())
         }
         else {
-            Err("Unknown meme :/".to_string())
+            Err(tr(locale, "meme.unknown", &[]))
         }
     }
 
@@ -356,25 +515,32 @@ impl MemeCli {
         tpl_id: u32,
         mood: String,
         topic: String,
+        language: Option<String>,
         user_id: Principal,
+        chat: &Chat,
+        nsfw: NsfwStrictness,
+        locale: Locale,
         client: &Client<CanisterRuntime, BotCommandContext>
     ) -> Result<SuccessResult, String> {
         if let Some(tpl) = meme::read(|s| 
             s.load(&tpl_id).cloned()
         )  {
-            let captions = MemeService::gen_captions(&tpl, mood, topic)
+            let captions = MemeService::gen_captions(&tpl, mood, topic, language)
                 .await?;
 
-            Self::gen_meme(tpl_id, captions, user_id, client)
-            }
+            Self::gen_meme(tpl_id, captions, user_id, chat, nsfw, locale, client)
+                .await
+        }
         else {
-            Err("Unknown meme :/".to_string())
+            Err(tr(locale, "meme.unknown", &[]))
         }
     }
 
     fn post_meme(
         meme_id: Option<MemeId>,
         user_id: Principal,
+        chat: &Chat,
+        locale: Locale,
         client: &Client<CanisterRuntime, BotCommandContext>
     ) -> Result<SuccessResult, String> {
         let mut user = UserStorage::load(&user_id);
@@ -392,16 +558,27 @@ impl MemeCli {
             else {
                 None
             }.or_else(|| {
-                Some(user.memes.last.clone()
-                    .map(|id| user.memes.list.get(&id).unwrap())
-                    .unwrap()
-                    .clone()
-                )
+                user.memes.last.as_ref()
+                    .and_then(|id| user.memes.list.get(id))
+                    .cloned()
             });
             
             if let Some(meme) = meme {
-                // generate image
-                let jpeg = BlobStorage::load(meme.tmp_blob_id).unwrap();
+                // load the image generated, making sure it wasn't replaced or expired
+                let jpeg = match TempBlobStorage::load_owned(meme.tmp_blob_id, &user_id) {
+                    Ok(jpeg) => jpeg,
+                    Err(TempBlobError::Expired) => {
+                        user.memes.list.remove(&meme.meme_id);
+                        if user.memes.last.as_ref() == Some(&meme.meme_id) {
+                            user.memes.last = None;
+                        }
+                        UserStorage::save(user_id, user);
+                        return Err(tr(locale, TempBlobError::Expired.message_id(), &[]));
+                    },
+                    Err(err) => {
+                        return Err(tr(locale, err.message_id(), &[]));
+                    }
+                };
 
                 let thumbnail_data = create_thumbnail(
                     &jpeg.data, 
@@ -410,7 +587,8 @@ impl MemeCli {
                     IMAGE_FORMAT
                 )?;
 
-                let blob_id = BlobStorage::save(jpeg, false);
+                let blob_id = BlobStorage::save(jpeg, BlobRefKind::Post);
+                let key = chat_key(chat);
 
                 let content = ImageContent {
                     mime_type: IMAGE_FORMAT.to_mime_type().to_string(),
@@ -430,46 +608,99 @@ impl MemeCli {
                         .send_message(MessageContentInitial::Image(content))
                         .execute_then_return_message(move |args, response| match response {
                             Ok(send_message::Response::Success(msg)) => {
-                                user.posts.list.insert(
+                                // reloaded, as the deposit scan or a refund could have 
+                                // recorded a transaction while the message was sent
+                                let mut user = UserStorage::load(&user_id);
+
+                                // a meme posted again replaces the previous post
+                                if let Some(prev) = user.posts.list.insert(
                                     meme.meme_id.clone(),
                                     UserPost{ 
                                         blob_id,
                                         meme_id: meme.meme_id.clone(),
                                         message_id: msg.message_id, 
                                         message_index: msg.message_index,
+                                        captions: meme.captions.clone(),
+                                    }
+                                ) {
+                                    BlobStorage::release(prev.blob_id, BlobRefKind::Post);
+                                }
+                                // indexed so the post can be reported by other users
+                                ReportStorage::save_post(
+                                    meme.meme_id.clone(), 
+                                    PostedMeme { 
+                                        blob_id, 
+                                        user: user_id, 
+                                        chat: key 
                                     }
                                 );
                                 user.posts.last = Some(meme.meme_id);
                                 UserStorage::save(user_id, user);
                             }
                             error => {
+                                BlobStorage::release(blob_id, BlobRefKind::Post);
                                 ic_cdk::println!("send_message: {args:?}, {error:?}");
                             }
                         })
                 })
             }
             else {
-                Err("No meme found!".to_string())
+                Err(tr(locale, "post.not_found", &[]))
             }
         }
         else {
-            Err("No meme found. Use /meme_gen first!".to_string())
+            Err(tr(locale, "post.no_memes", &[]))
         }
     }
 
+    fn report(
+        id: String,
+        reason: Option<String>,
+        user_id: Principal,
+        chat: &Chat,
+        locale: Locale,
+        client: &Client<CanisterRuntime, BotCommandContext>
+    ) -> Result<SuccessResult, String> {
+        // meme ids are tried first, as they could be numeric too
+        let target = match id.parse::<NftId>() {
+            Ok(token_id) if ReportStorage::load_post(&id).is_none() => {
+                ReportTarget::Nft(token_id)
+            },
+            _ => {
+                ReportTarget::Meme(id)
+            }
+        };
+
+        if let Err(err) = ModerationService::report(target, user_id, chat_key(chat), reason) {
+            return Err(tr(locale, err.message_id(), &[]));
+        }
+
+        Ok(EphemeralMessageBuilder::new(
+            MessageContentInitial::from_text(tr(locale, "report.sent", &[])), 
+            client.context().message_id().unwrap()
+        ).with_block_level_markdown(true)
+            .build()
+            .into()
+        )
+    }
+
     async fn wallet_balance(
+        token: TokenKind,
         user_id: Principal,
+        locale: Locale,
         client: &Client<CanisterRuntime, BotCommandContext>
     ) -> Result<SuccessResult, String> {
+        let token = TokenService::get(token).await?;
 
-        let icps = WalletService::balance_of(
+        let balance = WalletService::token_balance_of(
+            &token,
             user_id
         ).await?;
 
-        let content = format!(
-            "Balance:  \nICP: {:.8}  \n", 
-            icps as f32 / 100000000.0
-        );
+        let content = tr(locale, "wallet.balance", &[
+            &token.format(balance),
+            &token.symbol
+        ]);
         
         Ok(
             EphemeralMessageBuilder::new(
@@ -482,17 +713,22 @@ impl MemeCli {
     }
 
     async fn wallet_address(
+        token: TokenKind,
         user_id: Principal,
+        locale: Locale,
         client: &Client<CanisterRuntime, BotCommandContext>
     ) -> Result<SuccessResult, String> {
-        let icp_acc_id = WalletService::address_of(
+        let token = TokenService::get(token).await?;
+
+        let address = WalletService::token_address_of(
+            &token,
             user_id
         );
 
-        let content = format!(
-            "Address:  \nICP: {}  \n", 
-            icp_acc_id
-        );
+        let content = tr(locale, "wallet.address", &[
+            &address,
+            &token.symbol
+        ]);
         
         Ok(
             EphemeralMessageBuilder::new(
@@ -505,21 +741,42 @@ impl MemeCli {
     }
 
     async fn wallet_withdraw(
+        token: TokenKind,
         user_id: Principal,
         to: Option<String>,
-        amount: f32,
+        amount: Option<String>,
+        all: bool,
+        locale: Locale,
         client: &Client<CanisterRuntime, BotCommandContext>
     ) -> Result<SuccessResult, String> {
-        let (block_num, account_id) = WalletService::transfer_hex(
+        let token = TokenService::get(token).await?;
+
+        // with --all, the only positional argument is the destination
+        let (amount, to) = match (all, amount, to) {
+            (true, None, to) | (true, to, None) => (None, to),
+            (false, Some(amount), to) => {
+                let amount = token.parse_amount(&amount)
+                    .filter(|amount| *amount > 0)
+                    .ok_or_else(|| tr(locale, "wallet.invalid_amount", &[&amount, &token.decimals]))?;
+                (Some(amount), to)
+            },
+            (true, Some(_), Some(_)) => 
+                return Err(tr(locale, "wallet.all_with_amount", &[])),
+            (false, None, _) => 
+                return Err(tr(locale, "wallet.no_amount", &[])),
+        };
+
+        let (amount, block_num, account_id) = WalletService::withdraw(
+            &token,
             user_id,
             to,
-            (amount * 100000000.0) as u64
-        ).await?;
+            amount
+        ).await
+            .map_err(|err| Self::wallet_error(err, &token, locale))?;
 
-        let content = format!(
-            "Withdrawn of **{}** ICP to account id **{}** completed! At block index: **{}**", 
-            amount, account_id, block_num
-        );
+        let content = tr(locale, "wallet.withdrawn", &[
+            &token.format(amount), &account_id, &block_num, &token.symbol
+        ]);
         
         Ok(
             EphemeralMessageBuilder::new(
@@ -531,43 +788,50 @@ impl MemeCli {
         )
     }
 
-    fn wallet_logs(
+    async fn wallet_logs(
+        token: TokenKind,
         user_id: Principal,
         page_num: usize,
+        locale: Locale,
         client: &Client<CanisterRuntime, BotCommandContext>
     ) -> Result<SuccessResult, String> {
+        let token = TokenService::get(token).await?;
         let user = UserStorage::load(&user_id);
 
-        let logs = user.txs.iter()
+        let txs = user.txs.iter()
+            .filter(|tx| tx.token() == Some(token.kind))
+            .collect::<Vec<_>>();
+
+        let logs = txs.iter()
             .skip(page_num * LOG_ITEMS_PER_PAGE)
             .take(LOG_ITEMS_PER_PAGE)
-            .cloned()
-            .map(|tx| match tx {
-                UserTransaction::IcpWithdraw { amount, to, block_num, timestamp } => 
-                    format!(
-                        "Withdraw: amount({} ICP) to account_id({}) with block_num({}) at timestamp({})", 
-                        amount as f32 / 100000000.0, to, block_num, timestamp
-                    ),
-                _ => "".to_string()
-            })
+            .map(|tx| Self::wallet_log(tx, &token, locale))
             .collect::<Vec<_>>()
             .join("  \n");
 
-        let num_pages = (user.txs.len() + LOG_ITEMS_PER_PAGE-1) / LOG_ITEMS_PER_PAGE;
+        let num_pages = (txs.len() + LOG_ITEMS_PER_PAGE-1) / LOG_ITEMS_PER_PAGE;
         let page_num = (1+page_num).min(num_pages);
 
+        // only the ICP ledger is scanned for deposits
+        let note = if token.kind == TokenKind::Meme {
+            format!("  \n{}", tr(locale, "wallet.logs.no_deposits", &[&token.symbol]))
+        }
+        else {
+            String::new()
+        };
+
         Ok(
             EphemeralMessageBuilder::new(
                 MessageContentInitial::Text(
-                    format!("{}  \n  \nPage {}/{}",
+                    format!("{}  \n  \n{}{}",
                         if logs.len() > 0 {
                             logs
                         } 
                         else {
-                            "No transactions found".to_string()
+                            tr(locale, "logs.empty", &[])
                         },
-                        page_num,
-                        num_pages
+                        tr(locale, "page", &[&page_num, &num_pages]),
+                        note
                     ).into()
                 ), 
                 client.context().message_id().unwrap()
@@ -577,13 +841,358 @@ impl MemeCli {
         )
     }
 
+    fn admin(
+        command: cli::Admin,
+        user_id: Principal,
+        locale: Locale,
+        client: &Client<CanisterRuntime, BotCommandContext>
+    ) -> Result<SuccessResult, String> {
+        let text = match command {
+            cli::Admin::Roles(command) => {
+                Self::admin_roles(command, user_id, locale)?
+            },
+            cli::Admin::Nft(command) => {
+                Self::admin_nft(command, user_id, locale)?
+            },
+        };
+
+        Ok(EphemeralMessageBuilder::new(
+            MessageContentInitial::from_text(text), 
+            client.context().message_id().unwrap()
+        ).with_block_level_markdown(true)
+            .build()
+            .into()
+        )
+    }
+
+    fn admin_roles(
+        command: cli::Roles,
+        user_id: Principal,
+        locale: Locale
+    ) -> Result<String, String> {
+        let required = match &command {
+            cli::Roles::List => None,
+            _ => Some(Role::Owner),
+        };
+        let authorized = match required {
+            Some(role) => RoleStorage::has_role(&user_id, role),
+            None => RoleStorage::has_any_role(&user_id),
+        };
+        if !authorized {
+            return Err(tr(locale, "not_authorized", &[]));
+        }
+
+        let text = match command {
+            cli::Roles::List => {
+                RoleStorage::list().iter()
+                    .map(|(principal, grant)| tr(locale, "roles.entry", &[
+                        principal,
+                        &grant.roles.iter()
+                            .map(|role| role.name())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ]))
+                    .collect::<Vec<_>>()
+                    .join("  \n")
+            },
+            cli::Roles::Grant { principal, roles } => {
+                let Ok(principal) = Principal::from_text(&principal) else {
+                    return Err(tr(locale, "invalid_principal", &[&principal]));
+                };
+
+                RoleStorage::grant(principal, &roles, user_id);
+                tr(locale, "roles.granted", &[&principal])
+            },
+            cli::Roles::Revoke { principal, roles } => {
+                let Ok(principal) = Principal::from_text(&principal) else {
+                    return Err(tr(locale, "invalid_principal", &[&principal]));
+                };
+
+                RoleStorage::revoke(principal, &roles, user_id)
+                    .map_err(|err| tr(locale, err.message_id(), &[]))?;
+                tr(locale, "roles.revoked", &[&principal])
+            },
+        };
+
+        Ok(text)
+    }
+
+    fn admin_nft(
+        command: cli::AdminNft,
+        user_id: Principal,
+        locale: Locale
+    ) -> Result<String, String> {
+        if !RoleStorage::has_role(&user_id, Role::Treasurer) {
+            return Err(tr(locale, "not_authorized", &[]));
+        }
+
+        let text = match command {
+            cli::AdminNft::Config { 
+                min_reactions, max_reactions, min_cost, max_cost, 
+                min_chat_members, min_user_age, team_fee 
+            } => {
+                // amounts are parsed as fixed-point numbers, so they are not rounded
+                let parse = |name: &str, value: &str, decimals: u8| {
+                    parse_decimal(value, decimals)
+                        .and_then(|value| u64::try_from(value).ok())
+                        .ok_or_else(|| tr(locale, "invalid_decimal", &[&name, &value, &decimals]))
+                };
+
+                let mut config = nft::read(|s| s.config.clone());
+                let changed = min_reactions.is_some() || max_reactions.is_some() || 
+                    min_cost.is_some() || max_cost.is_some() || 
+                    min_chat_members.is_some() || min_user_age.is_some() || team_fee.is_some();
+
+                if let Some(min_reactions) = min_reactions {
+                    config.min_num_reactions = min_reactions;
+                }
+                if let Some(max_reactions) = max_reactions {
+                    config.max_num_reactions = max_reactions;
+                }
+                if let Some(min_cost) = min_cost {
+                    config.min_minting_cost = parse("min-cost", &min_cost, ICP_DECIMALS)?;
+                }
+                if let Some(max_cost) = max_cost {
+                    config.max_minting_cost = parse("max-cost", &max_cost, ICP_DECIMALS)?;
+                }
+                if let Some(min_chat_members) = min_chat_members {
+                    config.min_chat_members = min_chat_members;
+                }
+                if let Some(min_user_age) = min_user_age {
+                    config.min_user_creation_interval = min_user_age.checked_mul(24 * 60 * 60 * 1_000)
+                        .ok_or_else(|| tr(locale, "invalid_value", &[&"min-user-age", &min_user_age]))?;
+                }
+                if let Some(team_fee) = team_fee {
+                    config.team_fee_p = parse("team-fee", &team_fee, TEAM_FEE_P_DECIMALS)?;
+                }
+
+                if changed {
+                    NftService::change_config(config, user_id)?;
+                    tr(locale, "nft.config_changed", &[])
+                }
+                else {
+                    tr(locale, "nft.config", &[
+                        &config.min_num_reactions,
+                        &config.max_num_reactions,
+                        &format_decimal(config.min_minting_cost as u128, ICP_DECIMALS),
+                        &format_decimal(config.max_minting_cost as u128, ICP_DECIMALS),
+                        &config.min_chat_members,
+                        &(config.min_user_creation_interval / (24 * 60 * 60 * 1_000)),
+                        &format_decimal(config.team_fee_p as u128, TEAM_FEE_P_DECIMALS),
+                        &config.cost_curve.clone().unwrap_or_default().describe(),
+                        &config.reactions_curve.clone().unwrap_or_default().describe()
+                    ])
+                }
+            },
+            cli::AdminNft::Pause { reason } => {
+                NftService::pause_minting(Some(reason), user_id)?;
+                tr(locale, "nft.paused", &[])
+            },
+            cli::AdminNft::Resume => {
+                NftService::pause_minting(None, user_id)?;
+                tr(locale, "nft.resumed", &[])
+            },
+            cli::AdminNft::Logs { page } => {
+                let page_num = page.max(1) - 1;
+                let num_pages = (NftConfigStorage::size() + LOG_ITEMS_PER_PAGE-1) / LOG_ITEMS_PER_PAGE;
+
+                let logs = NftConfigStorage::list_changes(page_num * LOG_ITEMS_PER_PAGE, LOG_ITEMS_PER_PAGE).iter()
+                    .map(|change| match &change.kind {
+                        NftConfigChangeKind::Config(_) => 
+                            tr(locale, "nft.config_log.config", &[
+                                &format_utc(change.changed_at), &change.changed_by
+                            ]),
+                        NftConfigChangeKind::Paused(reason) => 
+                            tr(locale, "nft.config_log.paused", &[
+                                &format_utc(change.changed_at), &change.changed_by, reason
+                            ]),
+                        NftConfigChangeKind::Resumed => 
+                            tr(locale, "nft.config_log.resumed", &[
+                                &format_utc(change.changed_at), &change.changed_by
+                            ]),
+                        NftConfigChangeKind::Phases(phases) => 
+                            tr(locale, "nft.config_log.phases", &[
+                                &format_utc(change.changed_at), &change.changed_by, &phases.len()
+                            ]),
+                        NftConfigChangeKind::Allowlist { num_added, num_removed } => 
+                            tr(locale, "nft.config_log.allowlist", &[
+                                &format_utc(change.changed_at), &change.changed_by, num_added, num_removed
+                            ]),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("  \n");
+
+                format!("{}  \n  \n{}",
+                    if logs.len() > 0 {
+                        logs
+                    } 
+                    else {
+                        tr(locale, "logs.empty", &[])
+                    },
+                    tr(locale, "page", &[&(1+page_num).min(num_pages), &num_pages])
+                )
+            },
+            cli::AdminNft::Preview { mut supplies } => {
+                if supplies.is_empty() {
+                    supplies.push(NftStorage::size() as u32);
+                }
+
+                nft::read(|s| s.preview(None, &supplies, ic_cdk::api::time()))?.iter()
+                    .map(|preview| tr(locale, "nft.preview", &[
+                        &preview.supply,
+                        &format_decimal(preview.minting_cost as u128, ICP_DECIMALS),
+                        &preview.min_num_reactions
+                    ]))
+                    .collect::<Vec<_>>()
+                    .join("  \n")
+            },
+        };
+
+        Ok(text)
+    }
+
+    fn schedule(
+        command: cli::Schedule,
+        user_id: Principal,
+        chat: &Chat,
+        locale: Locale,
+        client: &Client<CanisterRuntime, BotCommandContext>
+    ) -> Result<SuccessResult, String> {
+        let key = chat_key(chat);
+        let now = ic_cdk::api::time();
+
+        let (kind, interval, next_run_at) = match command {
+            cli::Schedule::List => {
+                let jobs = ScheduleStorage::list_by_chat(&key).iter()
+                    .map(|(id, job)| tr(locale, "schedule.job", &[
+                        id, 
+                        &job.kind.describe(), 
+                        &format_utc(job.next_run_at),
+                        &job.last_error.as_ref()
+                            .map(|err| tr(locale, "schedule.last_failed", &[err]))
+                            .unwrap_or_default()
+                    ]))
+                    .collect::<Vec<_>>();
+
+                return Ok(EphemeralMessageBuilder::new(
+                    MessageContentInitial::from_text(
+                        if jobs.len() > 0 {
+                            jobs.join("  \n")
+                        }
+                        else {
+                            tr(locale, "schedule.empty", &[])
+                        }
+                    ), 
+                    client.context().message_id().unwrap()
+                ).with_block_level_markdown(true)
+                    .build()
+                    .into()
+                );
+            },
+            cli::Schedule::Cancel { id } => {
+                if ScheduleStorage::load(id).filter(|job| job.chat == key).is_none() {
+                    return Err(tr(locale, "schedule.not_found", &[&id]));
+                }
+                ScheduleStorage::remove(id);
+
+                return Ok(EphemeralMessageBuilder::new(
+                    MessageContentInitial::from_text(tr(locale, "schedule.cancelled", &[&id])), 
+                    client.context().message_id().unwrap()
+                ).with_block_level_markdown(true)
+                    .build()
+                    .into()
+                );
+            },
+            cli::Schedule::MemeOfTheDay { hour, mood, topic } => {
+                if hour > 23 {
+                    return Err(tr(locale, "schedule.invalid_hour", &[]));
+                }
+                (
+                    JobKind::MemeOfTheDay { 
+                        mood: mood.unwrap_or("funny".to_string()), 
+                        topic: topic.unwrap_or("crypto".to_string()), 
+                    }, 
+                    DAY, 
+                    next_occurrence(now, hour, None)
+                )
+            },
+            cli::Schedule::Leaderboard { weekday, hour } => {
+                if weekday > 6 || hour > 23 {
+                    return Err(tr(locale, "schedule.invalid_weekday", &[]));
+                }
+                (
+                    JobKind::WeeklyLeaderboard, 
+                    WEEK, 
+                    next_occurrence(now, hour, Some(weekday))
+                )
+            },
+            cli::Schedule::Reminder { text, every } => {
+                if every == 0 || every > 24 * 30 {
+                    return Err(tr(locale, "schedule.invalid_interval", &[]));
+                }
+                let interval = every as u64 * HOUR;
+                (
+                    JobKind::Reminder { text }, 
+                    interval, 
+                    now + interval
+                )
+            },
+        };
+
+        if ChatStorage::load(&key).is_none() {
+            return Err(tr(locale, "schedule.not_autonomous", &[]));
+        }
+
+        if ScheduleStorage::list_by_chat(&key).len() >= MAX_JOBS_PER_CHAT {
+            return Err(tr(locale, "schedule.too_many", &[&MAX_JOBS_PER_CHAT]));
+        }
+
+        let description = kind.describe();
+        let id = ScheduleStorage::add(Job {
+            chat: key,
+            kind,
+            interval,
+            next_run_at,
+            created_by: user_id,
+            created_at: now,
+            last_run_at: None,
+            last_error: None,
+        });
+
+        Ok(EphemeralMessageBuilder::new(
+            MessageContentInitial::from_text(tr(locale, "schedule.scheduled", &[
+                &id, 
+                &description, 
+                &format_utc(next_run_at)
+            ])), 
+            client.context().message_id().unwrap()
+        ).with_block_level_markdown(true)
+            .build()
+            .into()
+        )
+    }
+
     async fn nft_mint(
         meme_id: Option<MemeId>,
         user_id: Principal,
         chat: &Chat,
+        nsfw: NsfwStrictness,
+        locale: Locale,
         client: &Client<CanisterRuntime, BotCommandContext>
     ) -> Result<SuccessResult, String> {
-        let mut user = UserStorage::load(&user_id);
+        if let Some(pause) = state::read(|s| s.minting_pause().cloned()) {
+            return Err(tr(locale, "mint.paused", &[&pause.reason]));
+        }
+
+        if ModerationService::is_mint_banned(&user_id) {
+            return Err(tr(locale, "mint.banned", &[]));
+        }
+
+        // one mint at a time per user, so the user record isn't overwritten by a concurrent mint
+        let _user_lock = NftService::lock(MintLockKey::User(user_id))
+            .map_err(|err| Self::mint_error(err, locale))?;
+
+        let user = UserStorage::load(&user_id);
 
         let nft_service = nft::read(|s| {
             s.clone()
@@ -591,7 +1200,7 @@ impl MemeCli {
         let meme_nft_canister_id = nft_service.col.canister_id.clone();
 
         if user.posts.list.len() == 0 {
-            return Err("Meme not found. Use /meme_post first!".to_string());
+            return Err(tr(locale, "mint.no_posts", &[]));
         }
 
         // find user post
@@ -614,19 +1223,48 @@ impl MemeCli {
         });
 
         if post.is_none()  {
-            return Err("Post not found!".to_string());
+            return Err(tr(locale, "mint.post_not_found", &[]));
         }
         
         let post = post.unwrap();
 
+        // the image was taken down by the administrator
+        if state::read(|s| s.takedowns().hidden_blobs.contains(&post.blob_id)) {
+            return Err(tr(locale, "mint.taken_down", &[]));
+        }
+
+        // the check below would pass for concurrent mints of the same meme
+        let _meme_lock = NftService::lock(MintLockKey::Meme(post.meme_id.clone()))
+            .map_err(|err| Self::mint_error(err, locale))?;
+
         // check if NFT was already minted
         if let Some(nft) = NftStorage::load_by_meme_id(&post.meme_id) {
-            return Err(
-                format!(
-                    "Meme was already minted! Token id: **{}**", 
-                    nft.token_id
-                )
-            );
+            return Err(tr(locale, "mint.already_minted", &[&nft.token_id]));
+        }
+
+        // hold a token of the supply until the NFT is stored or the mint fails
+        let phase = NftService::current_phase(ic_cdk::api::time())
+            .map_err(|err| Self::mint_error(err, locale))?;
+        let num_minted_in_phase = phase.as_ref().map_or(0, |phase| {
+            user.mints.list.values()
+                .filter(|mint| phase.is_open_at(mint.timestamp * 1_000_000))
+                .count() as u32
+        });
+        let reservation = NftService::reserve(user_id, phase.as_ref(), num_minted_in_phase)
+            .map_err(|err| Self::mint_error(err, locale))?;
+
+        // moderate the captions again, as the denylist could have changed. An NFT is forever,
+        // so the filter can't be turned off
+        if let Some(captions) = &post.captions {
+            if ModerationService::check(
+                captions, 
+                if nsfw == NsfwStrictness::Off { NsfwStrictness::Normal } else { nsfw }, 
+                ModerationStage::Mint, 
+                Some(user_id), 
+                Some(chat_key(chat))
+            ).await.is_err() {
+                return Err(tr(locale, "moderation.refused", &[]));
+            }
         }
 
         // check if user has proof of uniqueness
@@ -641,7 +1279,7 @@ impl MemeCli {
         };
 
         if !has_poh {
-            return Err(format!("You haven't proven to be a unique individual yet. Please do that first on Open Chat"));
+            return Err(tr(locale, "mint.no_poh", &[]));
         }
 
         // check if group/channel has enough members
@@ -654,10 +1292,7 @@ impl MemeCli {
         };
 
         if num_chat_members < min_chat_members {
-            return Err(
-                format!("The group/channel where the meme was posted has only {} members. Expected at least {}", 
-                    num_chat_members, min_chat_members)
-            );
+            return Err(tr(locale, "mint.few_members", &[&num_chat_members, &min_chat_members]));
         }
 
         // check if post has enough reactions
@@ -672,26 +1307,34 @@ impl MemeCli {
         };
 
         if num_reactions < min_reactions {
-            return Err(
-                format!("Post has only **{}** reactions from different users created {} days ago or earlier who have proven to be unique individuals. Expected at least **{}** reactions. Try harder 😎", 
-                    num_reactions, nft_service.min_user_creation_interval_in_days(), min_reactions)
-            );
+            return Err(tr(locale, "mint.few_reactions", &[
+                &num_reactions, &nft_service.min_user_creation_interval_in_days(), &min_reactions
+            ]));
         }
 
-        // check user ICP balance
+        // pay with an ICRC-2 approval from the user's own wallet if there's one big enough, 
+        // otherwise fall back to the deposit subaccount
+        // the user pays the ledger fee on top of the cost
         let cost = nft_service.calc_minting_cost();
-        let balance = WalletService::balance_of(user_id).await?;
-        if balance < cost {
-            let acc_id = WalletService::address_of(user_id);
-            return Err(
-                format!(
-                    "Your Mementor wallet balance of **{:.8}** ICP is too low to cover the current minting cost of **{:.8}** ICP  \nPlease transfer enough ICP to this address: **{}**", 
-                    (balance as f32) / 100000000.0,
-                    (cost as f32) / 100000000.0,
-                    acc_id
-                )
-            );
+        let fee = TokenService::get(TokenKind::Icp).await?.fee as u64;
+        let allowance = WalletService::allowance_of(user_id).await.unwrap_or(0);
+        let payment = if allowance >= cost + fee {
+            MintPayment::Approval
         }
+        else {
+            let balance = WalletService::balance_of(user_id).await?;
+            if balance < cost + fee {
+                let acc_id = WalletService::address_of(user_id);
+                return Err(tr(locale, "mint.low_balance", &[
+                    &format_decimal(balance as u128, ICP_DECIMALS),
+                    &format_decimal((cost + fee) as u128, ICP_DECIMALS),
+                    &acc_id,
+                    &format_decimal((cost + fee) as u128, ICP_DECIMALS),
+                    &ic_cdk::id()
+                ]));
+            }
+            MintPayment::Deposit
+        };
 
         // get blob generated by the user post
         let jpeg = BlobStorage::load(post.blob_id).unwrap();
@@ -703,19 +1346,26 @@ impl MemeCli {
             IMAGE_FORMAT
         )?;
 
+        // journal the mint before any transfer is made
+        let team_account = state::read(|s| s.administrator().clone());
+        let team_fee_p = nft::read(|n| n.config.team_fee_p);
+        let mut op = MintOpService::begin(
+            user_id, 
+            &post.meme_id, 
+            post.blob_id, 
+            cost, 
+            (cost * team_fee_p) / TEAM_FEE_P_DENOMINATOR, 
+            team_account,
+            payment
+        );
+
         // debit user ICP wallet
-        if let Err(err) = WalletService::transfer(
-            user_id.into(), 
-            AccountIdentifier::new(&ic_cdk::id(), &DEFAULT_SUBACCOUNT), 
-            cost
-        ).await {
-            let err = format!(
-                "Failed to debit the minting cost: {}.", 
-                err
-            );
-            ic_cdk::println!("error: {}", err);
-            return Err(err);
+        if let Err(err) = MintOpService::debit(&mut op).await {
+            ic_cdk::println!("error: Failed to debit the minting cost: {}", err);
+            MintOpService::fail(&mut op, MintOpStep::Failed, err.clone());
+            return Err(tr(locale, "mint.debit_failed", &[&err]));
         };
+        MintOpService::advance(&mut op, MintOpStep::Minting);
 
         // mint the NFT with transfer approval to our bot 
         // NOTE: this is needed while OC doesn't support ICRC-7 NFTs on its wallet. by now users can't transfer their NFTs directly
@@ -731,33 +1381,32 @@ impl MemeCli {
             },
             Err(err) => {
                 ic_cdk::println!("error: NFT minting failed: {}", err);
+                state::mutate(|s| s.metrics_mut().record_mint_failure());
 
-                // return the value transferred to user
-                if let Err(err) = WalletService::transfer(
-                    None, 
-                    AccountIdentifier::new(&ic_cdk::id(), &user_id.into()), 
-                    cost + DEFAULT_FEE.e8s()
-                ).await {
-                    ic_cdk::println!(
-                        "error: Could not return minting cost {} to user {}: {}", 
-                        cost, 
-                        user_id.to_text(), 
-                        err
-                    );
-                };
+                // return the value transferred to user. If it fails, the timer will retry it
+                MintOpService::fail(&mut op, MintOpStep::Refunding, err.clone());
+                let refund = MintOpService::refund(&mut op).await;
+                state::mutate(|s| s.metrics_mut().record_refund(cost, refund.is_err()));
 
                 return Err(err);
             },
         };
 
         // store the nft in our DB
+        op.token_id = Some(token_id);
+        MintOpService::advance(&mut op, MintOpStep::PayingFee);
         NftStorage::save(token_id, &post.meme_id, Nft::new(
             token_id,
             post.blob_id,
             Some(meta),
             (ic_cdk::api::time() / 1_000_000_000) as _
         ));
+        BlobStorage::add_ref(post.blob_id, BlobRefKind::Nft);
+        state::mutate(|s| s.metrics_mut().record_mint(cost));
+        drop(reservation);
 
+        // reloaded, as the payment, withdraws and deposits were recorded while awaiting
+        let mut user = UserStorage::load(&user_id);
         user.mints.list.insert(
             post.meme_id.clone(),
             UserMint{
@@ -769,16 +1418,8 @@ impl MemeCli {
         user.mints.last = Some(post.meme_id.clone());
         UserStorage::save(user_id, user);
 
-        // transfer the dev team's cut
-        let team_account = state::read(|s| s.administrator().clone());
-        let team_fee_p = nft::read(|n| n.config.team_fee_p);
-        if let Err(err) = WalletService::transfer(
-            None, 
-            AccountIdentifier::new(&team_account, &DEFAULT_SUBACCOUNT), 
-            (cost * team_fee_p) / 1_00000000
-        ).await {
-            ic_cdk::println!("error: Failed to transfer the dev team fee: {}", err);
-        };
+        // transfer the dev team's cut. If it fails, the timer will retry it
+        let _ = MintOpService::pay_fee(&mut op).await;
 
         // return a message to user only
         Ok(EphemeralMessageBuilder::new(
@@ -787,7 +1428,7 @@ impl MemeCli {
                     mime_type: IMAGE_FORMAT.to_mime_type().to_string(),
                     width: IMAGE_WIDTH,
                     height: IMAGE_HEIGHT,
-                    caption: Some(format!("NFT **{}** has just been minted! 🎉🎉🎉", token_id)),
+                    caption: Some(tr(locale, "mint.minted", &[&token_id])),
                     blob_reference: Some(BlobReference {
                         canister_id: env::canister_id(),
                         blob_id: post.blob_id,
@@ -799,6 +1440,81 @@ impl MemeCli {
         ).build().into())
     }
 
+    fn wallet_log(
+        tx: &UserTransaction,
+        token: &Token,
+        locale: Locale
+    ) -> String {
+        let date = |timestamp: u32| format_utc(timestamp as u64 * 1_000_000_000);
+
+        match tx {
+            UserTransaction::IcpWithdraw { amount, to, block_num, timestamp } => 
+                tr(locale, "wallet.log.withdraw", &[
+                    &token.format(*amount as u128), &token.symbol, &to.to_hex(), block_num, &date(*timestamp)
+                ]),
+            UserTransaction::TokenWithdraw { amount, to, block_num, timestamp, .. } => 
+                tr(locale, "wallet.log.withdraw", &[
+                    &token.format(*amount), &token.symbol, to, block_num, &date(*timestamp)
+                ]),
+            UserTransaction::Debit { amount, reason, block_num, timestamp, .. } | 
+            UserTransaction::Credit { amount, reason, block_num, timestamp, .. } => {
+                let (id, arg) = match reason {
+                    TxReason::Deposit { from } => ("wallet.log.deposit", from),
+                    TxReason::MintPayment { meme_id } => ("wallet.log.mint_payment", meme_id),
+                    TxReason::MintRefund { meme_id } => ("wallet.log.mint_refund", meme_id),
+                };
+                tr(locale, id, &[
+                    &token.format(*amount), &token.symbol, arg, block_num, &date(*timestamp)
+                ])
+            },
+            UserTransaction::NftTransfer { .. } => 
+                "".to_string(),
+        }
+    }
+
+    fn wallet_error(
+        err: WalletError,
+        token: &Token,
+        locale: Locale
+    ) -> String {
+        match err {
+            WalletError::InvalidDestination(to) => 
+                tr(locale, "wallet.invalid_destination", &[&to, &token.symbol]),
+            WalletError::UnknownUsername(username) => 
+                tr(locale, "wallet.unknown_username", &[&username]),
+            WalletError::InsufficientBalance(balance) => 
+                tr(locale, "wallet.insufficient_balance", &[
+                    &token.format(balance), &token.format(token.fee), &token.symbol
+                ]),
+            WalletError::InvalidAmount(amount) => 
+                tr(locale, "wallet.invalid_amount", &[&token.format(amount), &token.decimals]),
+            WalletError::Transfer(err) => 
+                err,
+        }
+    }
+
+    fn mint_error(
+        err: MintError,
+        locale: Locale
+    ) -> String {
+        match err {
+            MintError::SoldOut => 
+                tr(locale, "mint.sold_out", &[]),
+            MintError::Closed(Some(starts_at)) => 
+                tr(locale, "mint.not_started", &[&format_utc(starts_at)]),
+            MintError::Closed(None) => 
+                tr(locale, "mint.closed", &[]),
+            MintError::NotAllowlisted(phase) => 
+                tr(locale, "mint.not_allowlisted", &[&phase]),
+            MintError::UserCapReached(max_mints) => 
+                tr(locale, "mint.user_cap", &[&max_mints]),
+            MintError::UserInFlight => 
+                tr(locale, "mint.user_in_flight", &[]),
+            MintError::MemeInFlight => 
+                tr(locale, "mint.meme_in_flight", &[]),
+        }
+    }
+
     async fn count_unique_reactions_from_poh_users(
         post: &UserPost,
         chat: &Chat,
@@ -897,8 +1613,50 @@ impl MemeCli {
         }
     }
 
+    fn settings(
+        command: cli::Settings,
+        user_id: Principal,
+        chat: &Chat,
+        locale: Locale,
+        client: &Client<CanisterRuntime, BotCommandContext>
+    ) -> Result<SuccessResult, String> {
+        let text = ChatSettingsCommand::apply(command, chat_key(chat), user_id, locale)?;
+
+        Ok(EphemeralMessageBuilder::new(
+            MessageContentInitial::from_text(text), 
+            client.context().message_id().unwrap()
+        ).with_block_level_markdown(true)
+            .build()
+            .into()
+        )
+    }
+
+    /// True if the user is an owner or an admin of the chat where the command was run
+    async fn is_chat_admin(
+        user_id: Principal,
+        client: &Client<CanisterRuntime, BotCommandContext>
+    ) -> bool {
+        let user_id = UserId::from(user_id);
+
+        match client.members(HashSet::from([MemberType::Owner, MemberType::Admin])).execute_async().await {
+            Ok(members::Response::Success(res)) => {
+                res.members_map.values()
+                    .any(|ids| ids.contains(&user_id))
+            },
+            Err(err) => {
+                ic_cdk::println!("error: getting chat members: {}", err.1);
+                false
+            },
+            _ => {
+                ic_cdk::println!("error: getting chat members");
+                false
+            }
+        }
+    }
+
     async fn nft_balance_of(
         user_id: Principal,
+        locale: Locale,
         client: &Client<CanisterRuntime, BotCommandContext>
     ) -> Result<SuccessResult, String> {
         let nft_service = nft::read(|s| s.clone());
@@ -907,7 +1665,7 @@ impl MemeCli {
             user_id.into()
         ).await?;
 
-        let content = format!("Balance: {}", balance);
+        let content = tr(locale, "nft.balance", &[&balance]);
         
         Ok(
             EphemeralMessageBuilder::new(
@@ -922,6 +1680,7 @@ impl MemeCli {
     async fn nft_tokens_of(
         user_id: Principal,
         page_num: usize,
+        locale: Locale,
         client: &Client<CanisterRuntime, BotCommandContext>
     ) -> Result<SuccessResult, String> {
         let nft_service = nft::read(|s| s.clone());
@@ -933,7 +1692,7 @@ impl MemeCli {
         if balance == 0 {
             return Ok(
                 EphemeralMessageBuilder::new(
-                    MessageContentInitial::Text("No tokens found. Go and mint a NFT before it's too late! 😋".to_string().into()), 
+                    MessageContentInitial::Text(tr(locale, "nft.no_tokens", &[]).into()), 
                     client.context().message_id().unwrap()
                 ).with_block_level_markdown(true)
                 .build()
@@ -968,7 +1727,7 @@ impl MemeCli {
         let blob_id = BlobStorage::save(Blob {
             data: jpeg,
             mime_type: IMG_FORMAT.to_mime_type().to_string()
-        }, true);
+        }, BlobRefKind::Preview);
         
         // return a message to user only
         Ok(EphemeralMessageBuilder::new(
@@ -976,9 +1735,7 @@ impl MemeCli {
                 mime_type: IMG_FORMAT.to_mime_type().to_string(),
                 width: preview.width(),
                 height: preview.height(),
-                caption: Some(format!(
-                    "Page {}/{}", 1+page_num, num_pages)
-                ),
+                caption: Some(tr(locale, "page", &[&(1+page_num), &num_pages])),
                 blob_reference: Some(BlobReference {
                     canister_id: env::canister_id(),
                     blob_id,
@@ -993,12 +1750,13 @@ impl MemeCli {
         from: Principal,
         token_id: NftId,
         to: Principal,
+        locale: Locale,
         client: &Client<CanisterRuntime, BotCommandContext>
     ) -> Result<SuccessResult, String> {
         let nft_service = nft::read(|s| s.clone());
 
         let Some(nft) = NftStorage::load(&token_id) else {
-            return Err(format!("NFT {} not found", token_id));
+            return Err(tr(locale, "nft.not_found", &[&token_id]));
         };
 
         nft_service.transfer_from(
@@ -1010,9 +1768,7 @@ impl MemeCli {
         Ok(
             EphemeralMessageBuilder::new(
                 MessageContentInitial::Text(
-                    format!("Token {} transferred to {}! 🎉", 
-                        token_id, to.to_text()
-                    ).to_string().into()
+                    tr(locale, "nft.transferred", &[&token_id, &to.to_text()]).into()
                 ), 
                 client.context().message_id().unwrap()
             ).with_block_level_markdown(true)
@@ -1024,40 +1780,44 @@ impl MemeCli {
     fn nft_logs(
         user_id: Principal,
         page_num: usize,
+        locale: Locale,
         client: &Client<CanisterRuntime, BotCommandContext>
     ) -> Result<SuccessResult, String> {
         let user = UserStorage::load(&user_id);
 
-        let logs = user.txs.iter()
-            .skip(page_num * LOG_ITEMS_PER_PAGE)
-            .take(LOG_ITEMS_PER_PAGE)
-            .cloned()
-            .map(|tx| match tx {
+        let txs = user.txs.iter()
+            .filter_map(|tx| match tx {
                 UserTransaction::NftTransfer { token_id, to, tx_id, timestamp } => 
-                    format!(
-                        "Transfer: token_id({}) to principal({}) with tx_id({}) at timestamp({})", 
-                        token_id, to.owner, tx_id, timestamp
-                    ),
-                _ => "".to_string()
+                    Some((token_id, to, tx_id, timestamp)),
+                _ => None
             })
+            .collect::<Vec<_>>();
+
+        let logs = txs.iter()
+            .skip(page_num * LOG_ITEMS_PER_PAGE)
+            .take(LOG_ITEMS_PER_PAGE)
+            .map(|(token_id, to, tx_id, timestamp)| 
+                tr(locale, "nft.log", &[
+                    token_id, &to.owner, tx_id, &format_utc(**timestamp as u64 * 1_000_000_000)
+                ])
+            )
             .collect::<Vec<_>>()
             .join("  \n");
 
-        let num_pages = (user.txs.len() + LOG_ITEMS_PER_PAGE-1) / LOG_ITEMS_PER_PAGE;
+        let num_pages = (txs.len() + LOG_ITEMS_PER_PAGE-1) / LOG_ITEMS_PER_PAGE;
         let page_num = (1+page_num).min(num_pages);
 
         Ok(
             EphemeralMessageBuilder::new(
                 MessageContentInitial::Text(
-                    format!("{}  \n  \nPage {}/{}",
+                    format!("{}  \n  \n{}",
                         if logs.len() > 0 {
                             logs
                         } 
                         else {
-                            "No transactions found".to_string()
+                            tr(locale, "logs.empty", &[])
                         },
-                        page_num,
-                        num_pages
+                        tr(locale, "page", &[&page_num, &num_pages])
                     ).into()
                 ), 
                 client.context().message_id().unwrap()
@@ -1068,6 +1828,7 @@ impl MemeCli {
     }
 
     async fn nft_status(
+        locale: Locale,
         client: &Client<CanisterRuntime, BotCommandContext>
     ) -> Result<SuccessResult, String> {
         let nft_service = nft::read(|s| {
@@ -1080,15 +1841,33 @@ impl MemeCli {
         //let min_chat_members = nft_service.min_chat_members();
         let max_supply = nft_service.col.max_supply;
         let total_supply = NftStorage::size();
-
-        let text = format!(
-            "**MEME NFT Status**  \n- canister id: {}  \n- minting cost: **{:.8} ICP**  \n- min reactions: {}  \n- supply: {}/{}  \n",
-            canister_id,
-            minting_cost as f32 / 1_00000000.0,
-            min_reactions,
-            total_supply,
-            max_supply
-        );
+        let remaining_supply = nft::read(|s| s.remaining_supply());
+
+        let mut text = tr(locale, "nft.status", &[
+            &canister_id,
+            &format_decimal(minting_cost as u128, ICP_DECIMALS),
+            &min_reactions,
+            &total_supply,
+            &max_supply,
+            &remaining_supply
+        ]);
+        match NftService::current_phase(ic_cdk::api::time()) {
+            Ok(Some(phase)) => {
+                text.push_str(&tr(locale, "nft.status.phase", &[
+                    &phase.name,
+                    &phase.kind.name(),
+                    &phase.ends_at.map(format_utc).unwrap_or("-".to_string())
+                ]));
+            },
+            Ok(None) => {
+            },
+            Err(err) => {
+                text.push_str(&tr(locale, "nft.status.closed", &[&Self::mint_error(err, locale)]));
+            },
+        }
+        if let Some(pause) = state::read(|s| s.minting_pause().cloned()) {
+            text.push_str(&tr(locale, "nft.status.paused", &[&pause.reason]));
+        }
 
         Ok(
             EphemeralMessageBuilder::new(
@@ -1126,6 +1905,7 @@ impl MemeCli {
             ])).with_chat(&HashSet::from([
                 ChatPermission::ReadMessages,
                 ChatPermission::ReadChatDetails,
+                ChatPermission::ReadMembership,
             ])),
             default_role: None,
             direct_messages: Some(true),
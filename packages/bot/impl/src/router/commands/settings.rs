@@ -0,0 +1,118 @@
+use candid::Principal;
+use clap::ValueEnum;
+use crate::{
+    storage::chat::ChatStorage,
+    types::{
+        chat::{ChatKey, ChatSettings},
+        cli::{is_command_name, Settings}
+    },
+    utils::{i18n::{tr, Locale}, time::format_utc}
+};
+
+/// Handles '/meme settings', the settings of a chat changed by its admins
+pub struct ChatSettingsCommand;
+
+impl ChatSettingsCommand {
+    pub fn apply(
+        command: Settings,
+        key: ChatKey,
+        user_id: Principal,
+        locale: Locale
+    ) -> Result<String, String> {
+        let mut settings = ChatStorage::load_settings(&key);
+
+        let text = match command {
+            Settings::Show => {
+                return Ok(Self::describe(&settings, locale));
+            },
+            Settings::Reset => {
+                ChatStorage::reset_settings(&key);
+                return Ok(tr(locale, "settings.reset", &[]));
+            },
+            Settings::Mood { mood } => {
+                settings.default_mood = mood;
+                tr(locale, "settings.mood_updated", &[])
+            },
+            Settings::Topic { topic } => {
+                settings.default_topic = topic;
+                tr(locale, "settings.topic_updated", &[])
+            },
+            Settings::Language { language } => {
+                settings.language = language;
+                tr(locale, "settings.language_updated", &[])
+            },
+            Settings::Locale { locale: code } => {
+                if let Some(code) = &code {
+                    if Locale::from_code(code).is_none() {
+                        return Err(tr(locale, "settings.unsupported_locale", &[
+                            code, 
+                            &Locale::ALL.iter().map(|l| l.code()).collect::<Vec<_>>().join(", ")
+                        ]));
+                    }
+                }
+                settings.locale = code;
+                tr(locale, "settings.locale_updated", &[])
+            },
+            Settings::Disable { command } => {
+                let name = command.join(" ");
+                if !is_command_name(&name) {
+                    return Err(tr(locale, "settings.unknown_command", &[&name]));
+                }
+                if !settings.disabled_commands.contains(&name) {
+                    settings.disabled_commands.push(name.clone());
+                }
+                tr(locale, "settings.command_disabled", &[&name])
+            },
+            Settings::Enable { command } => {
+                let name = command.join(" ");
+                if !settings.disabled_commands.contains(&name) {
+                    return Err(tr(locale, "settings.not_disabled", &[&name, &Self::disabled_list(&settings, locale)]));
+                }
+                settings.disabled_commands.retain(|disabled| *disabled != name);
+                tr(locale, "settings.command_enabled", &[&name])
+            },
+            Settings::Nsfw { level } => {
+                settings.nsfw = level;
+                tr(locale, "settings.nsfw_updated", &[])
+            },
+        };
+
+        settings.updated_by = Some(user_id);
+        settings.updated_at = ic_cdk::api::time();
+        ChatStorage::save_settings(key, settings);
+
+        Ok(text)
+    }
+
+    fn describe(
+        settings: &ChatSettings,
+        locale: Locale
+    ) -> String {
+        let mut lines = vec![
+            tr(locale, "settings.show.mood", &[&settings.default_mood.as_deref().unwrap_or("funny")]),
+            tr(locale, "settings.show.topic", &[&settings.default_topic.as_deref().unwrap_or("crypto")]),
+            tr(locale, "settings.show.language", &[&settings.language.as_deref().unwrap_or("English")]),
+            tr(locale, "settings.show.locale", &[&settings.locale.clone().unwrap_or_else(|| tr(locale, "settings.default.locale", &[]))]),
+            tr(locale, "settings.show.disabled", &[&Self::disabled_list(settings, locale)]),
+            tr(locale, "settings.show.nsfw", &[&settings.nsfw.to_possible_value().unwrap().get_name()]),
+        ];
+
+        if let Some(updated_by) = settings.updated_by {
+            lines.push(tr(locale, "settings.show.updated", &[&updated_by, &format_utc(settings.updated_at)]));
+        }
+
+        lines.join("  \n")
+    }
+
+    fn disabled_list(
+        settings: &ChatSettings,
+        locale: Locale
+    ) -> String {
+        if settings.disabled_commands.is_empty() {
+            tr(locale, "settings.default.disabled", &[])
+        }
+        else {
+            settings.disabled_commands.join(", ")
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,41 @@
+use std::{future::Future, pin::Pin};
+use oc_bots_sdk::{
+    api::command::{CommandResponse, SuccessResult}, 
+    types::{BotApiKeyContext, BotCommandContext, BotCommandScope}
+};
+use crate::{
+    storage::chat::ChatStorage, 
+    types::chat::RegisteredChat, 
+    utils::oc::chat_key
+};
+
+/// Called by OpenChat whenever an API key is generated for the bot in a chat, 
+/// so the bot can act autonomously there (e.g.: running the scheduled jobs)
+pub fn callback(
+    context: BotCommandContext
+) -> Pin<Box<dyn Future<Output = CommandResponse> + Send>> {
+    Box::pin(async move {
+        let api_key = context.command.arg::<String>("api_key");
+
+        match &context.scope {
+            BotCommandScope::Chat(chat_scope) => {
+                if let Err(err) = BotApiKeyContext::parse_api_key(api_key.clone()) {
+                    ic_cdk::println!("error: invalid API key synced: {:?}", err);
+                }
+                else {
+                    ChatStorage::save(chat_key(&chat_scope.chat), RegisteredChat {
+                        api_key,
+                        registered_at: ic_cdk::api::time(),
+                    });
+                }
+            },
+            _ => {
+                // only chat installations are supported by now
+            }
+        }
+
+        CommandResponse::Success(SuccessResult { 
+            message: None 
+        })
+    })
+}
\ No newline at end of file
@@ -0,0 +1,396 @@
+use std::collections::HashSet;
+use async_trait::async_trait;
+use oc_bots_sdk::{
+    api::{
+        command::{CommandHandler, SuccessResult},
+        definition::*
+    },
+    oc_api::client::Client,
+    types::{BotCommand, BotCommandContext, UserId}
+};
+use oc_bots_sdk_canister::CanisterRuntime;
+use clap::{CommandFactory, Parser, ValueEnum};
+use crate::{
+    types::{
+        cli::{Cli, Commands, Nft, Wallet},
+        nft::NftId,
+        token::TokenKind
+    },
+    utils::i18n::{tr, Locale}
+};
+use super::cli::{render_error, MemeCli};
+
+const MOODS: &[&str] = &[
+    "funny", "happy", "sad", "weird", "silly", "sarcastic", "wholesome", "angry",
+];
+
+/// Converts the typed arguments of a command into the same command parsed by the CLI
+type ParseFn = fn(&BotCommand, Locale) -> Result<Commands, String>;
+
+/// A '/meme_*' command with typed params, so users get autocompletion and choices.
+/// It shares the handlers with '/meme', which is kept as the power-user interface
+pub struct TypedCommand {
+    definition: BotCommandDefinition,
+    parse: ParseFn,
+}
+
+#[async_trait]
+impl CommandHandler<CanisterRuntime> for TypedCommand {
+    fn definition(
+        &self
+    ) -> &BotCommandDefinition {
+        &self.definition
+    }
+
+    async fn execute(
+        &self,
+        client: Client<CanisterRuntime, BotCommandContext>
+    ) -> Result<SuccessResult, String> {
+        match (self.parse)(&client.context().command, MemeCli::command_locale(&client)) {
+            Ok(command) => {
+                MemeCli::dispatch(command, &client).await
+            },
+            Err(err) => {
+                MemeCli::reply(Err(err), &client)
+            },
+        }
+    }
+}
+
+pub fn commands(
+) -> Vec<TypedCommand> {
+    vec![
+        TypedCommand {
+            definition: definition("meme_search", "Search for meme templates", vec![
+                string_param("query", "Query used to search for meme templates", true, 256, false, vec![]),
+                page_param(),
+            ]),
+            parse: |cmd, _| Ok(Commands::Search {
+                query: cmd.arg::<String>("query"),
+                page: page_arg(cmd),
+            }),
+        },
+        TypedCommand {
+            definition: definition("meme_gen", "Generate a meme from a template", vec![
+                template_param(),
+                string_param("captions", "Captions to be displayed on the image, one per line", true, 1024, true, vec![]),
+            ]),
+            parse: |cmd, locale| Ok(Commands::Gen {
+                id: template_arg(cmd, locale)?,
+                captions: cmd.arg::<String>("captions")
+                    .lines()
+                    .map(|line| line.trim().to_string())
+                    .filter(|line| !line.is_empty())
+                    .collect(),
+            }),
+        },
+        TypedCommand {
+            definition: definition("meme_suggest", "Use AI to suggest captions for generating a meme from a template", vec![
+                template_param(),
+                string_param("mood", "The story mood (default: funny)", false, 32, false, MOODS.iter()
+                    .map(|mood| BotCommandOptionChoice {
+                        name: mood.to_string(),
+                        value: mood.to_string(),
+                    })
+                    .collect()
+                ),
+                string_param("topic", "The story topic, e.g.: crypto, cats, robots, etc (default: crypto)", false, 64, false, vec![]),
+            ]),
+            parse: |cmd, locale| Ok(Commands::Suggest {
+                id: template_arg(cmd, locale)?,
+                mood: cmd.maybe_arg::<String>("mood"),
+                topic: cmd.maybe_arg::<String>("topic"),
+            }),
+        },
+        TypedCommand {
+            definition: definition("meme_post", "Post a meme previously created with '/meme_gen' or '/meme_suggest'", vec![
+                meme_param(),
+            ]),
+            parse: |cmd, _| Ok(Commands::Post {
+                id: cmd.maybe_arg::<String>("id"),
+            }),
+        },
+        TypedCommand {
+            definition: definition("meme_report", "Report a posted meme or a MEME NFT to the moderators", vec![
+                string_param("id", "Alphanumeric id of the meme or numeric id of the MEME NFT", true, 40, false, vec![]),
+                string_param("reason", "Reason of the report", false, 256, false, vec![]),
+            ]),
+            parse: |cmd, _| Ok(Commands::Report {
+                id: cmd.arg::<String>("id"),
+                reason: cmd.maybe_arg::<String>("reason"),
+            }),
+        },
+        TypedCommand {
+            definition: definition("meme_wallet_balance", "Display your balance in the Mementor Wallet", vec![
+                token_param(),
+            ]),
+            parse: |cmd, locale| Ok(Commands::Wallet(Wallet::Balance {
+                token: token_arg(cmd, locale)?,
+            })),
+        },
+        TypedCommand {
+            definition: definition("meme_wallet_address", "Display your address in the Mementor Wallet", vec![
+                token_param(),
+            ]),
+            parse: |cmd, locale| Ok(Commands::Wallet(Wallet::Address {
+                token: token_arg(cmd, locale)?,
+            })),
+        },
+        TypedCommand {
+            definition: definition("meme_wallet_withdraw", "Withdraw tokens from your account in the Mementor Wallet", vec![
+                string_param("amount", "Amount to withdraw in decimal format, eg: 1.25 (omitted with 'all')", false, 48, false, vec![]),
+                string_param("to", "Destination: an account address in hex format (ICP only), an ICRC-1 account or an OC username (default: your OC wallet)", false, 128, false, vec![]),
+                BotCommandParam {
+                    name: "all".to_string(),
+                    description: Some("Withdraw the whole balance, less the transfer fee".to_string()),
+                    placeholder: None,
+                    required: false,
+                    param_type: BotCommandParamType::BooleanParam,
+                },
+                token_param(),
+            ]),
+            parse: |cmd, locale| Ok(Commands::Wallet(Wallet::Withdraw {
+                amount: cmd.maybe_arg::<String>("amount"),
+                to: cmd.maybe_arg::<String>("to"),
+                all: cmd.maybe_arg::<bool>("all").unwrap_or(false),
+                token: token_arg(cmd, locale)?,
+            })),
+        },
+        TypedCommand {
+            definition: definition("meme_wallet_logs", "Display the wallet history: deposits, withdraws, minting payments and refunds", vec![
+                page_param(),
+                token_param(),
+            ]),
+            parse: |cmd, locale| Ok(Commands::Wallet(Wallet::Logs {
+                page: page_arg(cmd),
+                token: token_arg(cmd, locale)?,
+            })),
+        },
+        TypedCommand {
+            definition: definition("meme_nft_mint", "Mint a MEME NFT, previously posted with '/meme_post'", vec![
+                meme_param(),
+            ]),
+            parse: |cmd, _| Ok(Commands::Nft(Nft::Mint {
+                id: cmd.maybe_arg::<String>("id"),
+            })),
+        },
+        TypedCommand {
+            definition: definition("meme_nft_balance", "Print the MEME NFT balance of a user", vec![
+                user_param("owner", "Owner of the NFTs (default: you)", false),
+            ]),
+            parse: |cmd, _| Ok(Commands::Nft(Nft::Balance {
+                owner: cmd.maybe_arg::<UserId>("owner").map(|user| user.to_string()),
+            })),
+        },
+        TypedCommand {
+            definition: definition("meme_nft_tokens", "Display the MEME NFTs of a user", vec![
+                user_param("owner", "Owner of the NFTs (default: you)", false),
+                page_param(),
+            ]),
+            parse: |cmd, _| Ok(Commands::Nft(Nft::Tokens {
+                owner: cmd.maybe_arg::<UserId>("owner").map(|user| user.to_string()),
+                page: page_arg(cmd),
+            })),
+        },
+        TypedCommand {
+            definition: definition("meme_nft_transfer", "Transfer a MEME NFT to another user", vec![
+                BotCommandParam {
+                    name: "id".to_string(),
+                    description: Some("Token id, as returned by '/meme_nft_tokens'".to_string()),
+                    placeholder: Some("Enter the token id".to_string()),
+                    required: true,
+                    param_type: BotCommandParamType::IntegerParam(IntegerParam {
+                        min_value: 0,
+                        max_value: i64::MAX,
+                        choices: vec![],
+                    }),
+                },
+                user_param("to", "Recipient of the NFT", true),
+            ]),
+            parse: |cmd, _| Ok(Commands::Nft(Nft::Transfer {
+                id: cmd.arg::<i64>("id") as NftId,
+                to: cmd.arg::<UserId>("to").to_string(),
+            })),
+        },
+        TypedCommand {
+            definition: definition("meme_nft_logs", "Display logs of MEME NFT transactions", vec![
+                page_param(),
+            ]),
+            parse: |cmd, _| Ok(Commands::Nft(Nft::Logs {
+                page: page_arg(cmd),
+            })),
+        },
+        TypedCommand {
+            definition: definition("meme_nft_status", "Print the status of the MEME NFT collection", vec![]),
+            parse: |_, _| Ok(Commands::Nft(Nft::Status)),
+        },
+        TypedCommand {
+            definition: definition("meme_settings", "Display or change how Mementor behaves in this chat (admins only)", vec![
+                string_param("setting", "The setting to display or change", true, 16, false, Cli::command()
+                    .find_subcommand("settings")
+                    .into_iter()
+                    .flat_map(|cmd| cmd.get_subcommands())
+                    .map(|sub| BotCommandOptionChoice {
+                        name: sub.get_name().to_string(),
+                        value: sub.get_name().to_string(),
+                    })
+                    .collect()
+                ),
+                string_param("value", "The new value, e.g.: sad, es, strict or 'nft mint' (leave empty to restore the default)", false, 256, false, vec![]),
+            ]),
+            parse: settings_command,
+        },
+    ]
+}
+
+fn definition(
+    name: &str,
+    description: &str,
+    params: Vec<BotCommandParam>
+) -> BotCommandDefinition {
+    BotCommandDefinition {
+        name: name.to_string(),
+        description: Some(description.to_string()),
+        placeholder: Some("Please wait...".to_string()),
+        params,
+        permissions: BotPermissions::default().with_message(&HashSet::from([
+            MessagePermission::Text,
+            MessagePermission::Image
+        ])).with_chat(&HashSet::from([
+            ChatPermission::ReadMessages,
+            ChatPermission::ReadChatDetails,
+            ChatPermission::ReadMembership,
+        ])),
+        default_role: None,
+        direct_messages: Some(true),
+    }
+}
+
+fn string_param(
+    name: &str,
+    description: &str,
+    required: bool,
+    max_length: u16,
+    multi_line: bool,
+    choices: Vec<BotCommandOptionChoice<String>>
+) -> BotCommandParam {
+    BotCommandParam {
+        name: name.to_string(),
+        description: Some(description.to_string()),
+        placeholder: Some(format!("Enter the {}", name)),
+        required,
+        param_type: BotCommandParamType::StringParam(StringParam {
+            choices,
+            min_length: 0,
+            max_length,
+            multi_line,
+        }),
+    }
+}
+
+fn user_param(
+    name: &str,
+    description: &str,
+    required: bool
+) -> BotCommandParam {
+    BotCommandParam {
+        name: name.to_string(),
+        description: Some(description.to_string()),
+        placeholder: Some("Select a user".to_string()),
+        required,
+        param_type: BotCommandParamType::UserParam,
+    }
+}
+
+fn template_param(
+) -> BotCommandParam {
+    BotCommandParam {
+        name: "id".to_string(),
+        description: Some("Numeric id of the meme template as returned by '/meme_search'".to_string()),
+        placeholder: Some("Enter the template id".to_string()),
+        required: true,
+        param_type: BotCommandParamType::IntegerParam(IntegerParam {
+            min_value: 0,
+            max_value: u32::MAX as i64,
+            choices: vec![],
+        }),
+    }
+}
+
+fn meme_param(
+) -> BotCommandParam {
+    string_param("id", "Alphanumeric id of the meme (default: your last meme)", false, 32, false, vec![])
+}
+
+fn page_param(
+) -> BotCommandParam {
+    BotCommandParam {
+        name: "page".to_string(),
+        description: Some("Page number (default: 1)".to_string()),
+        placeholder: Some("1".to_string()),
+        required: false,
+        param_type: BotCommandParamType::IntegerParam(IntegerParam {
+            min_value: 1,
+            max_value: 1_000_000,
+            choices: vec![],
+        }),
+    }
+}
+
+fn token_param(
+) -> BotCommandParam {
+    string_param("token", "Token (default: ICP)", false, 8, false, TokenKind::value_variants().iter()
+        .filter_map(|kind| kind.to_possible_value())
+        .map(|value| BotCommandOptionChoice {
+            name: value.get_name().to_string(),
+            value: value.get_name().to_string(),
+        })
+        .collect()
+    )
+}
+
+fn template_arg(
+    cmd: &BotCommand,
+    locale: Locale
+) -> Result<u32, String> {
+    u32::try_from(cmd.arg::<i64>("id"))
+        .map_err(|_| tr(locale, "arg.invalid_template", &[]))
+}
+
+fn token_arg(
+    cmd: &BotCommand,
+    locale: Locale
+) -> Result<TokenKind, String> {
+    match cmd.maybe_arg::<String>("token") {
+        Some(token) => TokenKind::from_str(&token, true)
+            .map_err(|_| tr(locale, "arg.invalid_token", &[&token])),
+        None => Ok(TokenKind::Icp),
+    }
+}
+
+fn page_arg(
+    cmd: &BotCommand
+) -> usize {
+    cmd.maybe_arg::<i64>("page")
+        .map(|page| page.max(1) as usize)
+        .unwrap_or(1)
+}
+
+/// The settings are parsed by the CLI, as their values depend on the setting
+fn settings_command(
+    cmd: &BotCommand,
+    _locale: Locale
+) -> Result<Commands, String> {
+    let mut args = vec![
+        String::new(), 
+        "settings".to_string(), 
+        cmd.arg::<String>("setting")
+    ];
+    if let Some(value) = cmd.maybe_arg::<String>("value") {
+        args.extend(shell_words::split(&value).map_err(|err| err.to_string())?);
+    }
+
+    Cli::try_parse_from(args)
+        .map(|cli| cli.command)
+        .map_err(render_error)
+}
\ No newline at end of file
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use super::commands;
 use oc_bots_sdk::api::definition::*;
 use oc_bots_sdk_canister::{HttpRequest, HttpResponse};
@@ -11,7 +12,14 @@ pub async fn get(
             description: "Create memes, mint them as exclusive MEME NFTs, and earn MEME coins in return! Learn more at https://mementor.fun"
                 .to_string(),
             commands: commands::definitions(),
-            autonomous_config: None,
+            // needed to post the scheduled jobs and the webhook actions
+            autonomous_config: Some(AutonomousConfig {
+                permissions: BotPermissions::default().with_message(&HashSet::from([
+                    MessagePermission::Text,
+                    MessagePermission::Image
+                ])),
+                sync_api_key: true,
+            }),
         },
     )
 }
\ No newline at end of file
@@ -0,0 +1,123 @@
+use std::fmt::Write;
+use oc_bots_sdk_canister::{HttpRequest, HttpResponse};
+use crate::{
+    memory, 
+    state, 
+    storage::{
+        blob::BlobStorage, 
+        event::EventStorage, 
+        nft::NftStorage, 
+        user::UserStorage
+    }, 
+    types::metrics::LLM_LATENCY_BUCKETS
+};
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Exports the canister metrics in the Prometheus text format
+pub async fn get(
+    _request: HttpRequest
+) -> HttpResponse {
+    let mut out = String::new();
+
+    gauge(&mut out, "cycles_balance", "Cycles balance of the canister", 
+        ic_cdk::api::canister_balance128());
+    gauge(&mut out, "heap_memory_bytes", "Size of the heap memory", 
+        heap_size());
+    gauge(&mut out, "stable_memory_bytes", "Size of the stable memory", 
+        ic_cdk::api::stable::stable_size() * 65536);
+
+    header(&mut out, "stable_memory_region_bytes", "Size of each stable memory region", "gauge");
+    for (region, size) in memory::region_sizes() {
+        let _ = writeln!(out, "stable_memory_region_bytes{{region=\"{}\"}} {}", region, size);
+    }
+
+    let blobs = BlobStorage::stats();
+    gauge(&mut out, "users", "Number of users", UserStorage::size());
+    gauge(&mut out, "nfts", "Number of NFTs minted", NftStorage::size());
+    gauge(&mut out, "events", "Number of events logged", EventStorage::size());
+    gauge(&mut out, "blobs", "Number of permanent blobs", blobs.num_blobs);
+    gauge(&mut out, "blobs_bytes", "Size of the permanent blobs", blobs.num_bytes);
+    gauge(&mut out, "temp_blobs", "Number of temporary blobs", blobs.num_temp_blobs);
+    gauge(&mut out, "derived_blobs", "Number of cached derived blobs", blobs.num_derived_blobs);
+
+    state::read(|s| {
+        let metrics = s.metrics();
+
+        header(&mut out, "commands_total", "Number of commands executed", "counter");
+        for (name, cmd) in &metrics.commands {
+            let _ = writeln!(out, "commands_total{{command=\"{}\"}} {}", name, cmd.count);
+        }
+        header(&mut out, "command_errors_total", "Number of commands that failed", "counter");
+        for (name, cmd) in &metrics.commands {
+            let _ = writeln!(out, "command_errors_total{{command=\"{}\"}} {}", name, cmd.errors);
+        }
+
+        let llm = &metrics.llm;
+        counter(&mut out, "llm_calls_total", "Number of LLM calls", llm.calls);
+        counter(&mut out, "llm_failures_total", "Number of LLM calls with an invalid reply", llm.failures);
+        header(&mut out, "llm_latency_ms", "Latency of the LLM calls, in milliseconds", "histogram");
+        let mut cumulative = 0;
+        for (i, le) in LLM_LATENCY_BUCKETS.iter().enumerate() {
+            cumulative += llm.latency_buckets.get(i).copied().unwrap_or(0);
+            let _ = writeln!(out, "llm_latency_ms_bucket{{le=\"{}\"}} {}", le, cumulative);
+        }
+        let _ = writeln!(out, "llm_latency_ms_bucket{{le=\"+Inf\"}} {}", llm.calls);
+        let _ = writeln!(out, "llm_latency_ms_sum {}", llm.latency_sum);
+        let _ = writeln!(out, "llm_latency_ms_count {}", llm.calls);
+
+        counter(&mut out, "mints_total", "Number of NFTs minted", metrics.mints);
+        counter(&mut out, "mint_failures_total", "Number of NFT mints that failed", metrics.mint_failures);
+        counter(&mut out, "mint_amount_e8s_total", "ICP paid for minting, in e8s", metrics.mint_amount);
+        counter(&mut out, "refunds_total", "Number of minting costs refunded", metrics.refunds);
+        counter(&mut out, "refund_failures_total", "Number of minting costs that could not be refunded", metrics.refund_failures);
+        counter(&mut out, "refund_amount_e8s_total", "ICP refunded, in e8s", metrics.refund_amount);
+        counter(&mut out, "rate_limited_total", "Number of commands refused by the rate limiter", metrics.rate_limited);
+        counter(&mut out, "moderation_refusals_total", "Number of captions refused by the moderation filter", metrics.refusals);
+        counter(&mut out, "webhook_rejections_total", "Number of webhook requests refused as disabled, unsigned or replayed", metrics.webhook_rejections);
+    });
+
+    HttpResponse::new(200, out.into_bytes(), CONTENT_TYPE)
+}
+
+fn header(
+    out: &mut String,
+    name: &str,
+    help: &str,
+    kind: &str
+) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+fn gauge<T: std::fmt::Display>(
+    out: &mut String,
+    name: &str,
+    help: &str,
+    value: T
+) {
+    header(out, name, help, "gauge");
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+fn counter<T: std::fmt::Display>(
+    out: &mut String,
+    name: &str,
+    help: &str,
+    value: T
+) {
+    header(out, name, help, "counter");
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+fn heap_size(
+) -> u64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        core::arch::wasm32::memory_size(0) as u64 * 65536
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        0
+    }
+}
\ No newline at end of file
@@ -0,0 +1,199 @@
+use hmac::{Hmac, Mac};
+use oc_bots_sdk_canister::{HttpRequest, HttpResponse};
+use sha2::Sha256;
+use crate::{
+    services::{
+        chat::chat::ChatService, 
+        meme::{self, MemeService}, 
+        moderation::moderation::ModerationService
+    }, 
+    state, 
+    storage::{chat::ChatStorage, webhook::WebhookStorage}, 
+    types::{
+        blob::Blob, 
+        image::IMAGE_FORMAT, 
+        moderation::ModerationStage, 
+        webhook::{
+            Webhook, WebhookAction, WebhookDelivery, WebhookDeliveryStatus, 
+            WebhookName, WEBHOOK_MAX_CLOCK_SKEW
+        }
+    }
+};
+
+const SIGNATURE_HEADER: &str = "x-webhook-signature";
+const TIMESTAMP_HEADER: &str = "x-webhook-timestamp";
+
+/// Executes an action requested by an external system. The request must be signed with the 
+/// secret of the webhook: signature = hex(HMAC-SHA256(secret, "{timestamp}.{body}"))
+pub async fn execute(
+    request: HttpRequest
+) -> HttpResponse {
+    let name: WebhookName = request.path.trim_start_matches("/webhook/").to_string();
+
+    let Some(webhook) = WebhookStorage::load(&name) else {
+        return HttpResponse::not_found();
+    };
+
+    let received_at = ic_cdk::api::time();
+
+    // only authenticated requests are logged, so unauthenticated callers can't flood the log
+    if let Err((status_code, err)) = authenticate(&request, &webhook, &name, received_at / 1_000_000_000) {
+        state::mutate(|s| s.metrics_mut().record_webhook_rejection());
+        return HttpResponse::json(status_code, &serde_json::json!({"error": err}));
+    }
+
+    let mut delivery = WebhookDelivery {
+        id: 0,
+        webhook: name,
+        received_at,
+        action: None,
+        chat: None,
+        status: WebhookDeliveryStatus::Delivered,
+    };
+
+    let (status_code, status) = match handle(&request, &webhook, &mut delivery).await {
+        Ok(()) => {
+            (200, WebhookDeliveryStatus::Delivered)
+        },
+        Err(err) => {
+            err
+        },
+    };
+
+    delivery.status = status.clone();
+    let delivery_id = WebhookStorage::log_delivery(delivery);
+
+    let body = match status {
+        WebhookDeliveryStatus::Delivered => 
+            serde_json::json!({"delivery_id": delivery_id}),
+        WebhookDeliveryStatus::Rejected(err) | WebhookDeliveryStatus::Failed(err) => 
+            serde_json::json!({"delivery_id": delivery_id, "error": err}),
+    };
+
+    HttpResponse::json(status_code, &body)
+}
+
+fn authenticate(
+    request: &HttpRequest,
+    webhook: &Webhook,
+    name: &WebhookName,
+    now: u64
+) -> Result<(), (u16, String)> {
+    if !webhook.enabled {
+        return Err((403, "Webhook disabled".to_string()));
+    }
+
+    let (timestamp, signature) = verify_signature(request, webhook, now)
+        .map_err(|err| (401, err))?;
+
+    // a valid request can only be delivered once
+    if !WebhookStorage::register_nonce(name, timestamp, &signature, now) {
+        return Err((409, "Request already received".to_string()));
+    }
+
+    Ok(())
+}
+
+async fn handle(
+    request: &HttpRequest,
+    webhook: &Webhook,
+    delivery: &mut WebhookDelivery
+) -> Result<(), (u16, WebhookDeliveryStatus)> {
+    let reject = |status_code: u16, err: &str| {
+        (status_code, WebhookDeliveryStatus::Rejected(err.to_string()))
+    };
+
+    let action = serde_json::from_slice::<WebhookAction>(&request.body)
+        .map_err(|err| reject(400, &format!("Invalid action: {}", err)))?;
+
+    delivery.action = Some(action.name().to_string());
+    delivery.chat = Some(action.chat().clone());
+
+    if !webhook.chats.contains(action.chat()) {
+        return Err(reject(403, "Chat not allowed for this webhook"));
+    }
+
+    run(action).await
+        .map_err(|err| (502, WebhookDeliveryStatus::Failed(err)))
+}
+
+fn verify_signature(
+    request: &HttpRequest,
+    webhook: &Webhook,
+    now: u64
+) -> Result<(u64, Vec<u8>), String> {
+    let timestamp = request.get_header(TIMESTAMP_HEADER)
+        .and_then(|t| t.parse::<u64>().ok())
+        .ok_or_else(|| "Missing or invalid timestamp".to_string())?;
+
+    if now.abs_diff(timestamp) > WEBHOOK_MAX_CLOCK_SKEW {
+        return Err("Timestamp out of range".to_string());
+    }
+
+    let signature = request.get_header(SIGNATURE_HEADER)
+        .map(|s| s.trim_start_matches("sha256="))
+        .and_then(|s| hex::decode(s).ok())
+        .ok_or_else(|| "Missing or invalid signature".to_string())?;
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(&webhook.secret)
+        .map_err(|err| err.to_string())?;
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(&request.body);
+
+    mac.verify_slice(&signature)
+        .map_err(|_| "Signature mismatch".to_string())?;
+
+    Ok((timestamp, signature))
+}
+
+async fn run(
+    action: WebhookAction
+) -> Result<(), String> {
+    match action {
+        WebhookAction::PostText { chat, text } => {
+            ChatService::post_text(&chat, text).await
+        },
+        WebhookAction::PostMeme { chat, template_id, captions, mood, topic, caption } => {
+            let tpl = meme::read(|s| match template_id {
+                Some(id) => s.load(&id).cloned(),
+                None => s.random().cloned(),
+            }).ok_or_else(|| "Unknown meme template".to_string())?;
+
+            let settings = ChatStorage::load_settings(&chat);
+
+            let captions = match captions {
+                Some(captions) => captions,
+                None => {
+                    MemeService::gen_captions(
+                        &tpl, 
+                        mood.or(settings.default_mood).unwrap_or("funny".to_string()), 
+                        topic.or(settings.default_topic).unwrap_or("crypto".to_string()), 
+                        settings.language
+                    ).await?
+                }
+            };
+
+            ModerationService::check(
+                &captions, 
+                settings.nsfw, 
+                ModerationStage::Render, 
+                None, 
+                Some(chat.clone())
+            ).await?;
+
+            let (jpeg, width, height) = MemeService::gen_jpeg(&tpl, &captions)?;
+
+            ChatService::post_image(
+                &chat, 
+                Blob {
+                    mime_type: IMAGE_FORMAT.to_mime_type().to_string(),
+                    data: jpeg,
+                }, 
+                width, 
+                height, 
+                caption
+            ).await
+        },
+    }
+}
\ No newline at end of file
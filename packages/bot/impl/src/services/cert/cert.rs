@@ -0,0 +1,453 @@
+use std::collections::{BTreeSet, HashMap};
+use ic_http_certification::{
+    utils::add_v2_certificate_header, 
+    DefaultCelBuilder, DefaultResponseCertification, 
+    DefaultResponseOnlyCelExpression, HttpCertification, 
+    HttpCertificationPath, HttpCertificationTree, 
+    HttpCertificationTreeEntry, HttpResponse, 
+    CERTIFICATE_EXPRESSION_HEADER_NAME
+};
+use sha2::{Digest, Sha256};
+use crate::{
+    resources::{LOGO, NFT_PLACEHOLDER, NFT_PLACEHOLDER_MIME_TYPE}, 
+    state, 
+    storage::{
+        asset::AssetStorage, blob::BlobStorage, 
+        derived_blob::DerivedBlobStorage, temp_blob::TempBlobStorage
+    }, 
+    types::{
+        blob::{BlobHash, BlobId, BlobMeta}, 
+        derived_blob::DerivedBlobKey
+    }
+};
+
+const CERTIFIED_HEADERS: &[&str] = &["Content-Type"];
+const CERTIFIED_RANGE_HEADERS: &[&str] = &["Content-Type", "Content-Range"];
+// dynamic routes, served without certification
+const UNCERTIFIED_PATHS: &[&str] = &["/metrics"];
+
+/// Which of the responses of a path was certified
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ResponseKind {
+    // the full response (200) or the "not modified" one (304)
+    Full(u16),
+    // the partial response (206) with the whole chunk at this index
+    Range(u32),
+    // the full or "not modified" response of a cached derived blob, by transform key
+    Derived(u64, u16),
+    // dynamic routes only
+    Skip,
+}
+
+/// Keeps the certification tree of every immutable response served by 
+/// the blob and asset routes, so boundary nodes can verify them
+#[derive(Default)]
+pub struct CertService {
+    tree: HttpCertificationTree,
+    entries: HashMap<String, Vec<(ResponseKind, HttpCertificationTreeEntry<'static>)>>,
+    // paths where each cached derived blob was certified, so they can be uncertified once evicted
+    derived_paths: HashMap<DerivedBlobKey, Vec<String>>,
+}
+
+impl CertService {
+    pub fn blob_paths(
+        id: BlobId
+    ) -> [String; 2] {
+        [
+            format!("/blobs/{}", id),
+            format!("/assets/nfts/{}.jpg", id),
+        ]
+    }
+
+    pub fn temp_blob_path(
+        id: BlobId
+    ) -> String {
+        format!("/blobs/{}", id)
+    }
+
+    pub fn asset_path(
+        path: &str
+    ) -> String {
+        format!("/assets/{}", path)
+    }
+
+    /// Certifies every stored blob and asset. The tree lives in the heap, 
+    /// so it must be rebuilt after each upgrade
+    pub fn rebuild(
+        &mut self,
+        hidden_blobs: &BTreeSet<BlobId>
+    ) {
+        BlobStorage::for_each_meta(|id, meta| {
+            if hidden_blobs.contains(&id) {
+                self.hide(id);
+            }
+            else {
+                self.certify_blob_paths(id, meta);
+            }
+        });
+
+        self.certify(
+            Self::asset_path("nft_logo.png"), 
+            "image/png", 
+            Sha256::digest(LOGO).into()
+        );
+
+        // derived blobs requested through other paths are certified again on demand
+        DerivedBlobStorage::for_each(|key, mime_type, hash| {
+            if !hidden_blobs.contains(&key.0) {
+                for path in Self::blob_paths(key.0) {
+                    self.certify_derived_response(path, key, mime_type, hash);
+                }
+            }
+        });
+
+        TempBlobStorage::for_each_live(|id, mime_type, hash| {
+            self.certify(Self::temp_blob_path(id), mime_type, hash);
+        });
+
+        AssetStorage::for_each(|path, blob_id| {
+            if let Some(meta) = BlobStorage::load_meta(blob_id) {
+                self.certify_with_ranges(Self::asset_path(path), &meta);
+            }
+        });
+
+        for path in UNCERTIFIED_PATHS {
+            self.skip_responses(path.to_string());
+        }
+
+        self.commit();
+    }
+
+    pub fn certify_blob(
+        &mut self,
+        id: BlobId,
+        meta: &BlobMeta
+    ) {
+        if state::read(|s| s.takedowns().hidden_blobs.contains(&id)) {
+            self.hide(id);
+        }
+        else {
+            self.certify_blob_paths(id, meta);
+        }
+        self.commit();
+    }
+
+    /// A hidden blob is no longer served, and the placeholder is served in place of its NFT image
+    pub fn hide_blob(
+        &mut self,
+        id: BlobId
+    ) {
+        self.hide(id);
+        self.commit();
+    }
+
+    pub fn uncertify_blob(
+        &mut self,
+        id: BlobId
+    ) {
+        for path in Self::blob_paths(id) {
+            self.uncertify(&path);
+        }
+        self.commit();
+    }
+
+    /// Temp blobs are only served on their blob path, until their slot is reused
+    pub fn certify_temp_blob(
+        &mut self,
+        id: BlobId,
+        mime_type: &str,
+        hash: BlobHash
+    ) {
+        self.certify(Self::temp_blob_path(id), mime_type, hash);
+        self.commit();
+    }
+
+    pub fn uncertify_temp_blob(
+        &mut self,
+        id: BlobId
+    ) {
+        self.uncertify(&Self::temp_blob_path(id));
+        self.commit();
+    }
+
+    pub fn certify_asset(
+        &mut self,
+        path: &str,
+        meta: &BlobMeta
+    ) {
+        self.certify_with_ranges(Self::asset_path(path), meta);
+        self.commit();
+    }
+
+    /// Derived blobs are certified under their own hash, on the path they were requested through
+    pub fn certify_derived(
+        &mut self,
+        path: &str,
+        key: DerivedBlobKey,
+        mime_type: &str,
+        hash: BlobHash
+    ) {
+        self.certify_derived_response(path.to_string(), key, mime_type, hash);
+        self.commit();
+    }
+
+    pub fn uncertify_derived(
+        &mut self,
+        key: DerivedBlobKey
+    ) {
+        for path in self.derived_paths.remove(&key).unwrap_or_default() {
+            self.remove_entries(&path, |kind| matches!(kind, ResponseKind::Derived(k, _) if *k == key.1));
+        }
+        self.commit();
+    }
+
+    pub fn is_certified(
+        &self,
+        path: &str,
+        kind: ResponseKind
+    ) -> bool {
+        self.entries.get(path)
+            .map(|entries| entries.iter().any(|(k, _)| *k == kind))
+            .unwrap_or(false)
+    }
+
+    /// Adds the certificate headers to a response previously certified for this path.
+    /// Only available in query calls, as it needs the data certificate
+    pub fn add_certificate_headers(
+        &self,
+        path: &str,
+        kind: ResponseKind,
+        response: &mut HttpResponse
+    ) -> bool {
+        let Some(entry) = self.entries.get(path)
+            .and_then(|entries| entries.iter().find(|(k, _)| *k == kind))
+            .map(|(_, entry)| entry) else {
+            return false;
+        };
+
+        let Some(data_certificate) = ic_cdk::api::data_certificate() else {
+            return false;
+        };
+
+        let Ok(witness) = self.tree.witness(entry, path) else {
+            return false;
+        };
+
+        let cel_expr = match kind {
+            ResponseKind::Full(_) | ResponseKind::Derived(..) => Self::cel_expr(CERTIFIED_HEADERS).to_string(),
+            ResponseKind::Range(_) => Self::cel_expr(CERTIFIED_RANGE_HEADERS).to_string(),
+            ResponseKind::Skip => DefaultCelBuilder::skip_certification().to_string(),
+        };
+
+        response.headers.push((
+            CERTIFICATE_EXPRESSION_HEADER_NAME.to_string(),
+            cel_expr,
+        ));
+
+        add_v2_certificate_header(
+            &data_certificate,
+            response,
+            &witness,
+            &HttpCertificationPath::exact(path).to_expr_path()
+        );
+
+        true
+    }
+
+    /// Certifies the full response (200) and the "not modified" one (304) of a path
+    fn certify(
+        &mut self,
+        path: String,
+        mime_type: &str,
+        hash: BlobHash
+    ) {
+        self.uncertify(&path);
+
+        // the body is not needed, as its hash is already known
+        self.certify_response(path.clone(), ResponseKind::Full(200), mime_type, vec![], Some(hash));
+        self.certify_response(path, ResponseKind::Full(304), mime_type, vec![], None);
+    }
+
+    fn certify_blob_paths(
+        &mut self,
+        id: BlobId,
+        meta: &BlobMeta
+    ) {
+        for path in Self::blob_paths(id) {
+            self.certify_with_ranges(path, meta);
+        }
+    }
+
+    /// Besides the full responses, each chunk of a chunked blob can be served as a 
+    /// partial response, certified with the hash of the chunk
+    fn certify_with_ranges(
+        &mut self,
+        path: String,
+        meta: &BlobMeta
+    ) {
+        self.certify(path.clone(), &meta.mime_type, meta.hash);
+
+        for (index, (start, end, hash)) in meta.chunk_ranges().into_iter().enumerate() {
+            self.certify_response(
+                path.clone(), 
+                ResponseKind::Range(index as u32), 
+                &meta.mime_type, 
+                vec![("Content-Range".to_string(), meta.content_range(start, end))], 
+                Some(hash)
+            );
+        }
+    }
+
+    fn certify_derived_response(
+        &mut self,
+        path: String,
+        key: DerivedBlobKey,
+        mime_type: &str,
+        hash: BlobHash
+    ) {
+        self.certify_response(path.clone(), ResponseKind::Derived(key.1, 200), mime_type, vec![], Some(hash));
+        self.certify_response(path.clone(), ResponseKind::Derived(key.1, 304), mime_type, vec![], None);
+
+        let paths = self.derived_paths.entry(key).or_default();
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+
+    fn hide(
+        &mut self,
+        id: BlobId
+    ) {
+        let [blob_path, nft_path] = Self::blob_paths(id);
+        self.uncertify(&blob_path);
+        self.certify(
+            nft_path, 
+            NFT_PLACEHOLDER_MIME_TYPE, 
+            Sha256::digest(NFT_PLACEHOLDER).into()
+        );
+    }
+
+    /// Only for the paths that are never certified
+    fn skip_responses(
+        &mut self,
+        path: String
+    ) {
+        let entry = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact(path.clone()), 
+            HttpCertification::skip()
+        );
+        self.insert(path, ResponseKind::Skip, entry);
+    }
+
+    fn certify_response(
+        &mut self,
+        path: String,
+        kind: ResponseKind,
+        mime_type: &str,
+        extra_headers: Vec<(String, String)>,
+        body_hash: Option<BlobHash>
+    ) {
+        let (status_code, cel_expr) = match kind {
+            ResponseKind::Full(status_code) | 
+            ResponseKind::Derived(_, status_code) => (status_code, Self::cel_expr(CERTIFIED_HEADERS)),
+            ResponseKind::Range(_) => (206, Self::cel_expr(CERTIFIED_RANGE_HEADERS)),
+            ResponseKind::Skip => return self.skip_responses(path),
+        };
+
+        let mut headers = vec![
+            ("Content-Type".to_string(), mime_type.to_string()),
+        ];
+        headers.extend(extra_headers);
+        headers.push((CERTIFICATE_EXPRESSION_HEADER_NAME.to_string(), cel_expr.to_string()));
+
+        let response = HttpResponse {
+            status_code,
+            headers,
+            body: vec![],
+            upgrade: None,
+        };
+
+        let certification = match HttpCertification::response_only(
+            &cel_expr, 
+            &response, 
+            body_hash
+        ) {
+            Ok(certification) => certification,
+            Err(err) => {
+                ic_cdk::println!("error: certifying {}: {}", path, err);
+                return;
+            }
+        };
+
+        let entry = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact(path.clone()), 
+            certification
+        );
+        self.insert(path, kind, entry);
+    }
+
+    fn insert(
+        &mut self,
+        path: String,
+        kind: ResponseKind,
+        entry: HttpCertificationTreeEntry<'static>
+    ) {
+        self.tree.insert(&entry);
+
+        let entries = self.entries.entry(path).or_default();
+        if let Some(pos) = entries.iter().position(|(k, _)| *k == kind) {
+            let (_, prev) = entries.swap_remove(pos);
+            self.tree.delete(&prev);
+        }
+        entries.push((kind, entry));
+    }
+
+    /// Removes every response certified for the path
+    fn uncertify(
+        &mut self,
+        path: &str
+    ) {
+        self.remove_entries(path, |_| true);
+    }
+
+    fn remove_entries<F>(
+        &mut self,
+        path: &str,
+        f: F
+    ) 
+        where F: Fn(&ResponseKind) -> bool {
+        let Some(entries) = self.entries.get_mut(path) else {
+            return;
+        };
+
+        entries.retain(|(kind, entry)| {
+            if f(kind) {
+                self.tree.delete(entry);
+                false
+            }
+            else {
+                true
+            }
+        });
+
+        if entries.is_empty() {
+            self.entries.remove(path);
+        }
+    }
+
+    fn commit(
+        &self
+    ) {
+        ic_cdk::api::set_certified_data(&self.tree.root_hash());
+    }
+
+    fn cel_expr(
+        headers: &'static [&'static str]
+    ) -> DefaultResponseOnlyCelExpression<'static> {
+        DefaultCelBuilder::response_only_certification()
+            .with_response_certification(
+                DefaultResponseCertification::certified_response_headers(headers)
+            )
+            .build()
+    }
+}
\ No newline at end of file
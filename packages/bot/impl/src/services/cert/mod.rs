@@ -0,0 +1,22 @@
+pub mod cert;
+pub use cert::*;
+
+use std::cell::RefCell;
+
+thread_local! {
+    static CERT_SERVICE: RefCell<CertService> = RefCell::new(CertService::default());
+}
+
+pub fn read<F, R>(
+    fun: F
+) -> R 
+    where F: FnOnce(&CertService) -> R {
+    CERT_SERVICE.with_borrow(|s| fun(s))
+}
+
+pub fn mutate<F, R>(
+    fun: F
+) -> R 
+    where F: FnOnce(&mut CertService) -> R {
+    CERT_SERVICE.with_borrow_mut(|s| fun(s))
+}
\ No newline at end of file
@@ -0,0 +1,111 @@
+use oc_bots_sdk::{
+    oc_api::{
+        actions::{send_message, ActionArgsBuilder}, 
+        client::Client
+    }, 
+    types::{
+        BlobReference, BotApiKeyContext, ImageContent, MessageContentInitial
+    }
+};
+use oc_bots_sdk_canister::{env, CanisterRuntime, OPENCHAT_CLIENT_FACTORY};
+use crate::{
+    storage::{blob::BlobStorage, chat::ChatStorage}, 
+    types::{
+        blob::{Blob, BlobRefKind}, 
+        chat::ChatKey
+    }, 
+    utils::image::create_thumbnail
+};
+
+/// Acts autonomously on the chats registered, using the API key of each one
+pub struct ChatService;
+
+impl ChatService {
+    pub async fn post_text(
+        key: &ChatKey,
+        text: String
+    ) -> Result<(), String> {
+        let client = Self::client(key)?;
+        
+        Self::send(
+            &client, 
+            MessageContentInitial::from_text(text)
+        ).await
+    }
+
+    pub async fn post_image(
+        key: &ChatKey,
+        blob: Blob,
+        width: u32,
+        height: u32,
+        caption: Option<String>
+    ) -> Result<(), String> {
+        let client = Self::client(key)?;
+
+        let format = image::ImageFormat::from_mime_type(&blob.mime_type)
+            .ok_or_else(|| format!("Unsupported image type: {}", blob.mime_type))?;
+        let thumbnail_data = create_thumbnail(
+            &blob.data, 
+            width / 5,
+            height / 5,
+            format
+        )?;
+
+        let mime_type = blob.mime_type.clone();
+        let blob_id = BlobStorage::save(blob, BlobRefKind::Post);
+
+        let res = Self::send(
+            &client, 
+            MessageContentInitial::Image(ImageContent {
+                mime_type,
+                width,
+                height,
+                caption,
+                blob_reference: Some(BlobReference {
+                    canister_id: env::canister_id(),
+                    blob_id,
+                }),
+                thumbnail_data,
+            })
+        ).await;
+
+        if res.is_err() {
+            BlobStorage::release(blob_id, BlobRefKind::Post);
+        }
+
+        res
+    }
+
+    fn client(
+        key: &ChatKey
+    ) -> Result<Client<CanisterRuntime, BotApiKeyContext>, String> {
+        let chat = ChatStorage::load(key)
+            .ok_or_else(|| format!("Unknown chat: {}", key))?;
+
+        let context = BotApiKeyContext::parse_api_key(chat.api_key)
+            .map_err(|err| format!("Invalid API key of chat {}: {:?}", key, err))?;
+
+        Ok(OPENCHAT_CLIENT_FACTORY.build(context))
+    }
+
+    async fn send(
+        client: &Client<CanisterRuntime, BotApiKeyContext>,
+        content: MessageContentInitial
+    ) -> Result<(), String> {
+        match client
+                .send_message(content)
+                .with_block_level_markdown(true)
+                .execute_async()
+                .await {
+            Ok(send_message::Response::Success(_)) => {
+                Ok(())
+            },
+            Err(err) => {
+                Err(format!("Sending message failed: {}", err.1))
+            },
+            Ok(res) => {
+                Err(format!("Sending message failed: {:?}", res))
+            }
+        }
+    }
+}
\ No newline at end of file
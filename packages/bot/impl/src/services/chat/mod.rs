@@ -0,0 +1 @@
+pub mod chat;
\ No newline at end of file
@@ -0,0 +1,174 @@
+use std::cell::Cell;
+use ic_ledger_types::{
+    query_archived_blocks, query_blocks, AccountIdentifier, Block, 
+    GetBlocksArgs, Operation, DEFAULT_SUBACCOUNT, MAINNET_LEDGER_CANISTER_ID
+};
+use crate::{
+    services::wallet::wallet::WalletService, 
+    state, 
+    storage::{deposit::DepositStorage, user::UserStorage}, 
+    types::{
+        token::TokenKind, 
+        user::{TxReason, UserTransaction}
+    }
+};
+
+pub const DEPOSITS_SCAN_INTERVAL: u64 = 60; // every minute
+const MAX_BLOCKS_PER_SCAN: u64 = 1000;
+
+thread_local! {
+    static SCANNING: Cell<bool> = Cell::new(false);
+}
+
+/// Held while a scan is running, so the timer can't start another one. 
+/// Released when dropped, on every exit path
+struct ScanGuard;
+
+impl ScanGuard {
+    fn acquire(
+    ) -> Option<Self> {
+        if SCANNING.replace(true) {
+            None
+        }
+        else {
+            Some(Self)
+        }
+    }
+}
+
+impl Drop for ScanGuard {
+    fn drop(
+        &mut self
+    ) {
+        SCANNING.set(false);
+    }
+}
+
+/// Scans the ICP ledger for the transfers to the users' deposit accounts and records them 
+/// in their wallet history. The MEME ledger is not scanned, so MEME deposits only show up 
+/// in the balance, not in the history
+pub struct DepositService;
+
+impl DepositService {
+    pub async fn scan(
+    ) {
+        let Some(_guard) = ScanGuard::acquire() else {
+            return;
+        };
+
+        if let Err(err) = Self::scan_next().await {
+            ic_cdk::println!("error: scanning the ledger for deposits: {}", err);
+        }
+    }
+
+    async fn scan_next(
+    ) -> Result<(), String> {
+        let Some(start) = state::read(|s| s.deposits_next_block()) else {
+            return Self::start().await;
+        };
+
+        let res = query_blocks(
+            MAINNET_LEDGER_CANISTER_ID, 
+            GetBlocksArgs {
+                start,
+                length: MAX_BLOCKS_PER_SCAN,
+            }
+        ).await
+            .map_err(|e| e.1)?;
+
+        // the oldest blocks may have been moved to the archives already. An archive can 
+        // return fewer blocks than requested, so each range is fetched in pages
+        let mut next = start;
+        for range in res.archived_blocks {
+            let end = range.start + range.length;
+            let mut page_start = range.start;
+            while page_start < end {
+                let blocks = query_archived_blocks(
+                    &range.callback, 
+                    GetBlocksArgs {
+                        start: page_start,
+                        length: end - page_start,
+                    }
+                ).await
+                    .map_err(|e| e.1)?
+                    .map_err(|e| format!("{:?}", e))?
+                    .blocks;
+
+                if blocks.is_empty() {
+                    return Err(format!("archive returned no blocks from {}", page_start));
+                }
+
+                next = Self::index(next, page_start, &blocks)
+                    .ok_or_else(|| format!("gap in the archived blocks, from {} to {}", next, page_start))?;
+                state::mutate(|s| s.set_deposits_next_block(next));
+
+                page_start += blocks.len() as u64;
+            }
+        }
+
+        next = Self::index(next, res.first_block_index, &res.blocks)
+            .ok_or_else(|| format!("gap in the blocks, from {} to {}", next, res.first_block_index))?;
+        state::mutate(|s| s.set_deposits_next_block(next));
+
+        Ok(())
+    }
+
+    /// On the first scan, the deposit accounts of the existing users are indexed and the 
+    /// scan starts at the tip of the ledger
+    async fn start(
+    ) -> Result<(), String> {
+        let res = query_blocks(
+            MAINNET_LEDGER_CANISTER_ID, 
+            GetBlocksArgs {
+                start: 0,
+                length: 0,
+            }
+        ).await
+            .map_err(|e| e.1)?;
+
+        for user_id in UserStorage::ids() {
+            DepositStorage::register(user_id);
+        }
+
+        state::mutate(|s| s.set_deposits_next_block(res.chain_length));
+
+        Ok(())
+    }
+
+    /// Records the deposits found in the blocks not scanned yet. Returns the index of the next 
+    /// block, or None if the blocks don't follow the ones already scanned
+    fn index(
+        next: u64,
+        first_block: u64,
+        blocks: &[Block]
+    ) -> Option<u64> {
+        if first_block > next {
+            return None;
+        }
+
+        // the refunds are sent from the bot and already recorded by the mint operations
+        let bot_acc_id = AccountIdentifier::new(&ic_cdk::id(), &DEFAULT_SUBACCOUNT);
+
+        for (i, block) in blocks.iter().enumerate().skip((next - first_block) as usize) {
+            let Some(Operation::Transfer { from, to, amount, .. }) = &block.transaction.operation else {
+                continue;
+            };
+
+            if *from == bot_acc_id {
+                continue;
+            }
+
+            if let Some(user_id) = DepositStorage::find(to) {
+                WalletService::record(user_id, UserTransaction::Credit { 
+                    token: TokenKind::Icp, 
+                    amount: amount.e8s() as u128, 
+                    reason: TxReason::Deposit { from: from.to_hex() }, 
+                    block_num: (first_block + i as u64) as u128, 
+                    timestamp: (block.timestamp.timestamp_nanos / 1_000_000_000) as _,
+                });
+            }
+        }
+
+        Some(next.max(first_block + blocks.len() as u64))
+    }
+}
\ No newline at end of file
@@ -0,0 +1 @@
+pub mod deposit;
\ No newline at end of file
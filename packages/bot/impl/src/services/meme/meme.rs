@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, io::Cursor};
 use ic_stable_structures::Storable;
 use image::{Rgba, RgbaImage};
 use sha2::{
@@ -13,11 +13,13 @@ use tantivy::schema::{
 use tiny_skia::Color;
 use ic_llm::Model;
 use crate::{
+    state, 
     storage::{
         image::ImageStorage, 
         thumb::ThumbStorage
     }, 
     types::{
+        image::IMAGE_FORMAT, 
         meme::{MemeId, MEME_ID_SIZE}, 
         meme_tpl::{MemeTpl, MemeTplTextBox}, 
         thumb::{THUMB_HEIGHT, THUMB_WIDTH}
@@ -27,7 +29,9 @@ use crate::{
         full_text_indexer::{
             Field, FieldOptions, FullTextIndexer
         }, 
-        out_font::OutlinedFont
+        image::rgba8_to_rgb8, 
+        out_font::OutlinedFont, 
+        rng
     }
 };
 
@@ -98,6 +102,18 @@ impl MemeService {
         self.memes.get(id)
     }
 
+    pub fn random(
+        &self
+    ) -> Option<&MemeTpl> {
+        if self.memes.is_empty() {
+            return None;
+        }
+
+        let mut ids = self.memes.keys().collect::<Vec<_>>();
+        ids.sort();
+        self.memes.get(ids[rng::gen_range(0..ids.len())])
+    }
+
     pub fn search(
         &self,
         what: &str,
@@ -201,6 +217,26 @@ impl MemeService {
         }
     }
 
+    /// Generates the meme image, encoded as JPEG. Returns its data, width and height
+    pub fn gen_jpeg(
+        meme: &MemeTpl,
+        texts: &Vec<String>
+    ) -> Result<(Vec<u8>, u32, u32), String> {
+        let img = OutlinedFont::roboto(|font| {
+            Self::gen_image(
+                meme, 
+                &texts.iter().map(|t| t.to_uppercase()).collect(), 
+                font
+            ).map(|img| rgba8_to_rgb8(&img))
+        })?;
+
+        let mut jpeg: Vec<u8> = Vec::new();
+        img.write_to(&mut Cursor::new(&mut jpeg), IMAGE_FORMAT)
+            .map_err(|e| e.to_string())?;
+
+        Ok((jpeg, img.width(), img.height()))
+    }
+
     pub fn draw_texts(
         texts: &Vec<String>,
         meme: &MemeTpl,
@@ -302,7 +338,8 @@ impl MemeService {
     pub async fn gen_captions(
         tpl: &MemeTpl,
         mood: String,
-        topic: String
+        topic: String,
+        language: Option<String>
     ) -> Result<Vec<String>, String> {
         let num_captions = if tpl.boxes.len() == 0 {
             2
@@ -317,14 +354,26 @@ impl MemeService {
             .replace("{mood}", &mood.replace('"', "'"))
             .replace("{topic}", &topic.replace('"', "'"))
             .replace("{num_boxes}", &num_captions.to_string());
+
+        let prompt = match language {
+            Some(language) => format!(
+                "{}. Write the captions in {}", 
+                prompt, 
+                language.replace('"', "'")
+            ),
+            None => prompt,
+        };
         
+        let started_at = ic_cdk::api::time();
         let res = ic_llm::prompt(Model::Llama3_1_8B, prompt)
             .await;
+        let latency = (ic_cdk::api::time() - started_at) / 1_000_000;
+
+        let captions = serde_json::from_str::<Vec<String>>(&res.trim());
 
-        let captions: Vec<String> = serde_json::from_str(&res.trim())
-            .map_err(|err| err.to_string())?;
+        state::mutate(|s| s.metrics_mut().record_llm_call(latency, captions.is_err()));
 
-        Ok(captions)
+        captions.map_err(|err| err.to_string())
     }
 }
 
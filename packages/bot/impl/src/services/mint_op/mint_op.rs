@@ -0,0 +1,294 @@
+use candid::Principal;
+use ic_ledger_types::{AccountIdentifier, DEFAULT_SUBACCOUNT};
+use crate::{
+    services::{token::token::TokenService, wallet::wallet::WalletService}, 
+    state, 
+    storage::mint_op::MintOpStorage, 
+    types::{
+        blob::BlobId, 
+        meme::MemeId, 
+        mint_op::{MintOp, MintOpId, MintOpResolution, MintOpStep, MintPayment}, 
+        token::TokenKind, 
+        user::{TxReason, UserTransaction}
+    }
+};
+
+pub const MINT_OPS_RETRY_INTERVAL: u64 = 5 * 60; // every 5 minutes
+// after that, the operation must be resolved by an administrator
+const MAX_ATTEMPTS: u32 = 12;
+// the ledger only deduplicates transfers created in the last 24 hours
+const TRANSFER_DEDUP_WINDOW: u64 = 23 * 60 * 60 * 1_000_000_000;
+
+/// Journals the steps of each mint in stable memory, so the refunds and the fee transfers 
+/// that fail are retried and nothing is lost if the canister traps
+pub struct MintOpService;
+
+impl MintOpService {
+    pub fn begin(
+        user: Principal,
+        meme_id: &MemeId,
+        blob_id: BlobId,
+        cost: u64,
+        team_fee: u64,
+        team_account: Principal,
+        payment: MintPayment
+    ) -> MintOp {
+        let now = ic_cdk::api::time();
+        MintOpStorage::create(MintOp {
+            id: 0,
+            user,
+            meme_id: meme_id.clone(),
+            blob_id,
+            cost,
+            team_fee,
+            team_account,
+            payment: Some(payment),
+            debit_block: None,
+            token_id: None,
+            step: MintOpStep::Debiting,
+            transfer_at: now,
+            num_attempts: 0,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Moves the operation to the next step, persisting it before any call is made
+    pub fn advance(
+        op: &mut MintOp,
+        step: MintOpStep
+    ) {
+        let now = ic_cdk::api::time();
+        op.step = step;
+        op.transfer_at = now;
+        op.num_attempts = 0;
+        op.updated_at = now;
+        MintOpStorage::save(op);
+    }
+
+    pub fn fail(
+        op: &mut MintOp,
+        step: MintOpStep,
+        error: String
+    ) {
+        op.last_error = Some(error);
+        Self::advance(op, step);
+    }
+
+    /// Debits the minting cost from where the user chose to pay
+    pub async fn debit(
+        op: &mut MintOp
+    ) -> Result<(), String> {
+        let icp = TokenService::get(TokenKind::Icp).await?;
+
+        let block_num = match op.payment.unwrap_or(MintPayment::Deposit) {
+            MintPayment::Deposit => {
+                WalletService::transfer_at(
+                    &icp, 
+                    op.user.into(), 
+                    AccountIdentifier::new(&ic_cdk::id(), &DEFAULT_SUBACCOUNT), 
+                    op.cost,
+                    op.transfer_at
+                ).await
+                    .map(|(block_num, _)| block_num)?
+            },
+            MintPayment::Approval => {
+                WalletService::transfer_from_at(
+                    &icp, 
+                    op.user, 
+                    op.cost, 
+                    op.transfer_at
+                ).await?
+            },
+        };
+
+        op.debit_block = Some(block_num);
+        MintOpStorage::save(op);
+
+        // the user also pays the ledger fee
+        WalletService::record(op.user, UserTransaction::Debit { 
+            token: TokenKind::Icp, 
+            amount: op.cost as u128 + icp.fee, 
+            reason: TxReason::MintPayment { meme_id: op.meme_id.clone() }, 
+            block_num: block_num as u128, 
+            timestamp: (op.transfer_at / 1_000_000_000) as _,
+        });
+
+        Ok(())
+    }
+
+    /// Returns the minting cost, plus the ledger fee paid by the user, from the bot to where 
+    /// it was taken from
+    pub async fn refund(
+        op: &mut MintOp
+    ) -> Result<(), String> {
+        let to = match op.payment.unwrap_or(MintPayment::Deposit) {
+            MintPayment::Deposit => AccountIdentifier::new(&ic_cdk::id(), &op.user.into()),
+            MintPayment::Approval => AccountIdentifier::new(&op.user, &DEFAULT_SUBACCOUNT),
+        };
+
+        let res = match TokenService::get(TokenKind::Icp).await {
+            Ok(icp) => {
+                let amount = op.cost + icp.fee as u64;
+                WalletService::transfer_at(&icp, None, to, amount, op.transfer_at).await
+                    .map(|(block_num, _)| (block_num, amount))
+            },
+            Err(err) => {
+                Err(err)
+            }
+        };
+
+        if let Ok((block_num, amount)) = &res {
+            WalletService::record(op.user, UserTransaction::Credit { 
+                token: TokenKind::Icp, 
+                amount: *amount as u128, 
+                reason: TxReason::MintRefund { meme_id: op.meme_id.clone() }, 
+                block_num: *block_num as u128, 
+                timestamp: (ic_cdk::api::time() / 1_000_000_000) as _,
+            });
+        }
+
+        Self::complete_step(op, MintOpStep::Refunded, res.map(|_| ()))
+    }
+
+    pub async fn pay_fee(
+        op: &mut MintOp
+    ) -> Result<(), String> {
+        let res = if op.team_fee > 0 {
+            match TokenService::get(TokenKind::Icp).await {
+                Ok(icp) => {
+                    WalletService::transfer_at(
+                        &icp, 
+                        None, 
+                        AccountIdentifier::new(&op.team_account, &DEFAULT_SUBACCOUNT), 
+                        op.team_fee,
+                        op.transfer_at
+                    ).await
+                        .map(|_| ())
+                },
+                Err(err) => {
+                    Err(err)
+                }
+            }
+        }
+        else {
+            Ok(())
+        };
+
+        Self::complete_step(op, MintOpStep::Completed, res)
+    }
+
+    fn complete_step(
+        op: &mut MintOp,
+        next: MintOpStep,
+        res: Result<(), String>
+    ) -> Result<(), String> {
+        match res {
+            Ok(()) => {
+                op.last_error = None;
+                Self::advance(op, next);
+                Ok(())
+            },
+            Err(err) => {
+                ic_cdk::println!("error: Mint operation {} failed at {:?}: {}", op.id, op.step, err);
+                op.num_attempts += 1;
+                op.last_error = Some(err.clone());
+                op.updated_at = ic_cdk::api::time();
+                MintOpStorage::save(op);
+                Err(err)
+            }
+        }
+    }
+
+    async fn run_step(
+        op: &mut MintOp
+    ) -> Result<(), String> {
+        // out of the ledger's deduplication window, a retry would be a new transfer and could 
+        // pay twice, so the ledger must be checked by an administrator, who closes the operation
+        if ic_cdk::api::time().saturating_sub(op.transfer_at) > TRANSFER_DEDUP_WINDOW {
+            let next = op.step.clone();
+            return Self::complete_step(op, next, Err(
+                "Out of the ledger's deduplication window. Check the ledger and close the operation".to_string()
+            ));
+        }
+
+        match op.step {
+            MintOpStep::Refunding => {
+                let res = Self::refund(op).await;
+                state::mutate(|s| s.metrics_mut().record_refund(op.cost, res.is_err()));
+                res
+            },
+            MintOpStep::PayingFee => {
+                Self::pay_fee(op).await
+            },
+            _ => {
+                Err(format!("Mint operation {} can't be retried at {:?}", op.id, op.step))
+            }
+        }
+    }
+
+    /// Called by the timer to retry the refunds and the fee transfers that failed
+    pub async fn retry_pending(
+    ) {
+        let now = ic_cdk::api::time();
+
+        for mut op in MintOpStorage::list_open() {
+            // skip the mints in-flight and the operations waiting for an administrator
+            if !op.step.is_retryable() || 
+                op.num_attempts >= MAX_ATTEMPTS || 
+                op.updated_at + MINT_OPS_RETRY_INTERVAL * 1_000_000_000 > now {
+                continue;
+            }
+
+            let _ = Self::run_step(&mut op).await;
+        }
+    }
+
+    pub async fn resolve(
+        id: MintOpId,
+        resolution: MintOpResolution,
+        resolved_by: Principal
+    ) -> Result<(), String> {
+        let Some(mut op) = MintOpStorage::load(id) else {
+            return Err("Mint operation not found".to_string());
+        };
+
+        if op.step.is_final() {
+            return Err("Mint operation already closed".to_string());
+        }
+
+        match resolution {
+            MintOpResolution::Retry => {
+                if !op.step.is_retryable() {
+                    return Err("Only refunds and fee transfers can be retried".to_string());
+                }
+
+                op.num_attempts = 0;
+                Self::run_step(&mut op).await
+            },
+            MintOpResolution::Refund => {
+                match op.step {
+                    // without the debit block, it's unknown if the user was debited
+                    MintOpStep::Debiting if op.debit_block.is_none() => {
+                        Err("The debit was not confirmed. Check the ledger and close the operation".to_string())
+                    },
+                    MintOpStep::Debiting | MintOpStep::Minting => {
+                        Self::advance(&mut op, MintOpStep::Refunding);
+                        Self::run_step(&mut op).await
+                    },
+                    _ => {
+                        Err("Only mints stuck while debiting or minting can be refunded".to_string())
+                    }
+                }
+            },
+            MintOpResolution::Close(note) => {
+                Self::advance(&mut op, MintOpStep::Resolved { 
+                    by: resolved_by, 
+                    note 
+                });
+                Ok(())
+            },
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1 @@
+pub mod mint_op;
\ No newline at end of file
@@ -1,4 +1,12 @@
 pub mod meme;
 pub mod nft;
 pub mod wallet;
-pub mod fund;
\ No newline at end of file
+pub mod fund;
+pub mod cert;
+pub mod chat;
+pub mod scheduler;
+pub mod rate_limit;
+pub mod moderation;
+pub mod mint_op;
+pub mod token;
+pub mod deposit;
\ No newline at end of file
@@ -0,0 +1 @@
+pub mod moderation;
\ No newline at end of file
@@ -0,0 +1,241 @@
+use candid::Principal;
+use ic_llm::Model;
+use crate::{
+    services::cert,
+    state,
+    storage::{
+        blob::BlobStorage,
+        moderation::ModerationStorage,
+        nft::NftStorage,
+        report::ReportStorage
+    },
+    types::{
+        blob::BlobId,
+        chat::{ChatKey, NsfwStrictness},
+        moderation::{find_denied, normalize, ModerationStage, Refusal},
+        report::{
+            AuditEntry, Report, ReportError, ReportStatus, ReportTarget,
+            Reporter, TakedownAction, MAX_REPORTERS, MAX_REPORT_REASON_LEN
+        }
+    }
+};
+
+const MODERATION_PROMPT: &str = "You're a content moderator. Classify these meme captions, that will be posted publicly: {texts}. Reply \"UNSAFE: <short reason>\" if they contain sexual content, hate speech, harassment, threats, or incitement to violence or self-harm. Otherwise reply \"SAFE\". Reply with nothing else";
+
+pub struct ModerationService;
+
+impl ModerationService {
+    /// Checks the texts before they are rendered into an image or minted, logging any refusal for review.
+    /// Returns the reason of the refusal
+    pub async fn check(
+        texts: &[String],
+        strictness: NsfwStrictness,
+        stage: ModerationStage,
+        user: Option<Principal>,
+        chat: Option<ChatKey>
+    ) -> Result<(), String> {
+        if strictness == NsfwStrictness::Off || texts.is_empty() {
+            return Ok(());
+        }
+
+        let strict = strictness == NsfwStrictness::Strict;
+        let words = normalize(&texts.join(" "));
+
+        let (denied, llm_check) = state::read(|s| {
+            let config = s.moderation();
+            (
+                find_denied(&words, &config.denylist, strict).cloned(),
+                config.llm_check
+            )
+        });
+
+        let reason = if let Some(term) = denied {
+            Some(format!("Denied term: {}", term))
+        }
+        else if strict && llm_check {
+            Self::classify(texts).await
+        }
+        else {
+            None
+        };
+
+        let Some(reason) = reason else {
+            return Ok(());
+        };
+
+        ModerationStorage::log_refusal(Refusal {
+            id: 0,
+            stage,
+            user,
+            chat,
+            texts: texts.to_vec(),
+            reason: reason.clone(),
+            created_at: ic_cdk::api::time(),
+        });
+        state::mutate(|s| s.metrics_mut().record_refusal());
+
+        Err(reason)
+    }
+
+    /// Asks the LLM to classify the texts. Returns the reason if they are unsafe.
+    /// An unexpected reply lets the texts through, as the denylist was already checked
+    async fn classify(
+        texts: &[String]
+    ) -> Option<String> {
+        let prompt = MODERATION_PROMPT
+            .replace("{texts}", &serde_json::to_string(texts).unwrap());
+
+        let started_at = ic_cdk::api::time();
+        let res = ic_llm::prompt(Model::Llama3_1_8B, prompt)
+            .await;
+        let latency = (ic_cdk::api::time() - started_at) / 1_000_000;
+
+        let verdict = res.trim().to_string();
+        let unsafe_reason = verdict.strip_prefix("UNSAFE")
+            .map(|reason| reason.trim_start_matches(':').trim().to_string());
+        let valid = unsafe_reason.is_some() || verdict.starts_with("SAFE");
+
+        state::mutate(|s| s.metrics_mut().record_llm_call(latency, !valid));
+
+        unsafe_reason.map(|reason| format!("Flagged by the LLM: {}", reason))
+    }
+
+    /// Adds a report of a posted meme or of a minted NFT to the review queue.
+    /// The reports of the same image are grouped
+    pub fn report(
+        target: ReportTarget,
+        user: Principal,
+        chat: ChatKey,
+        reason: Option<String>
+    ) -> Result<(), ReportError> {
+        let (blob_id, owner) = match &target {
+            ReportTarget::Meme(meme_id) => {
+                let post = ReportStorage::load_post(meme_id)
+                    .ok_or(ReportError::NotFound)?;
+                (post.blob_id, Some(post.user))
+            },
+            ReportTarget::Nft(token_id) => {
+                let nft = NftStorage::load(token_id)
+                    .ok_or(ReportError::NotFound)?;
+                (nft.blob_id, None)
+            },
+        };
+
+        let now = ic_cdk::api::time();
+        let reporter = Reporter {
+            user,
+            chat,
+            reason: reason.map(|reason| reason.chars().take(MAX_REPORT_REASON_LEN).collect()),
+            reported_at: now,
+        };
+
+        let report = match ReportStorage::load(blob_id) {
+            Some(mut report) => {
+                if report.status == ReportStatus::TakenDown {
+                    return Err(ReportError::AlreadyTakenDown);
+                }
+                // only the first reporters are kept, so the others could report again
+                if report.reporters.iter().any(|r| r.user == user) {
+                    return Err(ReportError::AlreadyReported);
+                }
+
+                // a dismissed report is reopened by new reporters
+                report.status = ReportStatus::Open;
+                report.num_reports += 1;
+                if report.reporters.len() < MAX_REPORTERS {
+                    report.reporters.push(reporter);
+                }
+                report.updated_at = now;
+                report
+            },
+            None => {
+                Report {
+                    blob_id,
+                    target,
+                    owner,
+                    reporters: vec![reporter],
+                    num_reports: 1,
+                    status: ReportStatus::Open,
+                    created_at: now,
+                    updated_at: now,
+                }
+            }
+        };
+
+        ReportStorage::save(report);
+
+        Ok(())
+    }
+
+    /// Applies a takedown action of the administrator, recording it in the audit log
+    pub fn takedown(
+        action: TakedownAction,
+        actor: Principal
+    ) -> Result<(), String> {
+        let now = ic_cdk::api::time();
+
+        match &action {
+            TakedownAction::HideBlob(blob_id) => {
+                state::mutate(|s| s.takedowns_mut().hidden_blobs.insert(*blob_id));
+                cert::mutate(|c| c.hide_blob(*blob_id));
+                Self::resolve_report(*blob_id, ReportStatus::TakenDown, now);
+            },
+            TakedownAction::UnhideBlob(blob_id) => {
+                if !state::mutate(|s| s.takedowns_mut().hidden_blobs.remove(blob_id)) {
+                    return Err("Blob is not hidden".to_string());
+                }
+                match BlobStorage::load_meta(*blob_id) {
+                    Some(meta) => {
+                        cert::mutate(|c| c.certify_blob(*blob_id, &meta));
+                    },
+                    None => {
+                        cert::mutate(|c| c.uncertify_blob(*blob_id));
+                    }
+                }
+            },
+            TakedownAction::BanMinter(user) => {
+                state::mutate(|s| s.takedowns_mut().mint_bans.insert(*user));
+            },
+            TakedownAction::UnbanMinter(user) => {
+                if !state::mutate(|s| s.takedowns_mut().mint_bans.remove(user)) {
+                    return Err("User is not banned".to_string());
+                }
+            },
+            TakedownAction::DismissReport(blob_id) => {
+                if !Self::resolve_report(*blob_id, ReportStatus::Dismissed, now) {
+                    return Err("Unknown report".to_string());
+                }
+            },
+        }
+
+        ReportStorage::log_audit(AuditEntry {
+            id: 0,
+            actor,
+            action,
+            created_at: now,
+        });
+
+        Ok(())
+    }
+
+    pub fn is_mint_banned(
+        user: &Principal
+    ) -> bool {
+        state::read(|s| s.takedowns().mint_bans.contains(user))
+    }
+
+    fn resolve_report(
+        blob_id: BlobId,
+        status: ReportStatus,
+        now: u64
+    ) -> bool {
+        let Some(mut report) = ReportStorage::load(blob_id) else {
+            return false;
+        };
+
+        report.status = status;
+        report.updated_at = now;
+        ReportStorage::save(report);
+        true
+    }
+}
\ No newline at end of file
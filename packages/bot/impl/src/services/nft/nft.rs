@@ -1,3 +1,4 @@
+use std::collections::{BTreeMap, BTreeSet};
 use candid::Principal;
 use icrc7_types::{
     icrc37_types::{TransferFromArg, TransferFromResult}, 
@@ -10,20 +11,24 @@ use icrc_ledger_types::{
 use image::{Rgba, RgbaImage};
 use tiny_skia::Color;
 use crate::{
+    state, 
     storage::{
         blob, 
         event::EventStorage, 
         nft::NftStorage, 
+        nft_config::NftConfigStorage, 
         user::UserStorage
     }, 
     types::{
-        asset::Asset, 
         blob::BlobId, 
         event::Event, 
         image::IMAGE_FORMAT, 
         meme::MemeId, 
         nft::{Nft, NftId}, 
-        nft_col::{NftCollection, NftCollectionConfig}, 
+        nft_col::{
+            MintPhase, MintPhaseKind, MintingPause, MintingPreview, NftCollection, 
+            NftCollectionConfig, NftConfigChange, NftConfigChangeKind
+        }, 
         user::UserTransaction
     }, 
     utils::{
@@ -41,10 +46,57 @@ const PADDING: usize = 8;
 const PREVIEW_IMG_WIDTH: usize = 256;
 const PREVIEW_IMG_HEIGHT: usize = 256;
 
+#[derive(Debug, PartialEq)]
+pub enum MintError {
+    SoldOut,
+    // no phase is open, with the start of the next one, if any
+    Closed(Option<u64>),
+    NotAllowlisted(String),
+    UserCapReached(u32),
+    UserInFlight,
+    MemeInFlight,
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MintLockKey {
+    User(Principal),
+    Meme(MemeId),
+}
+
+/// Held while a mint is in-flight, so the same user or meme can't be minted concurrently. 
+/// Released when dropped, on every exit path
+pub struct MintLock {
+    key: MintLockKey,
+}
+
+impl Drop for MintLock {
+    fn drop(
+        &mut self
+    ) {
+        super::mutate(|s| s.in_flight.remove(&self.key));
+    }
+}
+
+/// A token of the supply held by an in-flight mint, given back when dropped
+pub struct MintReservation {
+    user_id: Principal,
+}
+
+impl Drop for MintReservation {
+    fn drop(
+        &mut self
+    ) {
+        super::mutate(|s| s.release(&self.user_id));
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct NftService {
     pub config: NftCollectionConfig,
     pub col: NftCollection,
+    // in-flight mints of each user, counted against the max supply
+    reserved: BTreeMap<Principal, u32>,
+    in_flight: BTreeSet<MintLockKey>,
 }
 
 impl NftService {
@@ -57,28 +109,258 @@ impl NftService {
         self.col = col;
     }
 
+    /// Replaces the minting config at runtime, persisting and logging the change
+    pub fn change_config(
+        config: NftCollectionConfig,
+        changed_by: Principal
+    ) -> Result<(), String> {
+        config.validate()?;
+
+        if super::read(|s| s.config == config) {
+            return Ok(());
+        }
+
+        super::mutate(|s| s.config = config.clone());
+        state::mutate(|s| s.set_nft_config(config.clone()));
+        Self::log_change(NftConfigChangeKind::Config(config), changed_by);
+
+        Ok(())
+    }
+
+    /// Pauses the minting, with a reason shown to the users, or resumes it if there's no reason
+    pub fn pause_minting(
+        reason: Option<String>,
+        changed_by: Principal
+    ) -> Result<(), String> {
+        let now = ic_cdk::api::time();
+
+        match reason {
+            Some(reason) => {
+                if reason.trim().is_empty() {
+                    return Err("A reason is required".to_string());
+                }
+
+                state::mutate(|s| s.set_minting_pause(Some(MintingPause {
+                    reason: reason.clone(),
+                    paused_by: changed_by,
+                    paused_at: now,
+                })));
+                Self::log_change(NftConfigChangeKind::Paused(reason), changed_by);
+            },
+            None => {
+                if state::read(|s| s.minting_pause().is_none()) {
+                    return Err("Minting is not paused".to_string());
+                }
+
+                state::mutate(|s| s.set_minting_pause(None));
+                Self::log_change(NftConfigChangeKind::Resumed, changed_by);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the minting phases, sorting them by start
+    pub fn change_phases(
+        mut phases: Vec<MintPhase>,
+        changed_by: Principal
+    ) -> Result<(), String> {
+        phases.sort_by_key(|phase| phase.starts_at);
+        MintPhase::validate_all(&phases)?;
+
+        state::mutate(|s| s.set_mint_phases(phases.clone()));
+        Self::log_change(NftConfigChangeKind::Phases(phases), changed_by);
+
+        Ok(())
+    }
+
+    pub fn update_allowlist(
+        to_add: Vec<Principal>,
+        to_remove: Vec<Principal>,
+        changed_by: Principal
+    ) {
+        let (num_added, num_removed) = state::mutate(|s| {
+            let allowlist = s.mint_allowlist_mut();
+            (
+                to_add.into_iter().filter(|p| allowlist.insert(*p)).count(),
+                to_remove.iter().filter(|p| allowlist.remove(p)).count()
+            )
+        });
+
+        if num_added > 0 || num_removed > 0 {
+            Self::log_change(NftConfigChangeKind::Allowlist { 
+                num_added: num_added as u32, 
+                num_removed: num_removed as u32 
+            }, changed_by);
+        }
+    }
+
+    /// Returns the phase open at the timestamp. Without phases, minting is always open
+    pub fn current_phase(
+        timestamp: u64
+    ) -> Result<Option<MintPhase>, MintError> {
+        state::read(|s| {
+            let phases = s.mint_phases();
+            if phases.is_empty() {
+                return Ok(None);
+            }
+
+            phases.iter()
+                .find(|phase| phase.is_open_at(timestamp))
+                .map(|phase| Some(phase.clone()))
+                .ok_or_else(|| MintError::Closed(
+                    phases.iter()
+                        .map(|phase| phase.starts_at)
+                        .find(|starts_at| *starts_at > timestamp)
+                ))
+        })
+    }
+
+    /// Reserves a token of the supply, and of the user's cap in the phase, before the async 
+    /// calls of the mint, so concurrent mints can't go over them
+    pub fn reserve(
+        user_id: Principal,
+        phase: Option<&MintPhase>,
+        num_minted_in_phase: u32
+    ) -> Result<MintReservation, MintError> {
+        if let Some(phase) = phase {
+            if phase.kind == MintPhaseKind::Allowlist && 
+                !state::read(|s| s.mint_allowlist().contains(&user_id)) {
+                return Err(MintError::NotAllowlisted(phase.name.clone()));
+            }
+        }
+
+        super::mutate(|s| {
+            if s.remaining_supply() == 0 {
+                return Err(MintError::SoldOut);
+            }
+
+            if let Some(max_mints) = phase.and_then(|phase| phase.max_mints_per_user) {
+                let num_reserved = s.reserved.get(&user_id).cloned().unwrap_or(0);
+                if num_minted_in_phase + num_reserved >= max_mints {
+                    return Err(MintError::UserCapReached(max_mints));
+                }
+            }
+
+            *s.reserved.entry(user_id).or_default() += 1;
+
+            Ok(MintReservation { 
+                user_id 
+            })
+        })
+    }
+
+    pub fn lock(
+        key: MintLockKey
+    ) -> Result<MintLock, MintError> {
+        super::mutate(|s| {
+            if !s.in_flight.insert(key.clone()) {
+                return Err(match key {
+                    MintLockKey::User(_) => MintError::UserInFlight,
+                    MintLockKey::Meme(_) => MintError::MemeInFlight,
+                });
+            }
+
+            Ok(MintLock { 
+                key 
+            })
+        })
+    }
+
+    fn release(
+        &mut self,
+        user_id: &Principal
+    ) {
+        if let Some(num_reserved) = self.reserved.get_mut(user_id) {
+            *num_reserved -= 1;
+            if *num_reserved == 0 {
+                self.reserved.remove(user_id);
+            }
+        }
+    }
+
+    /// Tokens left to mint, not counting the ones reserved by in-flight mints
+    pub fn remaining_supply(
+        &self
+    ) -> u32 {
+        let num_reserved = self.reserved.values().sum::<u32>() as u64;
+        (self.col.max_supply as u64)
+            .saturating_sub(NftStorage::size() + num_reserved) as u32
+    }
+
+    fn log_change(
+        kind: NftConfigChangeKind,
+        changed_by: Principal
+    ) {
+        NftConfigStorage::log_change(NftConfigChange {
+            id: 0,
+            kind,
+            changed_by,
+            changed_at: ic_cdk::api::time(),
+        });
+    }
+
     pub fn calc_minting_cost(
         &self
     ) -> u64 {
-        // y = min + floor(tanh(minted) * (max - min))
-        let total_supply = NftStorage::size() as u32;
-        self.config.min_minting_cost + (
-            (total_supply as f32 / self.col.max_supply as f32).tanh() * 
-            (self.config.max_minting_cost - self.config.min_minting_cost) as f32
-        ).floor() as u64
+        self.calc_minting_cost_at(NftStorage::size() as u32, ic_cdk::api::time())
     }
 
     pub fn calc_min_reactions(
         &self
     ) -> u32 {
-        // y = min + floor(tanh(minted) * (max - min))
-        let total_supply = NftStorage::size() as u32;
+        self.calc_min_reactions_at(NftStorage::size() as u32, ic_cdk::api::time())
+    }
+
+    /// y = min + floor(curve(supply) * (max - min))
+    pub fn calc_minting_cost_at(
+        &self,
+        supply: u32,
+        timestamp: u64
+    ) -> u64 {
+        let y = self.config.cost_curve.clone().unwrap_or_default()
+            .eval(supply, self.col.max_supply, timestamp, NftStorage::last_minted_at());
+        self.config.min_minting_cost + (
+            y * (self.config.max_minting_cost - self.config.min_minting_cost) as f64
+        ).floor() as u64
+    }
+
+    pub fn calc_min_reactions_at(
+        &self,
+        supply: u32,
+        timestamp: u64
+    ) -> u32 {
+        let y = self.config.reactions_curve.clone().unwrap_or_default()
+            .eval(supply, self.col.max_supply, timestamp, NftStorage::last_minted_at());
         self.config.min_num_reactions + (
-            (total_supply as f32 / self.col.max_supply as f32).tanh() * 
-            (self.config.max_num_reactions - self.config.min_num_reactions) as f32
+            y * (self.config.max_num_reactions - self.config.min_num_reactions) as f64
         ).floor() as u32
     }
 
+    /// Previews the minting cost and the min number of reactions at each supply level,
+    /// optionally with another config
+    pub fn preview(
+        &self,
+        config: Option<NftCollectionConfig>,
+        supplies: &[u32],
+        timestamp: u64
+    ) -> Result<Vec<MintingPreview>, String> {
+        let mut service = self.clone();
+        if let Some(config) = config {
+            config.validate()?;
+            service.config = config;
+        }
+
+        Ok(supplies.iter()
+            .map(|supply| MintingPreview {
+                supply: *supply,
+                minting_cost: service.calc_minting_cost_at(*supply, timestamp),
+                min_num_reactions: service.calc_min_reactions_at(*supply, timestamp),
+            })
+            .collect()
+        )
+    }
+
     pub fn min_chat_members(
         &self
     ) -> u32 {
@@ -97,18 +379,6 @@ impl NftService {
         self.config.min_user_creation_interval / (24 * 60 * 60 * 1_000)
     }
 
-    pub fn get_token_image(
-        &self,
-        token_id: BlobId
-    ) -> Option<Asset> {
-        blob::BlobStorage::load(token_id)
-            .map(|b| Asset{
-                data: b.data, 
-                mime_type: b.mime_type 
-            }
-        )
-    }
-
     pub async fn balance_of(
         &self,
         owner: Account
@@ -0,0 +1 @@
+pub mod rate_limit;
\ No newline at end of file
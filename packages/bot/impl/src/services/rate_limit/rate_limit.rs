@@ -0,0 +1,117 @@
+use std::{cell::RefCell, collections::HashMap};
+use candid::Principal;
+use crate::{
+    state,
+    types::{
+        chat::ChatKey,
+        rate_limit::{
+            RateLimitClass, RateLimitSubject, TokenBucket, 
+            MAX_RATE_LIMIT_BUCKETS, TRANSFORM_BUCKET
+        }
+    }
+};
+
+thread_local! {
+    // the buckets live on the heap only: an upgrade just refills them
+    static BUCKETS: RefCell<HashMap<(RateLimitSubject, RateLimitClass), TokenBucket>> = RefCell::default();
+    static TRANSFORMS: RefCell<Option<TokenBucket>> = RefCell::default();
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RateLimitError {
+    User(u64),
+    Chat(u64),
+}
+
+pub struct RateLimitService;
+
+impl RateLimitService {
+    /// Takes a token from both the user and the chat buckets, or none of them if any is empty.
+    /// On failure, returns the number of seconds to wait
+    pub fn check(
+        user_id: Principal,
+        chat: &ChatKey,
+        class: RateLimitClass
+    ) -> Result<(), RateLimitError> {
+        let (config, user_exempt, chat_exempt) = state::read(|s| {
+            let limits = s.rate_limits();
+            (
+                limits.config.clone(),
+                limits.exempt_users.contains(&user_id),
+                limits.exempt_chats.contains(chat)
+            )
+        });
+
+        let mut subjects = vec![];
+        if !user_exempt {
+            subjects.push(RateLimitSubject::User(user_id));
+        }
+        if !chat_exempt {
+            subjects.push(RateLimitSubject::Chat(chat.clone()));
+        }
+
+        let now = ic_cdk::api::time();
+
+        BUCKETS.with_borrow_mut(|buckets| {
+            if buckets.len() >= MAX_RATE_LIMIT_BUCKETS {
+                Self::prune(buckets, now);
+            }
+
+            for subject in &subjects {
+                let bucket_config = config.bucket(subject, class);
+                let bucket = buckets.entry((subject.clone(), class))
+                    .or_insert_with(|| TokenBucket::new(bucket_config, now));
+                bucket.refill(bucket_config, now);
+
+                let wait = bucket.wait_secs(bucket_config);
+                if wait > 0 {
+                    return Err(match subject {
+                        RateLimitSubject::User(_) => RateLimitError::User(wait),
+                        RateLimitSubject::Chat(_) => RateLimitError::Chat(wait),
+                    });
+                }
+            }
+
+            for subject in subjects {
+                if let Some(bucket) = buckets.get_mut(&(subject, class)) {
+                    bucket.take();
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Takes a token from the bucket shared by all the image transforms.
+    /// On failure, returns the number of seconds to wait
+    pub fn check_transform(
+    ) -> Result<(), u64> {
+        let now = ic_cdk::api::time();
+
+        TRANSFORMS.with_borrow_mut(|bucket| {
+            let bucket = bucket.get_or_insert_with(|| TokenBucket::new(&TRANSFORM_BUCKET, now));
+            bucket.refill(&TRANSFORM_BUCKET, now);
+
+            let wait = bucket.wait_secs(&TRANSFORM_BUCKET);
+            if wait > 0 {
+                return Err(wait);
+            }
+
+            bucket.take();
+            Ok(())
+        })
+    }
+
+    /// Drops the buckets that are full, as they are equivalent to new ones
+    fn prune(
+        buckets: &mut HashMap<(RateLimitSubject, RateLimitClass), TokenBucket>,
+        now: u64
+    ) {
+        let config = state::read(|s| s.rate_limits().config.clone());
+        buckets.retain(|(subject, class), bucket| {
+            let bucket_config = config.bucket(subject, *class);
+            bucket.refill(bucket_config, now);
+            !bucket.is_full(bucket_config)
+        });
+    }
+}
\ No newline at end of file
@@ -0,0 +1 @@
+pub mod scheduler;
\ No newline at end of file
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use candid::Principal;
+use crate::{
+    services::{
+        chat::chat::ChatService, 
+        meme::{self, MemeService}, 
+        moderation::moderation::ModerationService
+    }, 
+    state, 
+    storage::{chat::ChatStorage, event::EventStorage, schedule::ScheduleStorage}, 
+    types::{
+        blob::Blob, 
+        event::Event, 
+        image::IMAGE_FORMAT, 
+        moderation::ModerationStage, 
+        schedule::{Job, JobKind, WEEK}
+    }
+};
+
+const LEADERBOARD_SIZE: usize = 10;
+
+/// Runs the recurring jobs the chats opted into
+pub struct SchedulerService;
+
+impl SchedulerService {
+    pub async fn run_due_jobs(
+    ) {
+        let now = ic_cdk::api::time();
+
+        for (id, mut job) in ScheduleStorage::list_due(now) {
+            // reschedule before running, so a slow job isn't picked again by the next tick
+            let mut next_run_at = job.next_run_at + job.interval;
+            while next_run_at <= now {
+                next_run_at += job.interval;
+            }
+            job.next_run_at = next_run_at;
+            job.last_run_at = Some(now);
+            ScheduleStorage::save(id, job.clone());
+
+            let res = Self::run(&job).await;
+            if let Err(err) = &res {
+                ic_cdk::println!("error: running job {}: {}", id, err);
+            }
+
+            // the job could have been cancelled while running
+            if let Some(mut job) = ScheduleStorage::load(id) {
+                job.last_error = res.err();
+                ScheduleStorage::save(id, job);
+            }
+        }
+    }
+
+    async fn run(
+        job: &Job
+    ) -> Result<(), String> {
+        match &job.kind {
+            JobKind::MemeOfTheDay { mood, topic } => {
+                let tpl = meme::read(|s| {
+                    state::read(|st| st.meme_tpl_usage().trending())
+                        .and_then(|id| s.load(&id))
+                        .or_else(|| s.random())
+                        .cloned()
+                }).ok_or_else(|| "No meme templates found".to_string())?;
+
+                let settings = ChatStorage::load_settings(&job.chat);
+
+                let captions = MemeService::gen_captions(
+                    &tpl, 
+                    mood.clone(), 
+                    topic.clone(), 
+                    settings.language
+                ).await?;
+
+                ModerationService::check(
+                    &captions, 
+                    settings.nsfw, 
+                    ModerationStage::Render, 
+                    None, 
+                    Some(job.chat.clone())
+                ).await?;
+
+                let (jpeg, width, height) = MemeService::gen_jpeg(&tpl, &captions)?;
+
+                ChatService::post_image(
+                    &job.chat, 
+                    Blob {
+                        mime_type: IMAGE_FORMAT.to_mime_type().to_string(),
+                        data: jpeg,
+                    }, 
+                    width, 
+                    height, 
+                    Some("**Meme of the day** 🏆".to_string())
+                ).await
+            },
+            JobKind::WeeklyLeaderboard => {
+                ChatService::post_text(
+                    &job.chat, 
+                    Self::leaderboard(ic_cdk::api::time().saturating_sub(WEEK))
+                ).await
+            },
+            JobKind::Reminder { text } => {
+                ChatService::post_text(&job.chat, text.clone()).await
+            },
+        }
+    }
+
+    /// Top minters of MEME NFTs since the given time
+    fn leaderboard(
+        since: u64
+    ) -> String {
+        let since = (since / 1_000_000_000) as u32;
+        let mut mints: HashMap<Principal, u32> = HashMap::new();
+
+        EventStorage::for_each_rev(|event| {
+            match event {
+                Event::NftMinted { to, timestamp, .. } => {
+                    if *timestamp < since {
+                        return false;
+                    }
+                    *mints.entry(to.owner).or_default() += 1;
+                },
+                _ => {
+                }
+            }
+            true
+        });
+
+        if mints.is_empty() {
+            return "No MEME NFTs were minted this week. Be the first one with '/meme nft mint'! 😎".to_string();
+        }
+
+        let mut mints = mints.into_iter().collect::<Vec<_>>();
+        mints.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        let mut text = "**MEME NFT leaderboard of the week** 🏆  \n".to_string();
+        for (pos, (owner, count)) in mints.iter().take(LEADERBOARD_SIZE).enumerate() {
+            text.push_str(&format!(
+                "{}. @UserId({}): **{}** NFT{}  \n", 
+                pos + 1, 
+                owner.to_text(), 
+                count, 
+                if *count > 1 { "s" } else { "" }
+            ));
+        }
+
+        text
+    }
+}
\ No newline at end of file
@@ -0,0 +1,38 @@
+use std::{cell::RefCell, collections::BTreeMap};
+use ic_ledger_types::MAINNET_LEDGER_CANISTER_ID;
+use crate::{
+    state, 
+    types::token::{Token, TokenKind}, 
+    utils::icrc1::ICRC1
+};
+
+thread_local! {
+    // loaded on first use, so the ledgers aren't called during the upgrades
+    static TOKENS: RefCell<BTreeMap<TokenKind, Token>> = RefCell::new(BTreeMap::new());
+}
+
+pub struct TokenService;
+
+impl TokenService {
+    /// Returns the token, with the symbol, decimals and fee taken from its ledger
+    pub async fn get(
+        kind: TokenKind
+    ) -> Result<Token, String> {
+        if let Some(token) = TOKENS.with_borrow(|tokens| tokens.get(&kind).cloned()) {
+            return Ok(token);
+        }
+
+        let canister_id = match kind {
+            TokenKind::Icp => MAINNET_LEDGER_CANISTER_ID,
+            TokenKind::Meme => state::read(|s| s.meme_coin().canister_id),
+        };
+
+        let metadata = ICRC1::metadata(&canister_id).await?;
+        let token = Token::from_metadata(kind, canister_id, metadata)?;
+        TOKENS.with_borrow_mut(|tokens| {
+            tokens.insert(kind, token.clone())
+        });
+
+        Ok(token)
+    }
+}
\ No newline at end of file
@@ -1,11 +1,40 @@
-use candid::Principal;
+use std::str::FromStr;
+use candid::{Nat, Principal};
 use ic_ledger_types::{
     account_balance, AccountBalanceArgs, AccountIdentifier, 
-    Memo, Subaccount, Timestamp, Tokens, TransferArgs, 
-    DEFAULT_FEE, DEFAULT_SUBACCOUNT, MAINNET_LEDGER_CANISTER_ID
+    Memo, Subaccount, Timestamp, Tokens, TransferArgs, TransferError, 
+    DEFAULT_SUBACCOUNT, MAINNET_LEDGER_CANISTER_ID
 };
 
-use crate::{storage::user::UserStorage, types::user::UserTransaction};
+use icrc_ledger_types::{
+    icrc1::{account::{principal_to_subaccount, Account}, transfer::TransferArg}, 
+    icrc2::{allowance::AllowanceArgs, transfer_from::TransferFromArgs}
+};
+use crate::{
+    storage::{deposit::DepositStorage, user::UserStorage}, 
+    types::{
+        token::{Token, TokenKind}, 
+        user::UserTransaction
+    }, 
+    utils::{icrc1::ICRC1, icrc2::ICRC2, nat::nat_to_u128, oc::lookup_username}
+};
+
+#[derive(Debug)]
+pub enum WalletError {
+    InvalidDestination(String),
+    UnknownUsername(String),
+    // withdrawing everything, but the balance doesn't cover the fee
+    InsufficientBalance(u128),
+    // doesn't fit in the ICP ledger's amounts
+    InvalidAmount(u128),
+    Transfer(String),
+}
+
+/// Destination of a withdraw: ICP accepts both kinds, the ICRC-1 tokens only accounts
+enum Destination {
+    AccountId(AccountIdentifier),
+    Account(Account),
+}
 
 pub struct WalletService;
 
@@ -19,9 +48,13 @@ impl WalletService {
         )
     }
     
+    /// Address of the user's deposit account. Once shown, the account is indexed, so the 
+    /// ledger scan can find its deposits
     pub fn address_of(
         user_id: Principal
     ) -> String {
+        DepositStorage::register(user_id);
+
         let acc_id = AccountIdentifier::new(
             &ic_cdk::id(), 
             &Subaccount::from(user_id)
@@ -44,28 +77,216 @@ impl WalletService {
         Ok(icp.e8s())
     }
 
-    pub async fn transfer_hex(
-        user_id: Principal, 
-        to: Option<String>, 
-        amount: u64
-    ) -> Result<(u64, String), String> {
-        let to = if let Some(to) = to {
-            AccountIdentifier::from_hex(&to).unwrap()
+    /// Account of the user in an ICRC-1 ledger, a subaccount of the bot
+    fn icrc1_account(
+        user_id: Principal
+    ) -> Account {
+        Account {
+            owner: ic_cdk::id(),
+            subaccount: Some(principal_to_subaccount(user_id)),
+        }
+    }
+
+    pub fn token_address_of(
+        token: &Token,
+        user_id: Principal
+    ) -> String {
+        match token.kind {
+            TokenKind::Icp => Self::address_of(user_id),
+            TokenKind::Meme => Self::icrc1_account(user_id).to_string(),
+        }
+    }
+
+    pub async fn token_balance_of(
+        token: &Token,
+        user_id: Principal
+    ) -> Result<u128, String> {
+        match token.kind {
+            TokenKind::Icp => {
+                Self::balance_of(user_id).await
+                    .map(|balance| balance as u128)
+            },
+            TokenKind::Meme => {
+                ICRC1::balance_of(&token.canister_id, Self::icrc1_account(user_id)).await
+            },
         }
-        else {
-            AccountIdentifier::new(&user_id, &DEFAULT_SUBACCOUNT)
+    }
+
+    /// Parses the destination of a withdraw: an ICP account id in hex format, an ICRC-1 account 
+    /// (principal or principal-checksum.subaccount) or an OC username (default: the user's OC wallet)
+    async fn parse_destination(
+        user_id: Principal,
+        to: Option<String>
+    ) -> Result<Destination, WalletError> {
+        let Some(to) = to.map(|to| to.trim().to_string()) else {
+            return Ok(Destination::Account(Account { owner: user_id, subaccount: None }));
         };
-        
-        Self::transfer(Some(user_id), to, amount).await
+
+        if to.len() == 64 && to.chars().all(|c| c.is_ascii_hexdigit()) {
+            return AccountIdentifier::from_hex(&to)
+                .map(Destination::AccountId)
+                .map_err(|_| WalletError::InvalidDestination(to));
+        }
+
+        if let Ok(account) = Account::from_str(&to) {
+            return Ok(Destination::Account(account));
+        }
+
+        let username = to.trim_start_matches('@');
+        if username.is_empty() || !username.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(WalletError::InvalidDestination(to));
+        }
+
+        lookup_username(username).await
+            .map(|owner| Destination::Account(Account { owner, subaccount: None }))
+            .ok_or(WalletError::UnknownUsername(username.to_string()))
     }
 
-    pub async fn transfer(
+    /// Withdraws an amount, in the token's smallest unit, or the whole balance less the fee, 
+    /// if no amount is given. Returns the amount, the block number and the destination
+    pub async fn withdraw(
+        token: &Token,
+        user_id: Principal,
+        to: Option<String>,
+        amount: Option<u128>
+    ) -> Result<(u128, u128, String), WalletError> {
+        let to = Self::parse_destination(user_id, to).await?;
+
+        let amount = match amount {
+            Some(amount) => amount,
+            None => {
+                let balance = Self::token_balance_of(token, user_id).await
+                    .map_err(WalletError::Transfer)?;
+                if balance <= token.fee {
+                    return Err(WalletError::InsufficientBalance(balance));
+                }
+                balance - token.fee
+            }
+        };
+
+        match token.kind {
+            TokenKind::Icp => {
+                let to = match to {
+                    Destination::AccountId(acc_id) => acc_id,
+                    Destination::Account(account) => AccountIdentifier::new(
+                        &account.owner, 
+                        &account.subaccount.map_or(DEFAULT_SUBACCOUNT, Subaccount)
+                    ),
+                };
+
+                let e8s = u64::try_from(amount)
+                    .map_err(|_| WalletError::InvalidAmount(amount))?;
+
+                let now = ic_cdk::api::time();
+                let (block_num, _) = Self::transfer_at(token, Some(user_id), to, e8s, now).await
+                    .map_err(WalletError::Transfer)?;
+
+                Self::record(user_id, UserTransaction::IcpWithdraw { 
+                    amount: e8s,
+                    to, 
+                    block_num, 
+                    timestamp: (now / 1_000_000_000) as _,
+                });
+
+                Ok((amount, block_num as u128, to.to_hex()))
+            },
+            TokenKind::Meme => {
+                let to = match to {
+                    Destination::Account(account) => account,
+                    Destination::AccountId(acc_id) => 
+                        return Err(WalletError::InvalidDestination(acc_id.to_hex())),
+                };
+
+                let now = ic_cdk::api::time();
+                let block_num = ICRC1::transfer(
+                    &token.canister_id, 
+                    TransferArg {
+                        from_subaccount: Some(principal_to_subaccount(user_id)),
+                        to,
+                        fee: Some(Nat::from(token.fee)),
+                        created_at_time: Some(now),
+                        memo: None,
+                        amount: Nat::from(amount),
+                    }
+                ).await
+                    .map_err(WalletError::Transfer)?;
+
+                Self::record(user_id, UserTransaction::TokenWithdraw { 
+                    token: token.kind,
+                    amount,
+                    to, 
+                    block_num, 
+                    timestamp: (now / 1_000_000_000) as _,
+                });
+
+                Ok((amount, block_num, to.to_string()))
+            },
+        }
+    }
+
+    /// Appends a debit or a credit to the user's history
+    pub fn record(
+        user_id: Principal,
+        tx: UserTransaction
+    ) {
+        let mut user = UserStorage::load(&user_id);
+        user.txs.push(tx);
+        UserStorage::save(user_id, user);
+    }
+
+    /// ICP the user approved the bot to spend from their own wallet (ICRC-2)
+    pub async fn allowance_of(
+        user_id: Principal
+    ) -> Result<u64, String> {
+        let allowance = ICRC2::allowance(
+            &MAINNET_LEDGER_CANISTER_ID, 
+            AllowanceArgs {
+                account: Account { owner: user_id, subaccount: None },
+                spender: Account { owner: ic_cdk::id(), subaccount: None },
+            }
+        ).await?;
+
+        if allowance.expires_at.map_or(false, |expires_at| expires_at <= ic_cdk::api::time()) {
+            return Ok(0);
+        }
+
+        Ok(nat_to_u128(allowance.allowance) as u64)
+    }
+
+    /// Pulls ICP from the user's own wallet to the bot, using the user's approval. 
+    /// Like transfer_at, retries with the same creation time aren't paid twice
+    pub async fn transfer_from_at(
+        token: &Token,
+        user_id: Principal,
+        amount: u64,
+        created_at: u64
+    ) -> Result<u64, String> {
+        ICRC2::transfer_from(
+            &MAINNET_LEDGER_CANISTER_ID, 
+            TransferFromArgs {
+                spender_subaccount: None,
+                from: Account { owner: user_id, subaccount: None },
+                to: Account { owner: ic_cdk::id(), subaccount: None },
+                amount: Nat::from(amount),
+                fee: Some(Nat::from(token.fee)),
+                memo: None,
+                created_at_time: Some(created_at),
+            }
+        ).await
+            .map(|block_num| block_num as u64)
+    }
+
+    /// Transfers with a fixed creation time. The ledger deduplicates transfers with the same 
+    /// args for 24 hours, so a retry won't pay twice
+    pub async fn transfer_at(
+        token: &Token,
         from: Option<Principal>, 
         to: AccountIdentifier, 
-        amount: u64
+        amount: u64,
+        created_at: u64
     ) -> Result<(u64, String), String> {
-        let now = ic_cdk::api::time();
-        let block_num = ic_ledger_types::transfer(
+        let now = created_at;
+        let block_num = match ic_ledger_types::transfer(
             MAINNET_LEDGER_CANISTER_ID, 
             TransferArgs { 
                 from_subaccount: if let Some(from) = from {
@@ -75,25 +296,23 @@ impl WalletService {
                     None
                 },  
                 to, 
-                fee: DEFAULT_FEE.into(), 
+                fee: Tokens::from_e8s(token.fee as u64), 
                 created_at_time: Some(Timestamp{timestamp_nanos: now}), 
                 memo: Memo(0x4E4E), 
                 amount: Tokens::from_e8s(amount),
             }
         ).await
-            .map_err(|e| e.1)?
-            .map_err(|e| e.to_string())?;
-
-        if let Some(from) = from {
-            let mut user = UserStorage::load(&from);
-            user.txs.push(UserTransaction::IcpWithdraw { 
-                amount,
-                to, 
-                block_num, 
-                timestamp: (now / 1_000_000_000) as _,
-            });
-            UserStorage::save(from, user);
-        }
+            .map_err(|e| e.1)? {
+            Ok(block_num) => {
+                block_num
+            },
+            Err(TransferError::TxDuplicate { duplicate_of }) => {
+                return Ok((duplicate_of, to.to_hex()));
+            },
+            Err(err) => {
+                return Err(err.to_string());
+            }
+        };
 
         Ok((
             block_num,
@@ -1,8 +1,13 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, collections::BTreeSet};
 use candid::Principal;
 use oc_bots_sdk_canister::env;
 use serde::{Deserialize, Serialize};
-use crate::types::coin::Coin;
+use crate::types::{
+    blob::{BlobCounters, BlobId, BlobIndexing}, coin::Coin, meme_tpl::MemeTplUsage, metrics::Metrics, 
+    moderation::ModerationConfig, 
+    nft_col::{MintPhase, MintingPause, NftCollectionConfig}, 
+    rate_limit::RateLimits, report::Takedowns
+};
 
 thread_local! {
     static STATE: RefCell<Option<State>> = RefCell::default();
@@ -15,6 +20,34 @@ pub struct State {
     meme_coin: Coin,
     rng_seed: [u8; 32],
     temp_bobs_index: usize,
+    #[serde(default)]
+    legacy_blobs_indexed: bool,
+    #[serde(default)]
+    legacy_blobs_cursor: Option<BlobId>,
+    #[serde(default)]
+    blob_indexing: BlobIndexing,
+    #[serde(default)]
+    metrics: Metrics,
+    #[serde(default)]
+    meme_tpl_usage: MemeTplUsage,
+    #[serde(default)]
+    rate_limits: RateLimits,
+    #[serde(default)]
+    moderation: ModerationConfig,
+    #[serde(default)]
+    takedowns: Takedowns,
+    // changed at runtime by the administrators, so it survives the upgrades
+    #[serde(default)]
+    nft_config: Option<NftCollectionConfig>,
+    #[serde(default)]
+    minting_pause: Option<MintingPause>,
+    #[serde(default)]
+    mint_phases: Vec<MintPhase>,
+    #[serde(default)]
+    mint_allowlist: BTreeSet<Principal>,
+    // next ICP ledger block to be scanned for deposits (None: the scan never ran)
+    #[serde(default)]
+    deposits_next_block: Option<u64>,
 }
 
 const STATE_ALREADY_INITIALIZED: &str = "State has already been initialized";
@@ -69,6 +102,19 @@ impl State {
             administrator,
             meme_coin,
             temp_bobs_index: 0,
+            legacy_blobs_indexed: false,
+            legacy_blobs_cursor: None,
+            blob_indexing: BlobIndexing { done: true, cursor: None, counters: BlobCounters::default() },
+            metrics: Metrics::default(),
+            meme_tpl_usage: MemeTplUsage::default(),
+            rate_limits: RateLimits::default(),
+            moderation: ModerationConfig::default(),
+            takedowns: Takedowns::default(),
+            nft_config: None,
+            minting_pause: None,
+            mint_phases: Vec::new(),
+            mint_allowlist: BTreeSet::new(),
+            deposits_next_block: None,
             rng_seed: env::entropy(),
         }
     }
@@ -105,6 +151,12 @@ impl State {
         self.administrator
     }
 
+    pub fn meme_coin(
+        &self
+    ) -> &Coin {
+        &self.meme_coin
+    }
+
     pub fn set_administrator(
         &mut self, 
         administrator: Principal
@@ -117,4 +169,165 @@ impl State {
     ) -> &mut usize {
         &mut self.temp_bobs_index
     }
+
+    pub fn legacy_blobs_indexed(
+        &self
+    ) -> bool {
+        self.legacy_blobs_indexed
+    }
+
+    pub fn set_legacy_blobs_indexed(
+        &mut self,
+        indexed: bool
+    ) {
+        self.legacy_blobs_indexed = indexed;
+    }
+
+    pub fn legacy_blobs_cursor(
+        &self
+    ) -> Option<BlobId> {
+        self.legacy_blobs_cursor
+    }
+
+    pub fn set_legacy_blobs_cursor(
+        &mut self,
+        cursor: Option<BlobId>
+    ) {
+        self.legacy_blobs_cursor = cursor;
+    }
+
+    pub fn blob_indexing(
+        &self
+    ) -> &BlobIndexing {
+        &self.blob_indexing
+    }
+
+    pub fn blob_indexing_mut(
+        &mut self
+    ) -> &mut BlobIndexing {
+        &mut self.blob_indexing
+    }
+
+    pub fn metrics(
+        &self
+    ) -> &Metrics {
+        &self.metrics
+    }
+
+    pub fn metrics_mut(
+        &mut self
+    ) -> &mut Metrics {
+        &mut self.metrics
+    }
+
+    pub fn meme_tpl_usage(
+        &self
+    ) -> &MemeTplUsage {
+        &self.meme_tpl_usage
+    }
+
+    pub fn meme_tpl_usage_mut(
+        &mut self
+    ) -> &mut MemeTplUsage {
+        &mut self.meme_tpl_usage
+    }
+
+    pub fn rate_limits(
+        &self
+    ) -> &RateLimits {
+        &self.rate_limits
+    }
+
+    pub fn rate_limits_mut(
+        &mut self
+    ) -> &mut RateLimits {
+        &mut self.rate_limits
+    }
+
+    pub fn moderation(
+        &self
+    ) -> &ModerationConfig {
+        &self.moderation
+    }
+
+    pub fn moderation_mut(
+        &mut self
+    ) -> &mut ModerationConfig {
+        &mut self.moderation
+    }
+
+    pub fn takedowns(
+        &self
+    ) -> &Takedowns {
+        &self.takedowns
+    }
+
+    pub fn takedowns_mut(
+        &mut self
+    ) -> &mut Takedowns {
+        &mut self.takedowns
+    }
+
+    pub fn nft_config(
+        &self
+    ) -> Option<&NftCollectionConfig> {
+        self.nft_config.as_ref()
+    }
+
+    pub fn set_nft_config(
+        &mut self,
+        config: NftCollectionConfig
+    ) {
+        self.nft_config = Some(config);
+    }
+
+    pub fn minting_pause(
+        &self
+    ) -> Option<&MintingPause> {
+        self.minting_pause.as_ref()
+    }
+
+    pub fn set_minting_pause(
+        &mut self,
+        pause: Option<MintingPause>
+    ) {
+        self.minting_pause = pause;
+    }
+
+    pub fn mint_phases(
+        &self
+    ) -> &[MintPhase] {
+        &self.mint_phases
+    }
+
+    pub fn set_mint_phases(
+        &mut self,
+        phases: Vec<MintPhase>
+    ) {
+        self.mint_phases = phases;
+    }
+
+    pub fn mint_allowlist(
+        &self
+    ) -> &BTreeSet<Principal> {
+        &self.mint_allowlist
+    }
+
+    pub fn mint_allowlist_mut(
+        &mut self
+    ) -> &mut BTreeSet<Principal> {
+        &mut self.mint_allowlist
+    }
+    pub fn deposits_next_block(
+        &self
+    ) -> Option<u64> {
+        self.deposits_next_block
+    }
+
+    pub fn set_deposits_next_block(
+        &mut self,
+        block: u64
+    ) {
+        self.deposits_next_block = Some(block);
+    }
 }
\ No newline at end of file
@@ -1,27 +1,81 @@
+use std::cell::RefCell;
+use ic_stable_structures::BTreeMap;
 use crate::{
-    resources::LOGO, 
-    services::nft, 
+    memory::{get_assets_memory, Memory}, 
+    resources::{LOGO, NFT_PLACEHOLDER, NFT_PLACEHOLDER_MIME_TYPE}, 
+    services::cert, 
+    state, 
     types::{
-        asset::{Asset, AssetPath}, 
-        blob::BlobId
+        asset::{Asset, AssetPath, AssetSource}, 
+        blob::{BlobId, BlobRefKind}
     }
 };
+use super::blob::BlobStorage;
 
 pub struct AssetStorage;
 
+thread_local! {
+    // assets uploaded by the administrator
+    static ASSETS: RefCell<BTreeMap<AssetPath, BlobId, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_assets_memory()
+        )
+    );
+}
+
 impl AssetStorage {
-    pub fn load(
-        path: AssetPath
-    ) -> Option<Asset> {
-        if path == "nft_logo.png" {
-            Some(Asset{
+    pub fn save(
+        path: AssetPath,
+        blob_id: BlobId
+    ) {
+        if let Some(meta) = BlobStorage::load_meta(blob_id) {
+            cert::mutate(|c| c.certify_asset(&path, &meta));
+        }
+
+        let prev = ASSETS.with_borrow_mut(|assets| {
+            assets.insert(path, blob_id)
+        });
+
+        if let Some(prev) = prev {
+            BlobStorage::release(prev, BlobRefKind::Asset);
+        }
+    }
+
+    pub fn for_each<F>(
+        mut f: F
+    ) 
+        where F: FnMut(&AssetPath, BlobId) {
+        ASSETS.with_borrow(|assets| {
+            for (path, blob_id) in assets.iter() {
+                f(&path, blob_id);
+            }
+        });
+    }
+
+    pub fn resolve(
+        path: &AssetPath
+    ) -> Option<AssetSource> {
+        if let Some(blob_id) = ASSETS.with_borrow(|assets| assets.get(path)) {
+            Some(AssetSource::Blob(blob_id))
+        }
+        else if path == "nft_logo.png" {
+            Some(AssetSource::Static(Asset{
                 mime_type: "image/png".to_string(),
                 data: LOGO.to_vec(),
-            })
+            }))
         }
-        else if let Ok(Ok(nft_id)) = path.trim_start_matches("nfts/").parse::<String>()
-            .map(|s| s.trim_end_matches(".jpg").parse::<BlobId>()) {
-            nft::read(|s| s.get_token_image(nft_id))
+        else if let Some(Ok(nft_id)) = path.strip_prefix("nfts/")
+            .and_then(|s| s.strip_suffix(".jpg"))
+            .map(|s| s.parse::<BlobId>()) {
+            if state::read(|s| s.takedowns().hidden_blobs.contains(&nft_id)) {
+                Some(AssetSource::Static(Asset{
+                    mime_type: NFT_PLACEHOLDER_MIME_TYPE.to_string(),
+                    data: NFT_PLACEHOLDER.to_vec(),
+                }))
+            }
+            else {
+                Some(AssetSource::Blob(nft_id))
+            }
         }
         else {
             None
@@ -1,12 +1,30 @@
 use std::cell::RefCell;
 use ic_stable_structures::BTreeMap;
+use sha2::{Digest, Sha256};
 use crate::{
-    memory::{get_blobs_memory, Memory}, 
-    types::blob::{Blob, BlobId}, 
-    utils::rng
+    memory::{
+        get_blob_chunks_memory, get_blob_gc_queue_memory, 
+        get_blob_metas_memory, get_blobs_memory, Memory
+    }, 
+    services::cert, 
+    state, 
+    types::{
+        blob::{
+            blob_id_from_hash, Blob, BlobHash, BlobId, 
+            BlobMeta, BlobRefKind, BlobStoreStats, 
+            MAX_INLINE_BLOB_SIZE
+        }, 
+        temp_blob::TEMP_BLOB_ID_LIMIT
+    }
 };
 
-use super::temp_blob::{TempBlobStorage, MAX_TEMP_BLOBS};
+use super::{derived_blob::DerivedBlobStorage, temp_blob::TempBlobStorage};
+
+pub const PREVIEW_TTL: u64 = 7 * 24 * 60 * 60 * 1_000_000_000; // 7 days, in nanoseconds
+const GC_GRACE_PERIOD: u64 = 24 * 60 * 60 * 1_000_000_000; // 1 day, in nanoseconds
+// blobs stored before the GC queue existed, indexed on each GC run
+const INDEXING_MAX_ITEMS: usize = 1_000;
+const BLOB_TOO_LARGE: &str = "Blob size doesn't fit in 32 bits";
 
 pub struct BlobStorage;
 
@@ -16,35 +34,464 @@ thread_local! {
             get_blobs_memory()
         )
     );
+    static BLOB_METAS: RefCell<BTreeMap<BlobId, BlobMeta, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_blob_metas_memory()
+        )
+    );
+    // data of blobs too large to fit in a single entry
+    static BLOB_CHUNKS: RefCell<BTreeMap<(BlobId, u32), Vec<u8>, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_blob_chunks_memory()
+        )
+    );
+    // blobs without strong references, keyed by (release time, id), with their size
+    static GC_QUEUE: RefCell<BTreeMap<(u64, BlobId), u32, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_blob_gc_queue_memory()
+        )
+    );
 }
 
 impl BlobStorage {
+    /// Stores a blob keyed by its content, so saving the same bytes 
+    /// twice only increments the reference count of the first copy
     pub fn save(
         blob: Blob,
-        is_temp: bool
+        kind: BlobRefKind
     ) -> BlobId {
-        if is_temp {
-            TempBlobStorage::save(blob)
-        }
-        else {
-            let id: BlobId = rng::gen_range(MAX_TEMP_BLOBS as u128..u128::MAX);
+        let hash = Self::hash(&blob.data);
+        let id = blob_id_from_hash(&hash);
+        let now = ic_cdk::api::time();
+
+        let exists = BLOB_METAS.with_borrow(|metas| metas.contains_key(&id));
+        if !exists {
+            let meta = BlobMeta::new(
+                hash, 
+                blob.mime_type.clone(), 
+                Self::checked_size(blob.data.len() as u64), 
+                now
+            );
             BLOBS.with_borrow_mut(|blobs| {
                 blobs.insert(id, blob)
             });
-            id
+            cert::mutate(|c| c.certify_blob(id, &meta));
+            Self::put_meta(id, meta);
+        }
+
+        Self::add_ref(id, kind);
+        id
+    }
+
+    /// Stores a blob whose data was already split in chunks, like the ones 
+    /// received through an upload session. The hash must be of the whole data
+    pub fn save_chunked<I>(
+        mime_type: String,
+        hash: BlobHash,
+        size: u64,
+        chunks: I,
+        kind: BlobRefKind
+    ) -> BlobId 
+        where I: Iterator<Item = Vec<u8>> {
+        let id = blob_id_from_hash(&hash);
+        let now = ic_cdk::api::time();
+
+        let exists = BLOB_METAS.with_borrow(|metas| metas.contains_key(&id));
+        if !exists {
+            let mut meta = BlobMeta::new(hash, mime_type.clone(), Self::checked_size(size), now);
+            
+            if size <= MAX_INLINE_BLOB_SIZE {
+                BLOBS.with_borrow_mut(|blobs| {
+                    blobs.insert(id, Blob {
+                        mime_type,
+                        data: chunks.flatten().collect(),
+                    })
+                });
+            }
+            else {
+                let mut chunk_hashes = vec![];
+                BLOB_CHUNKS.with_borrow_mut(|blob_chunks| {
+                    for (index, chunk) in chunks.enumerate() {
+                        chunk_hashes.push((Self::checked_size(chunk.len() as u64), Self::hash(&chunk)));
+                        blob_chunks.insert((id, index as u32), chunk);
+                        meta.chunks += 1;
+                    }
+                });
+                meta.chunk_hashes = Some(chunk_hashes);
+                BLOBS.with_borrow_mut(|blobs| {
+                    blobs.insert(id, Blob {
+                        mime_type,
+                        data: vec![],
+                    })
+                });
+            }
+
+            cert::mutate(|c| c.certify_blob(id, &meta));
+            Self::put_meta(id, meta);
         }
+
+        Self::add_ref(id, kind);
+        id
     }
 
     pub fn load(
         id: BlobId
     ) -> Option<Blob> {
-        if id < MAX_TEMP_BLOBS as u128 {
+        if id < TEMP_BLOB_ID_LIMIT {
             TempBlobStorage::load(id)
+                .ok()
+                .map(|t| t.blob)
         }
         else {
-            BLOBS.with_borrow(|blobs| {
+            let mut blob = BLOBS.with_borrow(|blobs| {
                 blobs.get(&id)
+            })?;
+            
+            let num_chunks = BLOB_METAS.with_borrow(|metas| {
+                metas.get(&id).map(|m| m.chunks).unwrap_or(0)
+            });
+
+            if num_chunks > 0 {
+                BLOB_CHUNKS.with_borrow(|blob_chunks| {
+                    for (_, chunk) in blob_chunks.range((id, 0)..(id, num_chunks)) {
+                        blob.data.extend(chunk);
+                    }
+                });
+            }
+
+            Some(blob)
+        }
+    }
+
+    /// Loads a single chunk of a blob. Blobs stored inline have only one chunk
+    pub fn load_chunk(
+        id: BlobId,
+        index: u32
+    ) -> Option<Vec<u8>> {
+        let num_chunks = BLOB_METAS.with_borrow(|metas| {
+            metas.get(&id).map(|m| m.chunks).unwrap_or(0)
+        });
+
+        if num_chunks == 0 {
+            if index == 0 {
+                Self::load(id).map(|b| b.data)
+            }
+            else {
+                None
+            }
+        }
+        else {
+            BLOB_CHUNKS.with_borrow(|blob_chunks| {
+                blob_chunks.get(&(id, index))
             })
         }
     }
+
+    pub fn load_meta(
+        id: BlobId
+    ) -> Option<BlobMeta> {
+        BLOB_METAS.with_borrow(|metas| {
+            metas.get(&id)
+        })
+    }
+
+    pub fn for_each_meta<F>(
+        mut f: F
+    ) 
+        where F: FnMut(BlobId, &BlobMeta) {
+        BLOB_METAS.with_borrow(|metas| {
+            for (id, meta) in metas.iter() {
+                f(id, &meta);
+            }
+        });
+    }
+
+    pub fn add_ref(
+        id: BlobId,
+        kind: BlobRefKind
+    ) {
+        let now = ic_cdk::api::time();
+        Self::update_meta(id, |meta| {
+            match kind {
+                BlobRefKind::Post => meta.posts += 1,
+                BlobRefKind::Nft => meta.nfts += 1,
+                BlobRefKind::Asset => meta.assets += 1,
+                BlobRefKind::Preview => meta.preview_expires_at = now + PREVIEW_TTL,
+            }
+            meta.unreferenced_since = None;
+        });
+    }
+
+    pub fn release(
+        id: BlobId,
+        kind: BlobRefKind
+    ) {
+        let now = ic_cdk::api::time();
+        Self::update_meta(id, |meta| {
+            match kind {
+                BlobRefKind::Post => meta.posts = meta.posts.saturating_sub(1),
+                BlobRefKind::Nft => meta.nfts = meta.nfts.saturating_sub(1),
+                BlobRefKind::Asset => meta.assets = meta.assets.saturating_sub(1),
+                BlobRefKind::Preview => meta.preview_expires_at = now,
+            }
+            if meta.posts == 0 && meta.nfts == 0 && meta.assets == 0 {
+                meta.unreferenced_since = Some(now);
+            }
+        });
+    }
+
+    /// Removes up to max_items blobs that have not been referenced for longer than 
+    /// the grace period. Legacy blobs are also indexed here, a few at a time
+    pub fn collect_garbage(
+        max_items: usize
+    ) -> usize {
+        let now = ic_cdk::api::time();
+
+        Self::index_legacy_blobs(max_items, now);
+        Self::index_blobs(INDEXING_MAX_ITEMS);
+
+        // only the blobs released before the grace period are visited
+        let garbage = GC_QUEUE.with_borrow(|queue| {
+            queue.range(..(now.saturating_sub(GC_GRACE_PERIOD), BlobId::MAX))
+                .map(|((_, id), _)| id)
+                .take(max_items)
+                .collect::<Vec<_>>()
+        });
+
+        for id in &garbage {
+            BLOBS.with_borrow_mut(|blobs| {
+                blobs.remove(id)
+            });
+            let meta = Self::remove_meta(*id);
+            cert::mutate(|c| c.uncertify_blob(*id));
+            DerivedBlobStorage::remove_all(*id);
+            if let Some(meta) = meta {
+                BLOB_CHUNKS.with_borrow_mut(|blob_chunks| {
+                    for index in 0..meta.chunks {
+                        blob_chunks.remove(&(*id, index));
+                    }
+                });
+            }
+        }
+
+        garbage.len()
+    }
+
+    pub fn stats(
+    ) -> BlobStoreStats {
+        let now = ic_cdk::api::time();
+        let counters = state::read(|s| s.blob_indexing().counters.clone());
+        let mut stats = BlobStoreStats {
+            num_blobs: counters.num_blobs,
+            num_bytes: counters.num_bytes,
+            num_pinned: counters.num_pinned,
+            ..Default::default()
+        };
+
+        // only the blobs already released, that the GC will collect
+        GC_QUEUE.with_borrow(|queue| {
+            for (_, size) in queue.range(..(now, BlobId::MAX)) {
+                stats.num_unreferenced += 1;
+                stats.num_unreferenced_bytes += size as u64;
+            }
+        });
+
+        stats.num_legacy = BLOBS.with_borrow(|blobs| blobs.len())
+            .saturating_sub(BLOB_METAS.with_borrow(|metas| metas.len()));
+
+        stats.num_temp_blobs = TempBlobStorage::size();
+        stats.num_derived_blobs = DerivedBlobStorage::size();
+
+        stats
+    }
+
+    /// True for the blobs stored without metadata, before reference counting was introduced
+    pub fn is_legacy(
+        id: BlobId
+    ) -> bool {
+        id >= TEMP_BLOB_ID_LIMIT && 
+            !BLOB_METAS.with_borrow(|metas| metas.contains_key(&id)) &&
+            BLOBS.with_borrow(|blobs| blobs.contains_key(&id))
+    }
+
+    /// Indexes a single legacy blob, when it's requested before the GC got to it
+    pub fn index_legacy_blob(
+        id: BlobId
+    ) -> Option<BlobMeta> {
+        if let Some(meta) = Self::load_meta(id) {
+            return Some(meta);
+        }
+
+        let blob = BLOBS.with_borrow(|blobs| blobs.get(&id))?;
+        Some(Self::index_legacy(id, &blob, ic_cdk::api::time()))
+    }
+
+    fn index_legacy(
+        id: BlobId,
+        blob: &Blob,
+        now: u64
+    ) -> BlobMeta {
+        // we don't know who references a legacy blob, so it is never collected
+        let mut meta = BlobMeta::new(
+            Self::hash(&blob.data), 
+            blob.mime_type.clone(), 
+            Self::checked_size(blob.data.len() as u64), 
+            now
+        );
+        meta.pinned = true;
+        meta.unreferenced_since = None;
+
+        cert::mutate(|c| c.certify_blob(id, &meta));
+
+        Self::put_meta(id, meta.clone());
+        meta
+    }
+
+    fn index_legacy_blobs(
+        max_items: usize,
+        now: u64
+    ) {
+        if state::read(|s| s.legacy_blobs_indexed()) {
+            return;
+        }
+
+        let start = state::read(|s| s.legacy_blobs_cursor())
+            .unwrap_or(TEMP_BLOB_ID_LIMIT);
+
+        // blobs already indexed also count, so each run visits at most max_items
+        let mut last = None;
+        let mut num_visited = 0;
+        BLOBS.with_borrow(|blobs| {
+            for (id, blob) in blobs.range(start..).take(max_items) {
+                last = Some(id);
+                num_visited += 1;
+                if !BLOB_METAS.with_borrow(|metas| metas.contains_key(&id)) {
+                    Self::index_legacy(id, &blob, now);
+                }
+            }
+        });
+
+        state::mutate(|s| {
+            match last {
+                Some(id) if num_visited >= max_items && id < BlobId::MAX => {
+                    s.set_legacy_blobs_cursor(Some(id + 1));
+                },
+                _ => {
+                    s.set_legacy_blobs_cursor(None);
+                    s.set_legacy_blobs_indexed(true);
+                }
+            }
+        });
+    }
+
+    /// Adds the blobs stored before the GC queue existed to it, a few at a time
+    fn index_blobs(
+        max_items: usize
+    ) {
+        let indexing = state::read(|s| s.blob_indexing().clone());
+        if indexing.done {
+            return;
+        }
+
+        let metas = BLOB_METAS.with_borrow(|metas| {
+            metas.range(indexing.cursor.unwrap_or(0)..)
+                .take(max_items)
+                .collect::<Vec<_>>()
+        });
+
+        for (id, meta) in &metas {
+            Self::reindex(*id, None, Some(meta));
+        }
+
+        state::mutate(|s| {
+            let indexing = s.blob_indexing_mut();
+            for (_, meta) in &metas {
+                indexing.counters.add(meta);
+            }
+            match metas.last() {
+                Some((id, _)) if metas.len() >= max_items && *id < BlobId::MAX => {
+                    indexing.cursor = Some(id + 1);
+                },
+                _ => {
+                    indexing.cursor = None;
+                    indexing.done = true;
+                }
+            }
+        });
+    }
+
+    fn update_meta<F>(
+        id: BlobId,
+        f: F
+    ) 
+        where F: FnOnce(&mut BlobMeta) {
+        if let Some(mut meta) = Self::load_meta(id) {
+            f(&mut meta);
+            Self::put_meta(id, meta);
+        }
+    }
+
+    /// Every change to the metas goes through here, so the GC queue is kept in sync
+    fn put_meta(
+        id: BlobId,
+        meta: BlobMeta
+    ) {
+        let prev = BLOB_METAS.with_borrow_mut(|metas| {
+            metas.insert(id, meta.clone())
+        });
+        Self::reindex(id, prev.as_ref(), Some(&meta));
+    }
+
+    fn remove_meta(
+        id: BlobId
+    ) -> Option<BlobMeta> {
+        let prev = BLOB_METAS.with_borrow_mut(|metas| {
+            metas.remove(&id)
+        });
+        Self::reindex(id, prev.as_ref(), None);
+        prev
+    }
+
+    fn reindex(
+        id: BlobId,
+        prev: Option<&BlobMeta>,
+        next: Option<&BlobMeta>
+    ) {
+        // blobs not indexed yet are counted by the indexing itself
+        state::mutate(|s| {
+            let indexing = s.blob_indexing_mut();
+            if indexing.is_indexed(id) {
+                if let Some(meta) = prev {
+                    indexing.counters.sub(meta);
+                }
+                if let Some(meta) = next {
+                    indexing.counters.add(meta);
+                }
+            }
+        });
+
+        GC_QUEUE.with_borrow_mut(|queue| {
+            if let Some(key) = prev.and_then(|meta| meta.gc_key()) {
+                queue.remove(&(key, id));
+            }
+            if let Some(meta) = next {
+                if let Some(key) = meta.gc_key() {
+                    queue.insert((key, id), meta.size);
+                }
+            }
+        });
+    }
+
+    fn hash(
+        data: &[u8]
+    ) -> BlobHash {
+        Sha256::digest(data).into()
+    }
+
+    // sizes are bounded by the upload and stable value limits, so this never traps in practice
+    fn checked_size(
+        size: u64
+    ) -> u32 {
+        u32::try_from(size).expect(BLOB_TOO_LARGE)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,87 @@
+use std::cell::RefCell;
+use ic_stable_structures::BTreeMap;
+use crate::{
+    memory::{get_chat_settings_memory, get_chats_memory, Memory}, 
+    types::chat::{ChatKey, ChatSettings, RegisteredChat}
+};
+
+pub struct ChatStorage;
+
+thread_local! {
+    static CHATS: RefCell<BTreeMap<ChatKey, RegisteredChat, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_chats_memory()
+        )
+    );
+
+    static SETTINGS: RefCell<BTreeMap<ChatKey, ChatSettings, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_chat_settings_memory()
+        )
+    );
+}
+
+impl ChatStorage {
+    pub fn save(
+        key: ChatKey,
+        chat: RegisteredChat
+    ) {
+        CHATS.with_borrow_mut(|chats| {
+            chats.insert(key, chat)
+        });
+    }
+
+    pub fn load(
+        key: &ChatKey
+    ) -> Option<RegisteredChat> {
+        CHATS.with_borrow(|chats| {
+            chats.get(key)
+        })
+    }
+
+    pub fn remove(
+        key: &ChatKey
+    ) -> Option<RegisteredChat> {
+        CHATS.with_borrow_mut(|chats| {
+            chats.remove(key)
+        })
+    }
+
+    pub fn for_each<F>(
+        mut f: F
+    ) 
+        where F: FnMut(&ChatKey, &RegisteredChat) {
+        CHATS.with_borrow(|chats| {
+            for (key, chat) in chats.iter() {
+                f(&key, &chat);
+            }
+        });
+    }
+
+    /// Loads the settings of a chat, or the defaults if it was never configured
+    pub fn load_settings(
+        key: &ChatKey
+    ) -> ChatSettings {
+        SETTINGS.with_borrow(|settings| {
+            settings.get(key)
+                .unwrap_or_default()
+        })
+    }
+
+    pub fn save_settings(
+        key: ChatKey,
+        chat_settings: ChatSettings
+    ) {
+        SETTINGS.with_borrow_mut(|settings| {
+            settings.insert(key, chat_settings)
+        });
+    }
+
+    pub fn reset_settings(
+        key: &ChatKey
+    ) {
+        SETTINGS.with_borrow_mut(|settings| {
+            settings.remove(key)
+        });
+    }
+}
\ No newline at end of file
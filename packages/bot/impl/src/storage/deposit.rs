@@ -0,0 +1,50 @@
+use std::cell::RefCell;
+use candid::Principal;
+use ic_ledger_types::{AccountIdentifier, Subaccount};
+use ic_stable_structures::BTreeMap;
+use crate::{
+    memory::{get_deposit_accounts_memory, Memory}, 
+    types::user::UserId
+};
+
+pub struct DepositStorage;
+
+thread_local! {
+    // the account ids, in hex format, of the users' subaccounts, so the ledger scan can 
+    // tell which transfers are deposits
+    static ACCOUNTS: RefCell<BTreeMap<String, UserId, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_deposit_accounts_memory()
+        )
+    );
+}
+
+impl DepositStorage {
+    pub fn register(
+        user_id: Principal
+    ) {
+        let acc_id = AccountIdentifier::new(
+            &ic_cdk::id(), 
+            &Subaccount::from(user_id)
+        );
+
+        ACCOUNTS.with_borrow_mut(|accounts| {
+            if !accounts.contains_key(&acc_id.to_hex()) {
+                accounts.insert(acc_id.to_hex(), user_id);
+            }
+        });
+    }
+
+    pub fn find(
+        acc_id: &AccountIdentifier
+    ) -> Option<UserId> {
+        ACCOUNTS.with_borrow(|accounts| {
+            accounts.get(&acc_id.to_hex())
+        })
+    }
+
+    pub fn size(
+    ) -> u64 {
+        ACCOUNTS.with_borrow(|accounts| accounts.len())
+    }
+}
\ No newline at end of file
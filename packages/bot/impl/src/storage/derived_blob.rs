@@ -0,0 +1,122 @@
+use std::cell::RefCell;
+use ic_stable_structures::BTreeMap;
+use sha2::{Digest, Sha256};
+use crate::{
+    memory::{get_derived_blobs_memory, get_derived_blobs_queue_memory, Memory}, 
+    services::cert, 
+    types::{
+        blob::{Blob, BlobHash, BlobId}, 
+        derived_blob::{DerivedBlob, DerivedBlobKey, ImageTransform, MAX_DERIVED_BLOBS}
+    }
+};
+
+pub struct DerivedBlobStorage;
+
+thread_local! {
+    // resized/re-encoded versions of permanent blobs
+    static DERIVED_BLOBS: RefCell<BTreeMap<DerivedBlobKey, DerivedBlob, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_derived_blobs_memory()
+        )
+    );
+
+    // insertion order, used to evict the oldest entries
+    static QUEUE: RefCell<BTreeMap<u64, DerivedBlobKey, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_derived_blobs_queue_memory()
+        )
+    );
+}
+
+impl DerivedBlobStorage {
+    pub fn load(
+        blob_id: BlobId,
+        transform: &ImageTransform
+    ) -> Option<DerivedBlob> {
+        DERIVED_BLOBS.with_borrow(|blobs| {
+            blobs.get(&(blob_id, transform.key()))
+        })
+    }
+
+    /// Caches a derived blob, evicting the oldest ones (and their certification) 
+    /// if the cache is full. Returns the hash of the blob
+    pub fn save(
+        blob_id: BlobId,
+        transform: &ImageTransform,
+        blob: Blob
+    ) -> BlobHash {
+        let key = (blob_id, transform.key());
+        let hash: BlobHash = Sha256::digest(&blob.data).into();
+
+        let seq = QUEUE.with_borrow_mut(|queue| {
+            while queue.len() >= MAX_DERIVED_BLOBS {
+                let Some((_, oldest)) = queue.pop_first() else {
+                    break;
+                };
+                DERIVED_BLOBS.with_borrow_mut(|blobs| {
+                    blobs.remove(&oldest)
+                });
+                cert::mutate(|c| c.uncertify_derived(oldest));
+            }
+
+            let seq = queue.last_key_value()
+                .map(|(seq, _)| seq + 1)
+                .unwrap_or(0);
+            queue.insert(seq, key);
+            seq
+        });
+
+        let prev = DERIVED_BLOBS.with_borrow_mut(|blobs| {
+            blobs.insert(key, DerivedBlob {
+                seq,
+                blob,
+                hash,
+            })
+        });
+
+        if let Some(prev) = prev {
+            QUEUE.with_borrow_mut(|queue| {
+                queue.remove(&prev.seq)
+            });
+        }
+
+        hash
+    }
+
+    /// Removes every derived version of a blob
+    pub fn remove_all(
+        blob_id: BlobId
+    ) {
+        let removed = DERIVED_BLOBS.with_borrow(|blobs| {
+            blobs.range((blob_id, 0)..=(blob_id, u64::MAX))
+                .map(|(key, d)| (key, d.seq))
+                .collect::<Vec<_>>()
+        });
+
+        for (key, seq) in removed {
+            DERIVED_BLOBS.with_borrow_mut(|blobs| {
+                blobs.remove(&key)
+            });
+            QUEUE.with_borrow_mut(|queue| {
+                queue.remove(&seq)
+            });
+            cert::mutate(|c| c.uncertify_derived(key));
+        }
+    }
+
+    pub fn for_each<F>(
+        mut f: F
+    ) 
+        where F: FnMut(DerivedBlobKey, &str, BlobHash) {
+        DERIVED_BLOBS.with_borrow(|blobs| {
+            for (key, d) in blobs.iter() {
+                f(key, &d.blob.mime_type, d.hash);
+            }
+        });
+    }
+
+    pub fn size(
+    ) -> u64 {
+        DERIVED_BLOBS.with_borrow(|blobs| blobs.len())
+    }
+}
\ No newline at end of file
@@ -41,6 +41,22 @@ impl EventStorage {
         slice
     }
 
+    /// Iterates from the newest to the oldest event, while f returns true
+    pub fn for_each_rev<F>(
+        mut f: F
+    ) 
+        where F: FnMut(&Event) -> bool {
+        EVENTS.with_borrow(|events| {
+            for index in (0..events.len()).rev() {
+                if let Some(event) = events.get(index) {
+                    if !f(&event) {
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
     pub fn size(
     ) -> u32 {
         EVENTS.with_borrow(|events|
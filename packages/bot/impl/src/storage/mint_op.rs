@@ -0,0 +1,98 @@
+use std::cell::RefCell;
+use ic_stable_structures::BTreeMap;
+use crate::{
+    memory::{get_mint_ops_memory, get_open_mint_ops_memory, Memory}, 
+    types::mint_op::{MintOp, MintOpId}
+};
+
+pub struct MintOpStorage;
+
+thread_local! {
+    // the journal is never pruned, so the history of each mint is kept
+    static MINT_OPS: RefCell<BTreeMap<MintOpId, MintOp, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_mint_ops_memory()
+        )
+    );
+    // ids of the operations not in a final step, so they can be found without a full scan
+    static OPEN_MINT_OPS: RefCell<BTreeMap<MintOpId, (), Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_open_mint_ops_memory()
+        )
+    );
+}
+
+impl MintOpStorage {
+    pub fn create(
+        mut op: MintOp
+    ) -> MintOp {
+        let id = MINT_OPS.with_borrow(|ops| {
+            ops.last_key_value()
+                .map(|(id, _)| id + 1)
+                .unwrap_or(1)
+        });
+        op.id = id;
+        Self::save(&op);
+        op
+    }
+
+    pub fn save(
+        op: &MintOp
+    ) {
+        OPEN_MINT_OPS.with_borrow_mut(|open| {
+            if op.step.is_final() {
+                open.remove(&op.id);
+            }
+            else {
+                open.insert(op.id, ());
+            }
+        });
+
+        MINT_OPS.with_borrow_mut(|ops| {
+            ops.insert(op.id, op.clone())
+        });
+    }
+
+    pub fn load(
+        id: MintOpId
+    ) -> Option<MintOp> {
+        MINT_OPS.with_borrow(|ops| {
+            ops.get(&id)
+        })
+    }
+
+    pub fn list_open(
+    ) -> Vec<MintOp> {
+        OPEN_MINT_OPS.with_borrow(|open| {
+            MINT_OPS.with_borrow(|ops| {
+                open.iter()
+                    .filter_map(|(id, _)| ops.get(&id))
+                    .collect()
+            })
+        })
+    }
+
+    /// Lists the operations, or only the open ones, newest first
+    pub fn list(
+        open_only: bool,
+        offset: usize,
+        size: usize
+    ) -> Vec<MintOp> {
+        if open_only {
+            return Self::list_open().into_iter()
+                .rev()
+                .skip(offset)
+                .take(size)
+                .collect();
+        }
+
+        MINT_OPS.with_borrow(|ops| {
+            ops.iter()
+                .rev()
+                .skip(offset)
+                .take(size)
+                .map(|(_, op)| op)
+                .collect()
+        })
+    }
+}
\ No newline at end of file
@@ -5,4 +5,15 @@ pub mod thumb;
 pub mod user;
 pub mod asset;
 pub mod nft;
-pub mod event;
\ No newline at end of file
+pub mod event;
+pub mod upload;
+pub mod derived_blob;
+pub mod chat;
+pub mod webhook;
+pub mod schedule;
+pub mod moderation;
+pub mod report;
+pub mod role;
+pub mod nft_config;
+pub mod mint_op;
+pub mod deposit;
\ No newline at end of file
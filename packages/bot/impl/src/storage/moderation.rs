@@ -0,0 +1,51 @@
+use std::cell::RefCell;
+use ic_stable_structures::BTreeMap;
+use crate::{
+    memory::{get_refusals_memory, Memory}, 
+    types::moderation::{Refusal, RefusalId, MAX_REFUSALS}
+};
+
+pub struct ModerationStorage;
+
+thread_local! {
+    // only the latest refusals are kept
+    static REFUSALS: RefCell<BTreeMap<RefusalId, Refusal, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_refusals_memory()
+        )
+    );
+}
+
+impl ModerationStorage {
+    pub fn log_refusal(
+        mut refusal: Refusal
+    ) -> RefusalId {
+        REFUSALS.with_borrow_mut(|refusals| {
+            while refusals.len() >= MAX_REFUSALS {
+                refusals.pop_first();
+            }
+
+            let id = refusals.last_key_value()
+                .map(|(id, _)| id + 1)
+                .unwrap_or(1);
+            refusal.id = id;
+            refusals.insert(id, refusal);
+            id
+        })
+    }
+
+    /// Lists the refusals, newest first
+    pub fn list_refusals(
+        offset: usize,
+        size: usize
+    ) -> Vec<Refusal> {
+        REFUSALS.with_borrow(|refusals| {
+            refusals.iter()
+                .rev()
+                .skip(offset)
+                .take(size)
+                .map(|(_, refusal)| refusal)
+                .collect()
+        })
+    }
+}
\ No newline at end of file
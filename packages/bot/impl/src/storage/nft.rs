@@ -70,4 +70,13 @@ impl NftStorage {
     ) -> u64 {
         NFTS.with_borrow(|nfts| nfts.len())
     }
+
+    /// Timestamp, in nanoseconds, of the last NFT minted
+    pub fn last_minted_at(
+    ) -> Option<u64> {
+        NFTS.with_borrow(|nfts| {
+            nfts.last_key_value()
+                .map(|(_, nft)| nft.minted_at as u64 * 1_000_000_000)
+        })
+    }
 }
\ No newline at end of file
@@ -0,0 +1,54 @@
+use std::cell::RefCell;
+use ic_stable_structures::BTreeMap;
+use crate::{
+    memory::{get_nft_config_changes_memory, Memory}, 
+    types::nft_col::{NftConfigChange, NftConfigChangeId}
+};
+
+pub struct NftConfigStorage;
+
+thread_local! {
+    // config changes are rare, so they are never pruned
+    static CHANGES: RefCell<BTreeMap<NftConfigChangeId, NftConfigChange, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_nft_config_changes_memory()
+        )
+    );
+}
+
+impl NftConfigStorage {
+    pub fn log_change(
+        mut change: NftConfigChange
+    ) -> NftConfigChangeId {
+        CHANGES.with_borrow_mut(|changes| {
+            let id = changes.last_key_value()
+                .map(|(id, _)| id + 1)
+                .unwrap_or(1);
+            change.id = id;
+            changes.insert(id, change);
+            id
+        })
+    }
+
+    pub fn size(
+    ) -> usize {
+        CHANGES.with_borrow(|changes| {
+            changes.len() as usize
+        })
+    }
+
+    /// Lists the changes, newest first
+    pub fn list_changes(
+        offset: usize,
+        size: usize
+    ) -> Vec<NftConfigChange> {
+        CHANGES.with_borrow(|changes| {
+            changes.iter()
+                .rev()
+                .skip(offset)
+                .take(size)
+                .map(|(_, change)| change)
+                .collect()
+        })
+    }
+}
\ No newline at end of file
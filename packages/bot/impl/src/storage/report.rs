@@ -0,0 +1,119 @@
+use std::cell::RefCell;
+use ic_stable_structures::BTreeMap;
+use crate::{
+    memory::{
+        get_audit_log_memory, 
+        get_posted_memes_memory, 
+        get_reports_memory, 
+        Memory
+    }, 
+    types::{
+        blob::BlobId, 
+        meme::MemeId, 
+        report::{AuditEntry, AuditEntryId, PostedMeme, Report, ReportStatus}
+    }
+};
+
+pub struct ReportStorage;
+
+thread_local! {
+    // the last post of each meme
+    static POSTED_MEMES: RefCell<BTreeMap<MemeId, PostedMeme, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_posted_memes_memory()
+        )
+    );
+    static REPORTS: RefCell<BTreeMap<BlobId, Report, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_reports_memory()
+        )
+    );
+    // the audit log is never pruned, as admin actions are rare
+    static AUDIT_LOG: RefCell<BTreeMap<AuditEntryId, AuditEntry, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_audit_log_memory()
+        )
+    );
+}
+
+impl ReportStorage {
+    pub fn save_post(
+        meme_id: MemeId,
+        post: PostedMeme
+    ) {
+        POSTED_MEMES.with_borrow_mut(|posts| {
+            posts.insert(meme_id, post)
+        });
+    }
+
+    pub fn load_post(
+        meme_id: &MemeId
+    ) -> Option<PostedMeme> {
+        POSTED_MEMES.with_borrow(|posts| {
+            posts.get(meme_id)
+        })
+    }
+
+    pub fn save(
+        report: Report
+    ) {
+        REPORTS.with_borrow_mut(|reports| {
+            reports.insert(report.blob_id, report)
+        });
+    }
+
+    pub fn load(
+        blob_id: BlobId
+    ) -> Option<Report> {
+        REPORTS.with_borrow(|reports| {
+            reports.get(&blob_id)
+        })
+    }
+
+    /// Lists the reports, optionally filtered by status, most recently updated first
+    pub fn list(
+        status: Option<ReportStatus>,
+        offset: usize,
+        size: usize
+    ) -> Vec<Report> {
+        REPORTS.with_borrow(|reports| {
+            let mut list = reports.iter()
+                .map(|(_, report)| report)
+                .filter(|report| status.map_or(true, |status| report.status == status))
+                .collect::<Vec<_>>();
+            list.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+            list.into_iter()
+                .skip(offset)
+                .take(size)
+                .collect()
+        })
+    }
+
+    pub fn log_audit(
+        mut entry: AuditEntry
+    ) -> AuditEntryId {
+        AUDIT_LOG.with_borrow_mut(|log| {
+            let id = log.last_key_value()
+                .map(|(id, _)| id + 1)
+                .unwrap_or(1);
+            entry.id = id;
+            log.insert(id, entry);
+            id
+        })
+    }
+
+    /// Lists the audit log, newest first
+    pub fn list_audit(
+        offset: usize,
+        size: usize
+    ) -> Vec<AuditEntry> {
+        AUDIT_LOG.with_borrow(|log| {
+            log.iter()
+                .rev()
+                .skip(offset)
+                .take(size)
+                .map(|(_, entry)| entry)
+                .collect()
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,113 @@
+use std::cell::RefCell;
+use candid::Principal;
+use ic_stable_structures::BTreeMap;
+use crate::{
+    memory::{get_roles_memory, Memory}, 
+    types::role::{Role, RoleError, RoleGrant}
+};
+
+pub struct RoleStorage;
+
+thread_local! {
+    static ROLES: RefCell<BTreeMap<Principal, RoleGrant, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_roles_memory()
+        )
+    );
+}
+
+impl RoleStorage {
+    /// True if the principal has the role. Owners have every role
+    pub fn has_role(
+        principal: &Principal,
+        role: Role
+    ) -> bool {
+        ROLES.with_borrow(|roles| {
+            roles.get(principal)
+                .map(|grant| grant.roles.contains(&Role::Owner) || grant.roles.contains(&role))
+                .unwrap_or(false)
+        })
+    }
+
+    pub fn has_any_role(
+        principal: &Principal
+    ) -> bool {
+        ROLES.with_borrow(|roles| {
+            roles.get(principal)
+                .map(|grant| !grant.roles.is_empty())
+                .unwrap_or(false)
+        })
+    }
+
+    pub fn has_owner(
+    ) -> bool {
+        ROLES.with_borrow(|roles| {
+            roles.iter()
+                .any(|(_, grant)| grant.roles.contains(&Role::Owner))
+        })
+    }
+
+    pub fn grant(
+        principal: Principal,
+        new_roles: &[Role],
+        updated_by: Principal
+    ) {
+        ROLES.with_borrow_mut(|roles| {
+            let mut grant = roles.get(&principal)
+                .unwrap_or(RoleGrant {
+                    roles: Default::default(),
+                    updated_by,
+                    updated_at: 0,
+                });
+            grant.roles.extend(new_roles.iter().cloned());
+            grant.updated_by = updated_by;
+            grant.updated_at = ic_cdk::api::time();
+            roles.insert(principal, grant);
+        });
+    }
+
+    /// Revokes the roles, making sure there's always an owner left
+    pub fn revoke(
+        principal: Principal,
+        old_roles: &[Role],
+        updated_by: Principal
+    ) -> Result<(), RoleError> {
+        ROLES.with_borrow_mut(|roles| {
+            let Some(mut grant) = roles.get(&principal) else {
+                return Ok(());
+            };
+
+            if old_roles.contains(&Role::Owner) && grant.roles.contains(&Role::Owner) {
+                let num_owners = roles.iter()
+                    .filter(|(_, grant)| grant.roles.contains(&Role::Owner))
+                    .count();
+                if num_owners <= 1 {
+                    return Err(RoleError::LastOwner);
+                }
+            }
+
+            for role in old_roles {
+                grant.roles.remove(role);
+            }
+
+            if grant.roles.is_empty() {
+                roles.remove(&principal);
+            }
+            else {
+                grant.updated_by = updated_by;
+                grant.updated_at = ic_cdk::api::time();
+                roles.insert(principal, grant);
+            }
+
+            Ok(())
+        })
+    }
+
+    pub fn list(
+    ) -> Vec<(Principal, RoleGrant)> {
+        ROLES.with_borrow(|roles| {
+            roles.iter()
+                .collect()
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,78 @@
+use std::cell::RefCell;
+use ic_stable_structures::BTreeMap;
+use crate::{
+    memory::{get_jobs_memory, Memory}, 
+    types::{
+        chat::ChatKey, 
+        schedule::{Job, JobId}
+    }
+};
+
+pub struct ScheduleStorage;
+
+thread_local! {
+    static JOBS: RefCell<BTreeMap<JobId, Job, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_jobs_memory()
+        )
+    );
+}
+
+impl ScheduleStorage {
+    pub fn add(
+        job: Job
+    ) -> JobId {
+        JOBS.with_borrow_mut(|jobs| {
+            let id = jobs.last_key_value()
+                .map(|(id, _)| id + 1)
+                .unwrap_or(1);
+            jobs.insert(id, job);
+            id
+        })
+    }
+
+    pub fn save(
+        id: JobId,
+        job: Job
+    ) {
+        JOBS.with_borrow_mut(|jobs| {
+            jobs.insert(id, job)
+        });
+    }
+
+    pub fn remove(
+        id: JobId
+    ) -> Option<Job> {
+        JOBS.with_borrow_mut(|jobs| {
+            jobs.remove(&id)
+        })
+    }
+
+    pub fn load(
+        id: JobId
+    ) -> Option<Job> {
+        JOBS.with_borrow(|jobs| {
+            jobs.get(&id)
+        })
+    }
+
+    pub fn list_by_chat(
+        chat: &ChatKey
+    ) -> Vec<(JobId, Job)> {
+        JOBS.with_borrow(|jobs| {
+            jobs.iter()
+                .filter(|(_, job)| job.chat == *chat)
+                .collect()
+        })
+    }
+
+    pub fn list_due(
+        now: u64
+    ) -> Vec<(JobId, Job)> {
+        JOBS.with_borrow(|jobs| {
+            jobs.iter()
+                .filter(|(_, job)| job.next_run_at <= now)
+                .collect()
+        })
+    }
+}
\ No newline at end of file
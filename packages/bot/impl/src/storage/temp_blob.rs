@@ -1,49 +1,122 @@
 use std::cell::RefCell;
-use ic_stable_structures::Vec;
+use candid::Principal;
+use ic_stable_structures::BTreeMap;
+use sha2::{Digest, Sha256};
 use crate::{
-    memory::{get_temp_blobs_memory, Memory}, state, types::blob::{Blob, BlobId} 
+    memory::{get_temp_blobs_memory, Memory},
+    services::cert,
+    state,
+    types::{
+        blob::{Blob, BlobHash, BlobId},
+        temp_blob::{TempBlob, TempBlobError, MAX_TEMP_BLOBS, TEMP_BLOB_TTL}
+    }
 };
 
-pub const MAX_TEMP_BLOBS: usize = 10_000;
-
 pub struct TempBlobStorage;
 
 thread_local! {
-    static TEMP_BLOBS: RefCell<Vec<Blob, Memory>> = RefCell::new(
-        Vec::init(
+    static TEMP_BLOBS: RefCell<BTreeMap<u32, TempBlob, Memory>> = RefCell::new(
+        BTreeMap::init(
             get_temp_blobs_memory()
-        ).unwrap()
+        )
     );
 }
 
 impl TempBlobStorage {
     pub fn save(
-        blob: Blob
+        blob: Blob,
+        owner: Principal
     ) -> BlobId {
-        let index = state::mutate(|s| {
+        let slot = state::mutate(|s| {
             let index = s.temp_bobs_index();
             let last = *index;
-            *index = (*index + 1) % MAX_TEMP_BLOBS;
+            *index = (*index + 1) % MAX_TEMP_BLOBS as usize;
             last
+        }) as u32;
+
+        let hash: BlobHash = Sha256::digest(&blob.data).into();
+        let mime_type = blob.mime_type.clone();
+
+        let (id, prev_id) = TEMP_BLOBS.with_borrow_mut(|blobs| {
+            // bump the generation, so any reference to the previous blob in this slot becomes stale
+            let prev = blobs.get(&slot);
+            let generation = prev.as_ref()
+                .map(|b| b.generation.wrapping_add(1).max(1))
+                .unwrap_or(1);
+
+            let temp = TempBlob {
+                owner,
+                created_at: ic_cdk::api::time(),
+                ttl: TEMP_BLOB_TTL,
+                generation,
+                blob,
+                hash,
+            };
+            let id = temp.id(slot);
+
+            blobs.insert(slot, temp);
+            (id, prev.map(|b| b.id(slot)))
         });
 
-        TEMP_BLOBS.with_borrow_mut(|blobs| {
-            if index >= blobs.len() as usize {
-                blobs.push(&blob).unwrap();
-            }
-            else {
-                blobs.set(index as _, &blob);
+        cert::mutate(|c| {
+            if let Some(prev_id) = prev_id {
+                c.uncertify_temp_blob(prev_id);
             }
+            c.certify_temp_blob(id, &mime_type, hash);
         });
 
-        index as _
+        id
     }
 
     pub fn load(
         id: BlobId
-    ) -> Option<Blob> {
+    ) -> Result<TempBlob, TempBlobError> {
+        let (slot, generation) = TempBlob::split_id(id);
+
+        let Some(temp) = TEMP_BLOBS.with_borrow(|blobs| {
+            blobs.get(&slot)
+        }) else {
+            return Err(TempBlobError::NotFound);
+        };
+
+        if temp.generation != generation ||
+            temp.is_expired(ic_cdk::api::time()) {
+            return Err(TempBlobError::Expired);
+        }
+
+        Ok(temp)
+    }
+
+    pub fn load_owned(
+        id: BlobId,
+        owner: &Principal
+    ) -> Result<Blob, TempBlobError> {
+        let temp = Self::load(id)?;
+        if temp.owner != *owner {
+            return Err(TempBlobError::NotOwner);
+        }
+
+        Ok(temp.blob)
+    }
+
+    /// Calls f with the id, mime type and hash of each temp blob not expired yet
+    pub fn for_each_live<F>(
+        mut f: F
+    ) 
+        where F: FnMut(BlobId, &str, BlobHash) {
+        let now = ic_cdk::api::time();
         TEMP_BLOBS.with_borrow(|blobs| {
-            blobs.get(id as _)
-        })
+            for (slot, temp) in blobs.iter() {
+                if temp.is_expired(now) {
+                    continue;
+                }
+                f(temp.id(slot), &temp.blob.mime_type, temp.hash);
+            }
+        });
+    }
+
+    pub fn size(
+    ) -> u64 {
+        TEMP_BLOBS.with_borrow(|blobs| blobs.len())
     }
 }
\ No newline at end of file
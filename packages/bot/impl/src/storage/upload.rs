@@ -0,0 +1,108 @@
+use std::cell::RefCell;
+use bot_api::upload::UploadId;
+use ic_stable_structures::BTreeMap;
+use crate::{
+    memory::{get_upload_chunks_memory, get_uploads_memory, Memory}, 
+    types::upload::UploadSession, 
+    utils::rng
+};
+
+pub struct UploadStorage;
+
+thread_local! {
+    static UPLOADS: RefCell<BTreeMap<UploadId, UploadSession, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_uploads_memory()
+        )
+    );
+    static UPLOAD_CHUNKS: RefCell<BTreeMap<(UploadId, u32), Vec<u8>, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_upload_chunks_memory()
+        )
+    );
+}
+
+impl UploadStorage {
+    pub fn start(
+        session: UploadSession
+    ) -> UploadId {
+        let id: UploadId = rng::gen();
+        UPLOADS.with_borrow_mut(|uploads| {
+            uploads.insert(id, session)
+        });
+        id
+    }
+
+    pub fn load(
+        id: UploadId
+    ) -> Option<UploadSession> {
+        UPLOADS.with_borrow(|uploads| {
+            uploads.get(&id)
+        })
+    }
+
+    pub fn save_chunk(
+        id: UploadId,
+        index: u32,
+        data: Vec<u8>
+    ) {
+        UPLOAD_CHUNKS.with_borrow_mut(|chunks| {
+            chunks.insert((id, index), data)
+        });
+    }
+
+    pub fn missing_chunks(
+        id: UploadId,
+        session: &UploadSession
+    ) -> Vec<u32> {
+        UPLOAD_CHUNKS.with_borrow(|chunks| {
+            (0..session.num_chunks())
+                .filter(|index| !chunks.contains_key(&(id, *index)))
+                .collect()
+        })
+    }
+
+    /// Returns the chunks of the session in order, keeping them stored
+    pub fn load_chunks(
+        id: UploadId,
+        session: &UploadSession
+    ) -> Vec<Vec<u8>> {
+        UPLOAD_CHUNKS.with_borrow(|chunks| {
+            (0..session.num_chunks())
+                .filter_map(|index| chunks.get(&(id, index)))
+                .collect()
+        })
+    }
+
+    /// Removes the session and its chunks
+    pub fn remove(
+        id: UploadId,
+        session: &UploadSession
+    ) {
+        UPLOADS.with_borrow_mut(|uploads| {
+            uploads.remove(&id)
+        });
+
+        UPLOAD_CHUNKS.with_borrow_mut(|chunks| {
+            for index in 0..session.num_chunks() {
+                chunks.remove(&(id, index));
+            }
+        });
+    }
+
+    pub fn remove_expired(
+        now: u64
+    ) -> usize {
+        let expired = UPLOADS.with_borrow(|uploads| {
+            uploads.iter()
+                .filter(|(_, session)| session.expires_at <= now)
+                .collect::<Vec<_>>()
+        });
+
+        for (id, session) in &expired {
+            Self::remove(*id, session);
+        }
+
+        expired.len()
+    }
+}
\ No newline at end of file
@@ -34,4 +34,18 @@ impl UserStorage {
                 .unwrap_or_default()
         })
     }
+
+    pub fn ids(
+    ) -> Vec<UserId> {
+        USERS.with_borrow(|users| {
+            users.iter()
+                .map(|(id, _)| id)
+                .collect()
+        })
+    }
+
+    pub fn size(
+    ) -> u64 {
+        USERS.with_borrow(|users| users.len())
+    }
 }
\ No newline at end of file
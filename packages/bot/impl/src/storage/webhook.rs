@@ -0,0 +1,132 @@
+use std::cell::RefCell;
+use ic_stable_structures::BTreeMap;
+use sha2::{Digest, Sha256};
+use crate::{
+    memory::{
+        get_webhook_deliveries_memory, get_webhook_nonces_memory, 
+        get_webhooks_memory, Memory
+    }, 
+    types::webhook::{
+        Webhook, WebhookDelivery, WebhookDeliveryId, 
+        WebhookName, MAX_WEBHOOK_DELIVERIES, WEBHOOK_MAX_CLOCK_SKEW
+    }
+};
+
+pub struct WebhookStorage;
+
+thread_local! {
+    static WEBHOOKS: RefCell<BTreeMap<WebhookName, Webhook, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_webhooks_memory()
+        )
+    );
+
+    // only the latest deliveries are kept
+    static DELIVERIES: RefCell<BTreeMap<WebhookDeliveryId, WebhookDelivery, Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_webhook_deliveries_memory()
+        )
+    );
+
+    // signed requests already received, keyed by (timestamp, hash of the webhook and signature). 
+    // Only kept while their timestamp is accepted, as older requests are rejected anyway
+    static NONCES: RefCell<BTreeMap<(u64, [u8; 32]), (), Memory>> = RefCell::new(
+        BTreeMap::init(
+            get_webhook_nonces_memory()
+        )
+    );
+}
+
+impl WebhookStorage {
+    pub fn save(
+        name: WebhookName,
+        webhook: Webhook
+    ) {
+        WEBHOOKS.with_borrow_mut(|webhooks| {
+            webhooks.insert(name, webhook)
+        });
+    }
+
+    pub fn load(
+        name: &WebhookName
+    ) -> Option<Webhook> {
+        WEBHOOKS.with_borrow(|webhooks| {
+            webhooks.get(name)
+        })
+    }
+
+    pub fn remove(
+        name: &WebhookName
+    ) -> Option<Webhook> {
+        WEBHOOKS.with_borrow_mut(|webhooks| {
+            webhooks.remove(name)
+        })
+    }
+
+    pub fn for_each<F>(
+        mut f: F
+    ) 
+        where F: FnMut(&WebhookName, &Webhook) {
+        WEBHOOKS.with_borrow(|webhooks| {
+            for (name, webhook) in webhooks.iter() {
+                f(&name, &webhook);
+            }
+        });
+    }
+
+    pub fn log_delivery(
+        mut delivery: WebhookDelivery
+    ) -> WebhookDeliveryId {
+        DELIVERIES.with_borrow_mut(|deliveries| {
+            while deliveries.len() >= MAX_WEBHOOK_DELIVERIES {
+                deliveries.pop_first();
+            }
+
+            let id = deliveries.last_key_value()
+                .map(|(id, _)| id + 1)
+                .unwrap_or(1);
+            delivery.id = id;
+            deliveries.insert(id, delivery);
+            id
+        })
+    }
+
+    /// Lists the deliveries, newest first
+    pub fn list_deliveries(
+        offset: usize,
+        size: usize
+    ) -> Vec<WebhookDelivery> {
+        DELIVERIES.with_borrow(|deliveries| {
+            deliveries.iter()
+                .rev()
+                .skip(offset)
+                .take(size)
+                .map(|(_, delivery)| delivery)
+                .collect()
+        })
+    }
+    /// Records a signed request, returning false if it was already received (a replay)
+    pub fn register_nonce(
+        name: &WebhookName,
+        timestamp: u64,
+        signature: &[u8],
+        now: u64
+    ) -> bool {
+        let mut hasher = Sha256::new();
+        hasher.update(name.as_bytes());
+        hasher.update(b".");
+        hasher.update(signature);
+        let key: (u64, [u8; 32]) = (timestamp, hasher.finalize().into());
+
+        NONCES.with_borrow_mut(|nonces| {
+            let expired = nonces.range(..(now.saturating_sub(WEBHOOK_MAX_CLOCK_SKEW), [0u8; 32]))
+                .map(|(key, _)| key)
+                .collect::<Vec<_>>();
+            for key in expired {
+                nonces.remove(&key);
+            }
+
+            nonces.insert(key, ()).is_none()
+        })
+    }
+}
\ No newline at end of file
@@ -1,5 +1,6 @@
 use candid::CandidType;
 use serde::Deserialize;
+use super::blob::BlobId;
 
 pub type AssetPath = String;
 
@@ -9,3 +10,7 @@ pub struct Asset {
     pub data: Vec<u8>,
 }
 
+pub enum AssetSource {
+    Blob(BlobId),
+    Static(Asset),
+}
\ No newline at end of file
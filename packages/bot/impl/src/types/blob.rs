@@ -2,18 +2,212 @@ use std::borrow::Cow;
 
 use candid::{CandidType, Decode, Encode};
 use ic_stable_structures::{storable::Bound, Storable};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 const MAX_VALUE_SIZE: u32 = 524288;
+pub const MAX_INLINE_BLOB_SIZE: u64 = MAX_VALUE_SIZE as u64 - 1024; // leave room for the encoding overhead
 
 pub type BlobId = u128;
+pub type BlobHash = [u8; 32];
 
-#[derive(CandidType, Deserialize)]
+#[derive(Clone, CandidType, Deserialize)]
 pub struct Blob {
     pub mime_type: String,
     pub data: Vec<u8>,
 }
 
+#[derive(Clone, Copy, Debug)]
+pub enum BlobRefKind {
+    Post,
+    Nft,
+    Preview,
+    Asset,
+}
+
+#[derive(Clone, CandidType, Deserialize)]
+pub struct BlobMeta {
+    pub hash: BlobHash,
+    pub mime_type: String,
+    pub size: u32,
+    pub chunks: u32, // 0 when the data is stored inline, in the blob itself
+    pub created_at: u64,
+    pub posts: u32,
+    pub nfts: u32,
+    pub assets: u32,
+    pub preview_expires_at: u64, // previews are weak references, only valid until this time
+    pub unreferenced_since: Option<u64>,
+    pub pinned: bool, // legacy blobs stored before reference counting was introduced
+    pub chunk_hashes: Option<Vec<(u32, BlobHash)>>, // size and hash of each chunk, so partial responses can be certified
+}
+
+impl BlobMeta {
+    pub fn new(
+        hash: BlobHash,
+        mime_type: String,
+        size: u32,
+        created_at: u64
+    ) -> Self {
+        Self {
+            hash,
+            mime_type,
+            size,
+            chunks: 0,
+            created_at,
+            posts: 0,
+            nfts: 0,
+            assets: 0,
+            preview_expires_at: 0,
+            unreferenced_since: Some(created_at),
+            pinned: false,
+            chunk_hashes: None,
+        }
+    }
+
+    /// First byte, last byte and hash of each chunk. Empty for blobs stored inline 
+    /// or before the chunk hashes were kept
+    pub fn chunk_ranges(
+        &self
+    ) -> Vec<(u64, u64, BlobHash)> {
+        let mut start = 0u64;
+        self.chunk_hashes.iter()
+            .flatten()
+            .map(|(size, hash)| {
+                let range = (start, start + *size as u64 - 1, *hash);
+                start += *size as u64;
+                range
+            })
+            .collect()
+    }
+
+    /// Index of the chunk spanning exactly these bytes, the only ranges that are certified
+    pub fn chunk_index(
+        &self,
+        start: u64,
+        end: u64
+    ) -> Option<u32> {
+        self.chunk_ranges().into_iter()
+            .position(|(s, e, _)| s == start && e == end)
+            .map(|index| index as u32)
+    }
+
+    pub fn content_range(
+        &self,
+        start: u64,
+        end: u64
+    ) -> String {
+        format!("bytes {}-{}/{}", start, end, self.size)
+    }
+
+    pub fn released_at(
+        &self
+    ) -> u64 {
+        self.unreferenced_since.unwrap_or(0).max(self.preview_expires_at)
+    }
+
+    /// True while a post, NFT or asset references the blob. Previews don't count
+    pub fn is_referenced(
+        &self
+    ) -> bool {
+        self.pinned || self.posts > 0 || self.nfts > 0 || self.assets > 0
+    }
+
+    /// Key of the blob in the GC queue, None while it has strong references
+    pub fn gc_key(
+        &self
+    ) -> Option<u64> {
+        if self.is_referenced() {
+            None
+        }
+        else {
+            Some(self.released_at())
+        }
+    }
+}
+
+/// Progress of the indexing of the blobs stored before the GC queue existed
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BlobIndexing {
+    pub done: bool,
+    // next blob to be indexed
+    pub cursor: Option<BlobId>,
+    // totals of the blobs indexed so far, kept up to date on each change
+    #[serde(default)]
+    pub counters: BlobCounters,
+}
+
+#[derive(Clone, Serialize, Deserialize, Default)]
+pub struct BlobCounters {
+    pub num_blobs: u64,
+    pub num_bytes: u64,
+    pub num_pinned: u64,
+}
+
+impl BlobCounters {
+    pub fn add(
+        &mut self,
+        meta: &BlobMeta
+    ) {
+        self.num_blobs += 1;
+        self.num_bytes += meta.size as u64;
+        if meta.pinned {
+            self.num_pinned += 1;
+        }
+    }
+
+    pub fn sub(
+        &mut self,
+        meta: &BlobMeta
+    ) {
+        self.num_blobs = self.num_blobs.saturating_sub(1);
+        self.num_bytes = self.num_bytes.saturating_sub(meta.size as u64);
+        if meta.pinned {
+            self.num_pinned = self.num_pinned.saturating_sub(1);
+        }
+    }
+}
+
+impl Default for BlobIndexing {
+    fn default(
+    ) -> Self {
+        // existing stores must be indexed
+        Self {
+            done: false,
+            cursor: None,
+            counters: BlobCounters::default(),
+        }
+    }
+}
+
+impl BlobIndexing {
+    pub fn is_indexed(
+        &self,
+        id: BlobId
+    ) -> bool {
+        self.done || self.cursor.map_or(false, |cursor| id < cursor)
+    }
+}
+
+#[derive(CandidType, Deserialize, Default)]
+pub struct BlobStoreStats {
+    pub num_blobs: u64,
+    pub num_bytes: u64,
+    pub num_unreferenced: u64,
+    pub num_unreferenced_bytes: u64,
+    pub num_pinned: u64,
+    pub num_legacy: u64,
+    pub num_temp_blobs: u64,
+    pub num_derived_blobs: u64,
+}
+
+pub fn blob_id_from_hash(
+    hash: &BlobHash
+) -> BlobId {
+    // the high bit keeps content ids apart from temp blob ids
+    let mut buf = [0u8; 16];
+    buf.copy_from_slice(&hash[..16]);
+    u128::from_be_bytes(buf) | (1 << 127)
+}
+
 impl Storable for Blob {
     fn to_bytes(
         &self
@@ -31,4 +225,20 @@ impl Storable for Blob {
         max_size: MAX_VALUE_SIZE,
         is_fixed_size: false,
     };
+}
+
+impl Storable for BlobMeta {
+    fn to_bytes(
+        &self
+    ) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(
+        bytes: std::borrow::Cow<[u8]>
+    ) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
 }
\ No newline at end of file
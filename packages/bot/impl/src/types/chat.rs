@@ -0,0 +1,85 @@
+use std::borrow::Cow;
+use candid::{CandidType, Decode, Encode, Principal};
+use ic_stable_structures::{storable::Bound, Storable};
+use serde::Deserialize;
+
+pub use bot_api::chat::ChatKey;
+
+/// A chat where the bot was installed and can act autonomously, through its API key
+#[derive(Clone, CandidType, Deserialize)]
+pub struct RegisteredChat {
+    pub api_key: String,
+    pub registered_at: u64,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ChatInfo {
+    pub key: ChatKey,
+    pub registered_at: u64,
+}
+
+#[derive(Clone, Copy, CandidType, Deserialize, PartialEq, Debug, Default, clap::ValueEnum)]
+pub enum NsfwStrictness {
+    Off,
+    #[default]
+    Normal,
+    Strict,
+}
+
+/// Per chat settings, changed by the chat admins through '/meme settings'
+#[derive(Clone, CandidType, Deserialize, Default)]
+pub struct ChatSettings {
+    pub default_mood: Option<String>,
+    pub default_topic: Option<String>,
+    // language of the captions suggested by the AI
+    pub language: Option<String>,
+    // locale of the bot responses, e.g.: "es" (default: the language of each user)
+    pub locale: Option<String>,
+    // subcommands disabled, e.g.: "wallet" (all wallet subcommands) or "nft mint"
+    pub disabled_commands: Vec<String>,
+    pub nsfw: NsfwStrictness,
+    pub updated_by: Option<Principal>,
+    pub updated_at: u64,
+}
+
+impl ChatSettings {
+    pub fn is_command_disabled(
+        &self,
+        name: &str
+    ) -> bool {
+        self.disabled_commands.iter()
+            .any(|disabled| name == disabled || name.starts_with(&format!("{} ", disabled)))
+    }
+}
+
+impl Storable for RegisteredChat {
+    fn to_bytes(
+        &self
+    ) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(
+        bytes: std::borrow::Cow<[u8]>
+    ) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ChatSettings {
+    fn to_bytes(
+        &self
+    ) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(
+        bytes: std::borrow::Cow<[u8]>
+    ) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
\ No newline at end of file
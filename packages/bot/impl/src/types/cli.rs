@@ -1,5 +1,8 @@
 use clap::{Parser, Subcommand};
-use super::nft::NftId;
+use super::{
+    chat::NsfwStrictness, nft::NftId, rate_limit::RateLimitClass, 
+    role::Role, token::TokenKind
+};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -43,29 +46,124 @@ pub enum Commands {
         #[arg(help = "Optional alphanumeric id of the meme returned by '/meme gen or suggest' (default: last meme generated)")]
         id: Option<String>
     },
+    #[command(about = "Report a posted meme or a MEME NFT to the moderators")]
+    Report {
+        #[arg(help = "Alphanumeric id of the meme or numeric id of the MEME NFT")]
+        id: String,
+        #[arg(help = "Optional reason of the report (use single-quotes, e.g. 'a reason', to preserve white-spaces)")]
+        reason: Option<String>,
+    },
     #[command(subcommand, about = "Sub-commands of the **Mementor Wallet**")]
     Wallet (Wallet),
     #[command(subcommand, about = "Sub-commands of the **MEME NFT collection**")]
     Nft (Nft),
+    #[command(subcommand, about = "Schedule recurring posts in this chat")]
+    Schedule (Schedule),
+    #[command(subcommand, about = "Change how Mementor behaves in this chat (admins only)")]
+    Settings (Settings),
+    #[command(subcommand, about = "Sub-commands of the bot administrators")]
+    Admin (Admin),
+}
+
+impl Commands {
+    /// Commands that render images or call the LLM are the heavy ones
+    pub fn rate_limit_class(
+        &self
+    ) -> RateLimitClass {
+        match self {
+            Commands::Search { .. } |
+            Commands::Gen { .. } |
+            Commands::Suggest { .. } |
+            Commands::Nft(Nft::Tokens { .. }) => RateLimitClass::Heavy,
+            _ => RateLimitClass::Cheap,
+        }
+    }
+
+    /// Name of the (sub)command, as used by the metrics
+    pub fn name(
+        &self
+    ) -> &'static str {
+        match self {
+            Commands::Search { .. } => "search",
+            Commands::Gen { .. } => "gen",
+            Commands::Suggest { .. } => "suggest",
+            Commands::Post { .. } => "post",
+            Commands::Report { .. } => "report",
+            Commands::Wallet(cmd) => match cmd {
+                Wallet::Balance { .. } => "wallet balance",
+                Wallet::Address { .. } => "wallet address",
+                Wallet::Withdraw { .. } => "wallet withdraw",
+                Wallet::Logs { .. } => "wallet logs",
+            },
+            Commands::Nft(cmd) => match cmd {
+                Nft::Mint { .. } => "nft mint",
+                Nft::Balance { .. } => "nft balance",
+                Nft::Tokens { .. } => "nft tokens",
+                Nft::Transfer { .. } => "nft transfer",
+                Nft::Logs { .. } => "nft logs",
+                Nft::Status => "nft status",
+            },
+            Commands::Schedule(cmd) => match cmd {
+                Schedule::MemeOfTheDay { .. } => "schedule meme-of-the-day",
+                Schedule::Leaderboard { .. } => "schedule leaderboard",
+                Schedule::Reminder { .. } => "schedule reminder",
+                Schedule::List => "schedule list",
+                Schedule::Cancel { .. } => "schedule cancel",
+            },
+            Commands::Settings(cmd) => match cmd {
+                Settings::Show => "settings show",
+                Settings::Mood { .. } => "settings mood",
+                Settings::Topic { .. } => "settings topic",
+                Settings::Language { .. } => "settings language",
+                Settings::Locale { .. } => "settings locale",
+                Settings::Disable { .. } => "settings disable",
+                Settings::Enable { .. } => "settings enable",
+                Settings::Nsfw { .. } => "settings nsfw",
+                Settings::Reset => "settings reset",
+            },
+            Commands::Admin(cmd) => match cmd {
+                Admin::Roles(Roles::List) => "admin roles list",
+                Admin::Roles(Roles::Grant { .. }) => "admin roles grant",
+                Admin::Roles(Roles::Revoke { .. }) => "admin roles revoke",
+                Admin::Nft(AdminNft::Config { .. }) => "admin nft config",
+                Admin::Nft(AdminNft::Pause { .. }) => "admin nft pause",
+                Admin::Nft(AdminNft::Resume) => "admin nft resume",
+                Admin::Nft(AdminNft::Logs { .. }) => "admin nft logs",
+                Admin::Nft(AdminNft::Preview { .. }) => "admin nft preview",
+            },
+        }
+    }
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Wallet {
-    #[command(about = "Display your ICP balance in the Mementor Wallet")]
-    Balance,
-    #[command(about = "Display your ICP address in the Mementor Wallet")]
-    Address,
-    #[command(about = "Withdraw ICP from your account in the Mementor Wallet")]
+    #[command(about = "Display your balance in the Mementor Wallet")]
+    Balance {
+        #[arg(long, value_enum, ignore_case = true, default_value_t = TokenKind::Icp, help = "Token (default: ICP)")]
+        token: TokenKind,
+    },
+    #[command(about = "Display your address in the Mementor Wallet")]
+    Address {
+        #[arg(long, value_enum, ignore_case = true, default_value_t = TokenKind::Icp, help = "Token (default: ICP)")]
+        token: TokenKind,
+    },
+    #[command(about = "Withdraw tokens from your account in the Mementor Wallet")]
     Withdraw {
-        #[arg(help = "Amount to withdraw in decimal format (eg: 1.25)")]
-        amount: f32,
-        #[arg(help = "Optional destination account address in hex format (default: your OC wallet)")]
+        #[arg(help = "Amount to withdraw in decimal format (eg: 1.25), omitted with --all")]
+        amount: Option<String>,
+        #[arg(help = "Optional destination: an account address in hex format (ICP only), an ICRC-1 account or an OC username (default: your OC wallet)")]
         to: Option<String>,
+        #[arg(long, help = "Withdraw the whole balance, less the transfer fee")]
+        all: bool,
+        #[arg(long, value_enum, ignore_case = true, default_value_t = TokenKind::Icp, help = "Token (default: ICP)")]
+        token: TokenKind,
     },
-    #[command(about = "Display logs of ICP transactions")]
+    #[command(about = "Display the wallet history: deposits, withdraws, minting payments and refunds")]
     Logs {
         #[arg(default_value_t = 1, help = "Optional page number (default: 1)")]
         page: usize,
+        #[arg(long, value_enum, ignore_case = true, default_value_t = TokenKind::Icp, help = "Token (default: ICP)")]
+        token: TokenKind,
     },
 }
 
@@ -103,3 +201,164 @@ pub enum Nft {
     #[command(about = "Print the status of the MEME NFT collection")]
     Status,
 }
+
+#[derive(Subcommand, Debug)]
+pub enum Schedule {
+    #[command(about = "Post a daily meme, generated from a trending template with AI captions")]
+    MemeOfTheDay {
+        #[arg(long, default_value_t = 12, help = "Hour of the day, in UTC (default: 12)")]
+        hour: u8,
+        #[arg(long, help = "The story mood to use (default funny)")]
+        mood: Option<String>,
+        #[arg(long, help = "The story topic (default crypto)")]
+        topic: Option<String>,
+    },
+    #[command(about = "Post the weekly leaderboard of MEME NFT minters")]
+    Leaderboard {
+        #[arg(long, default_value_t = 0, help = "Day of the week, from 0 (Monday) to 6 (Sunday) (default: 0)")]
+        weekday: u8,
+        #[arg(long, default_value_t = 12, help = "Hour of the day, in UTC (default: 12)")]
+        hour: u8,
+    },
+    #[command(about = "Post a recurring reminder")]
+    Reminder {
+        #[arg(help = "Text of the reminder (use single-quotes, e.g. 'some text', to preserve white-spaces)")]
+        text: String,
+        #[arg(long, default_value_t = 24, help = "Interval, in hours (default: 24)")]
+        every: u32,
+    },
+    #[command(about = "List the jobs scheduled in this chat")]
+    List,
+    #[command(about = "Cancel a scheduled job")]
+    Cancel {
+        #[arg(help = "Job id, as returned by '/meme schedule list'")]
+        id: u64,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Admin {
+    #[command(subcommand, about = "Manage the roles of the bot administrators")]
+    Roles (Roles),
+    #[command(subcommand, about = "Manage the MEME NFT collection (treasurers only)")]
+    Nft (AdminNft),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Roles {
+    #[command(about = "List the administrators and their roles")]
+    List,
+    #[command(about = "Grant roles to a principal (owners only)")]
+    Grant {
+        #[arg(help = "Principal of the administrator")]
+        principal: String,
+        #[arg(value_enum, required = true, help = "Roles to grant")]
+        roles: Vec<Role>,
+    },
+    #[command(about = "Revoke roles from a principal (owners only)")]
+    Revoke {
+        #[arg(help = "Principal of the administrator")]
+        principal: String,
+        #[arg(value_enum, required = true, help = "Roles to revoke")]
+        roles: Vec<Role>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AdminNft {
+    #[command(about = "Display or change the minting config (only the options passed are changed)")]
+    Config {
+        #[arg(long, help = "Min number of reactions required to mint")]
+        min_reactions: Option<u32>,
+        #[arg(long, help = "Max number of reactions required to mint")]
+        max_reactions: Option<u32>,
+        #[arg(long, help = "Min minting cost, in ICP (eg: 0.5)")]
+        min_cost: Option<String>,
+        #[arg(long, help = "Max minting cost, in ICP (eg: 2.5)")]
+        max_cost: Option<String>,
+        #[arg(long, help = "Min number of members of the chat")]
+        min_chat_members: Option<u32>,
+        #[arg(long, help = "Min age, in days, of the accounts whose reactions are counted")]
+        min_user_age: Option<u64>,
+        #[arg(long, help = "Team fee, in percent of the minting cost (eg: 12.5)")]
+        team_fee: Option<String>,
+    },
+    #[command(about = "Pause the minting")]
+    Pause {
+        #[arg(help = "Reason shown to the users (use single-quotes, e.g. 'a reason', to preserve white-spaces)")]
+        reason: String,
+    },
+    #[command(about = "Resume the minting")]
+    Resume,
+    #[command(about = "Display the log of config changes")]
+    Logs {
+        #[arg(default_value_t = 1, help = "Optional page number (default: 1)")]
+        page: usize,
+    },
+    #[command(about = "Preview the minting cost and the min reactions at supply levels")]
+    Preview {
+        #[arg(help = "Supply levels, separated by white-space (default: the current supply)")]
+        supplies: Vec<u32>,
+    },
+}
+
+/// Names of all the (sub)commands that can be disabled in a chat
+pub const COMMAND_NAMES: &[&str] = &[
+    "search", "gen", "suggest", "post", "report", 
+    "wallet balance", "wallet address", "wallet withdraw", "wallet logs", 
+    "nft mint", "nft balance", "nft tokens", "nft transfer", "nft logs", "nft status", 
+    "schedule meme-of-the-day", "schedule leaderboard", "schedule reminder", "schedule list", "schedule cancel",
+    "admin roles list", "admin roles grant", "admin roles revoke",
+    "admin nft config", "admin nft pause", "admin nft resume", "admin nft logs", "admin nft preview",
+];
+
+/// Returns true if name is a (sub)command or a group of them, e.g.: "wallet"
+pub fn is_command_name(
+    name: &str
+) -> bool {
+    COMMAND_NAMES.iter()
+        .any(|n| *n == name || n.starts_with(&format!("{} ", name)))
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Settings {
+    #[command(about = "Display the settings of this chat")]
+    Show,
+    #[command(about = "Set the default story mood used by '/meme suggest'")]
+    Mood {
+        #[arg(help = "The story mood, e.g.: happy, sad, funny, etc (leave empty to restore the default)")]
+        mood: Option<String>,
+    },
+    #[command(about = "Set the default story topic used by '/meme suggest'")]
+    Topic {
+        #[arg(help = "The story topic, e.g.: crypto, cats, robots, etc (leave empty to restore the default)")]
+        topic: Option<String>,
+    },
+    #[command(about = "Set the language of the captions suggested by the AI")]
+    Language {
+        #[arg(help = "The language, e.g.: Spanish, Portuguese, etc (leave empty to restore the default)")]
+        language: Option<String>,
+    },
+    #[command(about = "Set the language of the bot responses")]
+    Locale {
+        #[arg(help = "Language code, e.g.: en, es or pt (leave empty to use the language of each user)")]
+        locale: Option<String>,
+    },
+    #[command(about = "Disable a command, or a group of commands, in this chat")]
+    Disable {
+        #[arg(required = true, help = "Name of the command, e.g.: 'wallet' (all wallet commands) or 'nft mint'")]
+        command: Vec<String>,
+    },
+    #[command(about = "Re-enable a command, or a group of commands, in this chat")]
+    Enable {
+        #[arg(required = true, help = "Name of the command, as used by '/meme settings disable'")]
+        command: Vec<String>,
+    },
+    #[command(about = "Set how strict the NSFW filter is in this chat")]
+    Nsfw {
+        #[arg(value_enum, help = "Strictness level")]
+        level: NsfwStrictness,
+    },
+    #[command(about = "Restore the default settings")]
+    Reset,
+}
\ No newline at end of file
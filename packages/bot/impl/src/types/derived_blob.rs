@@ -0,0 +1,95 @@
+use std::borrow::Cow;
+use candid::{CandidType, Decode, Encode};
+use ic_stable_structures::{storable::Bound, Storable};
+use image::ImageFormat;
+use serde::Deserialize;
+use super::blob::{Blob, BlobHash, BlobId};
+
+// oldest derived blobs are evicted once this limit is reached
+pub const MAX_DERIVED_BLOBS: u64 = 2_000;
+// only these widths/heights are served, so the cache can't be flooded with arbitrary sizes
+pub const TRANSFORM_SIZES: &[u32] = &[128, 256, 512];
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum TransformFormat {
+    Jpeg = 1,
+    Png = 2,
+    WebP = 3,
+}
+
+impl TransformFormat {
+    pub fn from_param(
+        value: &str
+    ) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "jpg" | "jpeg" => Some(Self::Jpeg),
+            "png" => Some(Self::Png),
+            "webp" => Some(Self::WebP),
+            _ => None,
+        }
+    }
+
+    pub fn from_mime_type(
+        mime_type: &str
+    ) -> Self {
+        match mime_type {
+            "image/png" => Self::Png,
+            "image/webp" => Self::WebP,
+            _ => Self::Jpeg,
+        }
+    }
+
+    pub fn image_format(
+        &self
+    ) -> ImageFormat {
+        match self {
+            Self::Jpeg => ImageFormat::Jpeg,
+            Self::Png => ImageFormat::Png,
+            Self::WebP => ImageFormat::WebP,
+        }
+    }
+}
+
+/// Resizing (keeping the aspect ratio) and/or re-encoding of an image blob
+#[derive(Clone, Copy)]
+pub struct ImageTransform {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub format: Option<TransformFormat>,
+}
+
+impl ImageTransform {
+    /// Packs the transform in a single integer, used to key the cache
+    pub fn key(
+        &self
+    ) -> u64 {
+        ((self.width.unwrap_or(0) as u64) << 40) | 
+            ((self.height.unwrap_or(0) as u64) << 16) | 
+            self.format.map(|f| f as u64).unwrap_or(0)
+    }
+}
+
+pub type DerivedBlobKey = (BlobId, u64);
+
+#[derive(CandidType, Deserialize)]
+pub struct DerivedBlob {
+    pub seq: u64,
+    pub blob: Blob,
+    pub hash: BlobHash,
+}
+
+impl Storable for DerivedBlob {
+    fn to_bytes(
+        &self
+    ) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(
+        bytes: std::borrow::Cow<[u8]>
+    ) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
\ No newline at end of file
@@ -0,0 +1,50 @@
+use candid::{define_function, CandidType};
+use serde::Deserialize;
+use super::blob::BlobId;
+
+#[derive(Clone, CandidType, Deserialize)]
+pub struct StreamingToken {
+    pub blob_id: BlobId,
+    pub index: u32,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct StreamingCallbackHttpResponse {
+    pub body: Vec<u8>,
+    pub token: Option<StreamingToken>,
+}
+
+define_function!(pub StreamingCallback : (StreamingToken) -> (StreamingCallbackHttpResponse) query);
+
+#[derive(CandidType, Deserialize)]
+pub enum StreamingStrategy {
+    Callback {
+        callback: StreamingCallback,
+        token: StreamingToken,
+    },
+}
+
+/// Same as the one from ic_http_certification, but able to stream bodies 
+/// larger than a single message
+#[derive(CandidType, Deserialize)]
+pub struct HttpResponse {
+    pub status_code: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    pub upgrade: Option<bool>,
+    pub streaming_strategy: Option<StreamingStrategy>,
+}
+
+impl From<ic_http_certification::HttpResponse> for HttpResponse {
+    fn from(
+        res: ic_http_certification::HttpResponse
+    ) -> Self {
+        Self {
+            status_code: res.status_code,
+            headers: res.headers,
+            body: res.body,
+            upgrade: res.upgrade,
+            streaming_strategy: None,
+        }
+    }
+}
\ No newline at end of file
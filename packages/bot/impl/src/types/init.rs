@@ -12,7 +12,8 @@ pub struct InitOrUpgradeArgs {
     pub memes_json_gz: Vec<u8>, 
     pub index_tar_gz: Vec<u8>,
     pub meme_nft: NftCollection,
-    pub meme_nft_config: NftCollectionConfig,
+    // None keeps the config currently in use
+    pub meme_nft_config: Option<NftCollectionConfig>,
     pub meme_coin: Coin,
     pub meme_coin_config: CoinConfig,
 }
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Default, Serialize, Deserialize)]
@@ -31,4 +32,45 @@ pub struct MemeTpl {
     pub usage: String,
     #[serde(rename="k")]
     pub keywords: Vec<String>,
+}
+
+/// Number of memes generated per template, over the current and the previous week
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct MemeTplUsage {
+    pub week: u64,
+    pub current: BTreeMap<u32, u32>,
+    pub previous: BTreeMap<u32, u32>,
+}
+
+impl MemeTplUsage {
+    pub fn record(
+        &mut self,
+        tpl_id: u32,
+        week: u64
+    ) {
+        if week != self.week {
+            self.previous = if week == self.week + 1 {
+                std::mem::take(&mut self.current)
+            }
+            else {
+                BTreeMap::new()
+            };
+            self.current.clear();
+            self.week = week;
+        }
+
+        *self.current.entry(tpl_id).or_default() += 1;
+    }
+
+    /// The template most used this week, or in the previous one
+    pub fn trending(
+        &self
+    ) -> Option<u32> {
+        [&self.current, &self.previous].into_iter()
+            .find_map(|usage| {
+                usage.iter()
+                    .max_by_key(|(_, count)| **count)
+                    .map(|(id, _)| *id)
+            })
+    }
 }
\ No newline at end of file
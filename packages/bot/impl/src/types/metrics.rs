@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+
+// upper bounds of the LLM latency histogram buckets, in milliseconds
+pub const LLM_LATENCY_BUCKETS: [u64; 7] = [500, 1_000, 2_000, 5_000, 10_000, 30_000, 60_000];
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct CommandMetrics {
+    pub count: u64,
+    pub errors: u64,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct LlmMetrics {
+    pub calls: u64,
+    pub failures: u64,
+    pub latency_sum: u64, // ms
+    // one counter per bucket, plus the +Inf one
+    pub latency_buckets: Vec<u64>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Metrics {
+    pub commands: BTreeMap<String, CommandMetrics>,
+    pub llm: LlmMetrics,
+    pub mints: u64,
+    pub mint_failures: u64,
+    pub mint_amount: u64, // e8s
+    pub refunds: u64,
+    pub refund_failures: u64,
+    pub refund_amount: u64, // e8s
+    pub rate_limited: u64,
+    pub refusals: u64,
+    pub webhook_rejections: u64,
+}
+
+impl Metrics {
+    pub fn record_command(
+        &mut self,
+        name: &str,
+        failed: bool
+    ) {
+        let cmd = self.commands.entry(name.to_string()).or_default();
+        cmd.count += 1;
+        if failed {
+            cmd.errors += 1;
+        }
+    }
+
+    pub fn record_llm_call(
+        &mut self,
+        latency: u64,
+        failed: bool
+    ) {
+        let llm = &mut self.llm;
+        llm.calls += 1;
+        if failed {
+            llm.failures += 1;
+        }
+        llm.latency_sum += latency;
+
+        if llm.latency_buckets.len() != LLM_LATENCY_BUCKETS.len() + 1 {
+            llm.latency_buckets = vec![0; LLM_LATENCY_BUCKETS.len() + 1];
+        }
+        let index = LLM_LATENCY_BUCKETS.iter()
+            .position(|&le| latency <= le)
+            .unwrap_or(LLM_LATENCY_BUCKETS.len());
+        llm.latency_buckets[index] += 1;
+    }
+
+    pub fn record_mint(
+        &mut self,
+        amount: u64
+    ) {
+        self.mints += 1;
+        self.mint_amount += amount;
+    }
+
+    pub fn record_mint_failure(
+        &mut self
+    ) {
+        self.mint_failures += 1;
+    }
+
+    pub fn record_refund(
+        &mut self,
+        amount: u64,
+        failed: bool
+    ) {
+        if failed {
+            self.refund_failures += 1;
+        }
+        else {
+            self.refunds += 1;
+            self.refund_amount += amount;
+        }
+    }
+
+    pub fn record_rate_limited(
+        &mut self
+    ) {
+        self.rate_limited += 1;
+    }
+
+    pub fn record_refusal(
+        &mut self
+    ) {
+        self.refusals += 1;
+    }
+
+    pub fn record_webhook_rejection(
+        &mut self
+    ) {
+        self.webhook_rejections += 1;
+    }
+}
\ No newline at end of file
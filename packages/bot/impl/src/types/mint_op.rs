@@ -0,0 +1,114 @@
+use std::borrow::Cow;
+use candid::{CandidType, Decode, Encode, Principal};
+use ic_stable_structures::{storable::Bound, Storable};
+use serde::Deserialize;
+use super::{blob::BlobId, meme::MemeId, nft::NftId};
+
+pub type MintOpId = u64;
+
+/// Steps of a mint. Each transfer is journaled before it's made, so it can be retried or 
+/// resolved by an administrator if the canister traps in the middle
+#[derive(Clone, PartialEq, CandidType, Deserialize, Debug)]
+pub enum MintOpStep {
+    // the minting cost is being debited from the user
+    Debiting,
+    // the NFT is being minted
+    Minting,
+    // the mint failed and the cost must be returned to the user
+    Refunding,
+    // the NFT was minted and the team fee must be transferred
+    PayingFee,
+    Completed,
+    Refunded,
+    // the debit failed, so there's nothing to undo
+    Failed,
+    // closed by an administrator
+    Resolved {
+        by: Principal,
+        note: String,
+    },
+}
+
+impl MintOpStep {
+    pub fn is_final(
+        &self
+    ) -> bool {
+        match self {
+            MintOpStep::Completed |
+            MintOpStep::Refunded |
+            MintOpStep::Failed |
+            MintOpStep::Resolved { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Steps retried by the timer
+    pub fn is_retryable(
+        &self
+    ) -> bool {
+        match self {
+            MintOpStep::Refunding |
+            MintOpStep::PayingFee => true,
+            _ => false,
+        }
+    }
+}
+
+/// Where the minting cost was taken from, so it's returned to the same place
+#[derive(Clone, Copy, PartialEq, CandidType, Deserialize, Debug)]
+pub enum MintPayment {
+    // the user's deposit subaccount in the Mementor wallet
+    Deposit,
+    // the user's own wallet, through an ICRC-2 approval
+    Approval,
+}
+
+#[derive(Clone, CandidType, Deserialize)]
+pub struct MintOp {
+    pub id: MintOpId,
+    pub user: Principal,
+    pub meme_id: MemeId,
+    pub blob_id: BlobId,
+    pub cost: u64,
+    pub team_fee: u64,
+    pub team_account: Principal,
+    // not set by the operations created before the approvals, that were all deposits
+    pub payment: Option<MintPayment>,
+    // block of the debit of the minting cost, once it's confirmed by the ledger
+    pub debit_block: Option<u64>,
+    pub token_id: Option<NftId>,
+    pub step: MintOpStep,
+    // creation time of the transfer of the current step, reused by the retries
+    pub transfer_at: u64,
+    pub num_attempts: u32,
+    pub last_error: Option<String>,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+#[derive(Clone, CandidType, Deserialize, Debug)]
+pub enum MintOpResolution {
+    // retries the refund or the fee transfer now
+    Retry,
+    // returns the cost to the user of a mint stuck while minting, or while debiting if the 
+    // debit was confirmed. Check the NFT wasn't minted first
+    Refund,
+    // closes the operation, with a note of what was done
+    Close(String),
+}
+
+impl Storable for MintOp {
+    fn to_bytes(
+        &self
+    ) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(
+        bytes: std::borrow::Cow<[u8]>
+    ) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
\ No newline at end of file
@@ -10,4 +10,18 @@ pub mod nft;
 pub mod nft_col;
 pub mod coin;
 pub mod meme;
-pub mod event;
\ No newline at end of file
+pub mod event;
+pub mod temp_blob;
+pub mod upload;
+pub mod http;
+pub mod derived_blob;
+pub mod metrics;
+pub mod chat;
+pub mod webhook;
+pub mod schedule;
+pub mod rate_limit;
+pub mod moderation;
+pub mod report;
+pub mod role;
+pub mod mint_op;
+pub mod token;
\ No newline at end of file
@@ -0,0 +1,148 @@
+use std::{borrow::Cow, collections::BTreeSet};
+use candid::{CandidType, Decode, Encode, Principal};
+use ic_stable_structures::{storable::Bound, Storable};
+use serde::{Deserialize, Serialize};
+use super::chat::ChatKey;
+
+pub const MAX_REFUSALS: u64 = 1_000;
+pub const MAX_DENYLIST_TERMS: usize = 5_000;
+
+pub type RefusalId = u64;
+
+/// Moderation settings, managed by the administrator
+#[derive(Clone, Default, CandidType, Serialize, Deserialize)]
+pub struct ModerationConfig {
+    // normalized terms, see normalize()
+    pub denylist: BTreeSet<String>,
+    // ask the LLM to classify the captions in chats with strict NSFW filtering
+    pub llm_check: bool,
+}
+
+#[derive(Clone, Copy, CandidType, Deserialize, Debug)]
+pub enum ModerationStage {
+    Render,
+    Mint,
+}
+
+#[derive(Clone, CandidType, Deserialize)]
+pub struct Refusal {
+    pub id: RefusalId,
+    pub stage: ModerationStage,
+    pub user: Option<Principal>,
+    pub chat: Option<ChatKey>,
+    pub texts: Vec<String>,
+    pub reason: String,
+    pub created_at: u64,
+}
+
+/// Lowercases the text, undoes the most common leetspeak substitutions and
+/// splits it into words, dropping everything that is not alphanumeric
+pub fn normalize(
+    text: &str
+) -> Vec<String> {
+    text.to_lowercase()
+        .chars()
+        .map(|c| match c {
+            '0' => 'o',
+            '1' | '!' | '|' => 'i',
+            '3' => 'e',
+            '4' | '@' => 'a',
+            '5' | '$' => 's',
+            '7' | '+' => 't',
+            '8' => 'b',
+            '9' => 'g',
+            c if c.is_alphanumeric() => c,
+            _ => ' ',
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .map(|word| word.to_string())
+        .collect()
+}
+
+/// Run-length encoding of a word, so stretched words ("fuuun") can be compared
+fn runs(
+    word: &str
+) -> Vec<(char, usize)> {
+    let mut runs: Vec<(char, usize)> = vec![];
+    for c in word.chars() {
+        match runs.last_mut() {
+            Some((last, count)) if *last == c => *count += 1,
+            _ => runs.push((c, 1)),
+        }
+    }
+    runs
+}
+
+/// True if word is the term, possibly with some of its letters repeated
+fn is_stretched(
+    word: &str,
+    term: &str
+) -> bool {
+    let (word, term) = (runs(word), runs(term));
+    word.len() == term.len() &&
+        word.iter().zip(term.iter())
+            .all(|((wc, wn), (tc, tn))| wc == tc && wn >= tn)
+}
+
+/// True if some consecutive words, once joined, are the term (possibly stretched), 
+/// e.g.: "k i l l" or "ki ll". Words merely containing the term ("skill") don't match
+fn is_split(
+    words: &[String],
+    term: &str
+) -> bool {
+    let max_runs = runs(term).len();
+
+    for start in 0..words.len() {
+        let mut joined = String::new();
+        for word in &words[start..] {
+            joined.push_str(word);
+            if is_stretched(&joined, term) {
+                return true;
+            }
+            if runs(&joined).len() > max_runs {
+                break;
+            }
+        }
+    }
+
+    false
+}
+
+/// Returns the first denied term found in the words. Loose matching compares whole words,
+/// while strict matching also finds terms split by separators
+pub fn find_denied<'a>(
+    words: &[String],
+    denylist: &'a BTreeSet<String>,
+    strict: bool
+) -> Option<&'a String> {
+    denylist.iter().find(|term| {
+        let term_words = term.split(' ')
+            .map(|word| word.to_string())
+            .collect::<Vec<_>>();
+
+        let found = term_words.len() <= words.len() &&
+            words.windows(term_words.len())
+                .any(|window| window.iter().zip(term_words.iter())
+                    .all(|(word, term)| is_stretched(word, term))
+                );
+
+        found || (strict && is_split(words, &term_words.concat()))
+    })
+}
+
+impl Storable for Refusal {
+    fn to_bytes(
+        &self
+    ) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(
+        bytes: std::borrow::Cow<[u8]>
+    ) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
\ No newline at end of file
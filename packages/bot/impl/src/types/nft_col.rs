@@ -1,7 +1,18 @@
-use std::u32;
-use candid::{CandidType, Principal};
+use std::{borrow::Cow, u32};
+use candid::{CandidType, Decode, Encode, Principal};
+use ic_stable_structures::{storable::Bound, Storable};
 use serde::{Deserialize, Serialize};
 
+// the team fee is a fraction of 1e8
+pub const TEAM_FEE_P_DENOMINATOR: u64 = 1_00000000;
+// decimal places of the team fee, when given in percent
+pub const TEAM_FEE_P_DECIMALS: u8 = 6;
+// the value of the curve tiers is a fraction of 1e4
+pub const CURVE_TIER_P_DENOMINATOR: u32 = 10000;
+// larger rates make exp() overflow to inf or NaN, so the curve would evaluate to the min
+pub const MAX_EXPONENTIAL_RATE: f64 = 50.0;
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
 #[derive(Clone, CandidType, Serialize, Deserialize, Default)]
 pub enum NftCollectionType {
     #[default]
@@ -27,7 +38,7 @@ impl Default for NftCollection {
     }
 }
 
-#[derive(Clone, Serialize, Deserialize, CandidType, Default)]
+#[derive(Clone, Serialize, Deserialize, CandidType, Default, PartialEq)]
 pub struct NftCollectionConfig {
     pub min_num_reactions: u32,
     pub max_num_reactions: u32,
@@ -36,4 +47,281 @@ pub struct NftCollectionConfig {
     pub min_chat_members: u32,
     pub min_user_creation_interval: u64,
     pub team_fee_p: u64,
+    // the tanh curve is used if not set
+    #[serde(default)]
+    pub cost_curve: Option<MintCurve>,
+    #[serde(default)]
+    pub reactions_curve: Option<MintCurve>,
+}
+
+impl NftCollectionConfig {
+    pub fn validate(
+        &self
+    ) -> Result<(), String> {
+        if self.min_num_reactions > self.max_num_reactions {
+            return Err("The min number of reactions can't be greater than the max".to_string());
+        }
+
+        if self.min_minting_cost > self.max_minting_cost {
+            return Err("The min minting cost can't be greater than the max".to_string());
+        }
+
+        if self.team_fee_p > TEAM_FEE_P_DENOMINATOR {
+            return Err("The team fee can't be greater than 100%".to_string());
+        }
+
+        if let Some(curve) = &self.cost_curve {
+            curve.validate()?;
+        }
+
+        if let Some(curve) = &self.reactions_curve {
+            curve.validate()?;
+        }
+
+        Ok(())
+    }
 }
+
+#[derive(Clone, PartialEq, Serialize, Deserialize, CandidType, Debug)]
+pub struct CurveTier {
+    pub from_supply: u32,
+    pub value_p: u32,
+}
+
+/// How the minting cost and the min number of reactions go from the min to the max value
+#[derive(Clone, PartialEq, Serialize, Deserialize, CandidType, Debug, Default)]
+pub enum MintCurve {
+    // min + tanh(supply / max_supply) * (max - min)
+    #[default]
+    Tanh,
+    Linear,
+    // the greater the rate, the steeper the curve near the max supply
+    Exponential {
+        rate: f64,
+    },
+    // the value of the last tier reached by the supply, as a fraction of (max - min)
+    Stepped(Vec<CurveTier>),
+    // goes from the max to the min value over the duration, restarting at each mint.
+    // Timestamps are in nanoseconds
+    DutchAuction {
+        starts_at: u64,
+        duration: u64,
+    },
+}
+
+impl MintCurve {
+    pub fn validate(
+        &self
+    ) -> Result<(), String> {
+        match self {
+            MintCurve::Tanh |
+            MintCurve::Linear => {
+            },
+            MintCurve::Exponential { rate } => {
+                if !rate.is_finite() || *rate <= 0.0 || *rate > MAX_EXPONENTIAL_RATE {
+                    return Err(format!("The rate of the exponential curve must be greater than zero and up to {}", MAX_EXPONENTIAL_RATE));
+                }
+            },
+            MintCurve::Stepped(tiers) => {
+                if tiers.is_empty() {
+                    return Err("The stepped curve needs at least one tier".to_string());
+                }
+
+                if tiers.windows(2).any(|w| w[0].from_supply >= w[1].from_supply) {
+                    return Err("The tiers must be sorted by supply, without duplicates".to_string());
+                }
+
+                if tiers.iter().any(|tier| tier.value_p > CURVE_TIER_P_DENOMINATOR) {
+                    return Err("The tier value can't be greater than 100%".to_string());
+                }
+            },
+            MintCurve::DutchAuction { duration, .. } => {
+                if *duration == 0 {
+                    return Err("The duration of the dutch auction must be greater than zero".to_string());
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Returns how far, from 0.0 to 1.0, the value is between the min and the max
+    pub fn eval(
+        &self,
+        supply: u32,
+        max_supply: u32,
+        timestamp: u64,
+        last_minted_at: Option<u64>
+    ) -> f64 {
+        let x = supply as f64 / max_supply.max(1) as f64;
+
+        let y = match self {
+            MintCurve::Tanh => {
+                x.tanh()
+            },
+            MintCurve::Linear => {
+                x
+            },
+            MintCurve::Exponential { rate } => {
+                (rate * x).exp_m1() / rate.exp_m1()
+            },
+            MintCurve::Stepped(tiers) => {
+                tiers.iter()
+                    .take_while(|tier| tier.from_supply <= supply)
+                    .last()
+                    .map_or(0.0, |tier| tier.value_p as f64 / CURVE_TIER_P_DENOMINATOR as f64)
+            },
+            MintCurve::DutchAuction { starts_at, duration } => {
+                let started_at = last_minted_at.map_or(*starts_at, |at| at.max(*starts_at));
+                let elapsed = timestamp.saturating_sub(started_at);
+                1.0 - elapsed as f64 / *duration as f64
+            },
+        };
+
+        y.clamp(0.0, 1.0)
+    }
+
+    pub fn describe(
+        &self
+    ) -> String {
+        match self {
+            MintCurve::Tanh => 
+                "tanh".to_string(),
+            MintCurve::Linear => 
+                "linear".to_string(),
+            MintCurve::Exponential { rate } => 
+                format!("exponential (rate: {})", rate),
+            MintCurve::Stepped(tiers) => 
+                format!("stepped ({} tiers)", tiers.len()),
+            MintCurve::DutchAuction { duration, .. } => 
+                format!("dutch auction ({}s)", duration / NANOS_PER_SEC),
+        }
+    }
+}
+
+/// The minting cost and the min number of reactions at a supply level
+#[derive(CandidType)]
+pub struct MintingPreview {
+    pub supply: u32,
+    pub minting_cost: u64,
+    pub min_num_reactions: u32,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, CandidType, Debug)]
+pub enum MintPhaseKind {
+    // only the principals in the allowlist can mint
+    Allowlist,
+    Public,
+}
+
+impl MintPhaseKind {
+    pub fn name(
+        &self
+    ) -> &'static str {
+        match self {
+            MintPhaseKind::Allowlist => "allowlist",
+            MintPhaseKind::Public => "public",
+        }
+    }
+}
+
+/// A window of time when minting is open. Timestamps are in nanoseconds
+#[derive(Clone, PartialEq, Serialize, Deserialize, CandidType)]
+pub struct MintPhase {
+    pub name: String,
+    pub kind: MintPhaseKind,
+    pub starts_at: u64,
+    pub ends_at: Option<u64>,
+    pub max_mints_per_user: Option<u32>,
+}
+
+impl MintPhase {
+    pub fn is_open_at(
+        &self,
+        timestamp: u64
+    ) -> bool {
+        timestamp >= self.starts_at && 
+            self.ends_at.map_or(true, |ends_at| timestamp < ends_at)
+    }
+
+    /// Checks the phases, that must be sorted by start and can't overlap
+    pub fn validate_all(
+        phases: &[MintPhase]
+    ) -> Result<(), String> {
+        for (i, phase) in phases.iter().enumerate() {
+            if phase.name.trim().is_empty() {
+                return Err("The phase name is required".to_string());
+            }
+
+            if phase.ends_at.map_or(false, |ends_at| ends_at <= phase.starts_at) {
+                return Err(format!("The phase {} must end after it starts", phase.name));
+            }
+
+            if phase.max_mints_per_user == Some(0) {
+                return Err(format!("The max mints per user of the phase {} can't be zero", phase.name));
+            }
+
+            if let Some(next) = phases.get(i + 1) {
+                if phase.ends_at.map_or(true, |ends_at| ends_at > next.starts_at) {
+                    return Err(format!("The phases {} and {} overlap", phase.name, next.name));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Set by the administrators to stop the minting, e.g.: during an incident
+#[derive(Clone, Serialize, Deserialize, CandidType)]
+pub struct MintingPause {
+    pub reason: String,
+    pub paused_by: Principal,
+    pub paused_at: u64,
+}
+
+#[derive(CandidType)]
+pub struct NftConfigStatus {
+    pub config: NftCollectionConfig,
+    pub minting_pause: Option<MintingPause>,
+    pub phases: Vec<MintPhase>,
+    pub num_allowlisted: u64,
+}
+
+pub type NftConfigChangeId = u64;
+
+#[derive(Clone, CandidType, Deserialize)]
+pub enum NftConfigChangeKind {
+    Config(NftCollectionConfig),
+    Paused(String),
+    Resumed,
+    Phases(Vec<MintPhase>),
+    Allowlist {
+        num_added: u32,
+        num_removed: u32,
+    },
+}
+
+#[derive(Clone, CandidType, Deserialize)]
+pub struct NftConfigChange {
+    pub id: NftConfigChangeId,
+    pub kind: NftConfigChangeKind,
+    pub changed_by: Principal,
+    pub changed_at: u64,
+}
+
+impl Storable for NftConfigChange {
+    fn to_bytes(
+        &self
+    ) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(
+        bytes: std::borrow::Cow<[u8]>
+    ) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
\ No newline at end of file
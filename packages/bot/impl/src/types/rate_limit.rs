@@ -0,0 +1,148 @@
+use std::collections::BTreeSet;
+use candid::{CandidType, Principal};
+use serde::{Deserialize, Serialize};
+use super::chat::ChatKey;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+// once there are this many buckets, the full ones are dropped
+pub const MAX_RATE_LIMIT_BUCKETS: usize = 10_000;
+// image transforms are requested anonymously through the gateways, so they share a single bucket
+pub const TRANSFORM_BUCKET: BucketConfig = BucketConfig { capacity: 10, refill_secs: 6 };
+
+/// Cheap commands only read or write small records. Heavy ones render images or call the LLM
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum RateLimitClass {
+    Cheap,
+    Heavy,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum RateLimitSubject {
+    User(Principal),
+    Chat(ChatKey),
+}
+
+/// A bucket holds up to capacity tokens and gets a new one every refill_secs
+#[derive(Clone, CandidType, Serialize, Deserialize)]
+pub struct BucketConfig {
+    pub capacity: u32,
+    pub refill_secs: u32,
+}
+
+#[derive(Clone, CandidType, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    pub user_cheap: BucketConfig,
+    pub user_heavy: BucketConfig,
+    pub chat_cheap: BucketConfig,
+    pub chat_heavy: BucketConfig,
+}
+
+impl Default for RateLimitConfig {
+    fn default(
+    ) -> Self {
+        Self {
+            user_cheap: BucketConfig { capacity: 20, refill_secs: 3 },
+            user_heavy: BucketConfig { capacity: 5, refill_secs: 30 },
+            chat_cheap: BucketConfig { capacity: 60, refill_secs: 1 },
+            chat_heavy: BucketConfig { capacity: 20, refill_secs: 10 },
+        }
+    }
+}
+
+impl RateLimitConfig {
+    pub fn bucket(
+        &self,
+        subject: &RateLimitSubject,
+        class: RateLimitClass
+    ) -> &BucketConfig {
+        match (subject, class) {
+            (RateLimitSubject::User(_), RateLimitClass::Cheap) => &self.user_cheap,
+            (RateLimitSubject::User(_), RateLimitClass::Heavy) => &self.user_heavy,
+            (RateLimitSubject::Chat(_), RateLimitClass::Cheap) => &self.chat_cheap,
+            (RateLimitSubject::Chat(_), RateLimitClass::Heavy) => &self.chat_heavy,
+        }
+    }
+
+    pub fn validate(
+        &self
+    ) -> Result<(), String> {
+        for bucket in [&self.user_cheap, &self.user_heavy, &self.chat_cheap, &self.chat_heavy] {
+            if bucket.capacity == 0 || bucket.refill_secs == 0 {
+                return Err("Capacity and refill interval must be greater than zero".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Rate limits, as tuned by the administrator
+#[derive(Clone, Default, CandidType, Serialize, Deserialize)]
+pub struct RateLimits {
+    pub config: RateLimitConfig,
+    pub exempt_users: BTreeSet<Principal>,
+    pub exempt_chats: BTreeSet<ChatKey>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum RateLimitExemption {
+    User(Principal),
+    Chat(ChatKey),
+}
+
+#[derive(Clone)]
+pub struct TokenBucket {
+    // in thousandths of a token, so partial refills aren't lost
+    millis: u64,
+    updated_at: u64,
+}
+
+impl TokenBucket {
+    pub fn new(
+        config: &BucketConfig,
+        now: u64
+    ) -> Self {
+        Self {
+            millis: config.capacity as u64 * 1_000,
+            updated_at: now,
+        }
+    }
+
+    pub fn refill(
+        &mut self,
+        config: &BucketConfig,
+        now: u64
+    ) {
+        let elapsed = now.saturating_sub(self.updated_at);
+        let refilled = elapsed / (NANOS_PER_SEC / 1_000) / config.refill_secs as u64;
+        self.millis = (self.millis + refilled).min(config.capacity as u64 * 1_000);
+        self.updated_at = now;
+    }
+
+    pub fn is_full(
+        &self,
+        config: &BucketConfig
+    ) -> bool {
+        self.millis >= config.capacity as u64 * 1_000
+    }
+
+    /// Returns the number of seconds to wait until a token is available, or zero if there's one
+    pub fn wait_secs(
+        &self,
+        config: &BucketConfig
+    ) -> u64 {
+        if self.millis >= 1_000 {
+            0
+        }
+        else {
+            ((1_000 - self.millis) * config.refill_secs as u64).div_ceil(1_000)
+        }
+    }
+
+    pub fn take(
+        &mut self
+    ) {
+        self.millis = self.millis.saturating_sub(1_000);
+    }
+}
\ No newline at end of file
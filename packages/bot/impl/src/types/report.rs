@@ -0,0 +1,146 @@
+use std::{borrow::Cow, collections::BTreeSet};
+use candid::{CandidType, Decode, Encode, Principal};
+use ic_stable_structures::{storable::Bound, Storable};
+use serde::{Deserialize, Serialize};
+use super::{blob::BlobId, chat::ChatKey, meme::MemeId, nft::NftId};
+
+// only the first reporters are kept, the others are just counted
+pub const MAX_REPORTERS: usize = 20;
+pub const MAX_REPORT_REASON_LEN: usize = 256;
+
+pub type AuditEntryId = u64;
+
+/// Where a meme was posted, so it can be found when reported
+#[derive(Clone, CandidType, Deserialize)]
+pub struct PostedMeme {
+    pub blob_id: BlobId,
+    pub user: Principal,
+    pub chat: ChatKey,
+}
+
+#[derive(Clone, CandidType, Deserialize, Debug)]
+pub enum ReportTarget {
+    Meme(MemeId),
+    Nft(NftId),
+}
+
+#[derive(Clone, Copy, PartialEq, CandidType, Deserialize, Debug)]
+pub enum ReportStatus {
+    Open,
+    Dismissed,
+    TakenDown,
+}
+
+#[derive(Clone, CandidType, Deserialize)]
+pub struct Reporter {
+    pub user: Principal,
+    pub chat: ChatKey,
+    pub reason: Option<String>,
+    pub reported_at: u64,
+}
+
+/// Reports are grouped by the blob reported, so the same image is reviewed only once
+#[derive(Clone, CandidType, Deserialize)]
+pub struct Report {
+    pub blob_id: BlobId,
+    pub target: ReportTarget,
+    pub owner: Option<Principal>,
+    pub reporters: Vec<Reporter>,
+    pub num_reports: u32,
+    pub status: ReportStatus,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+/// Blobs hidden and users banned from minting by the administrator
+#[derive(Clone, Default, CandidType, Serialize, Deserialize)]
+pub struct Takedowns {
+    pub hidden_blobs: BTreeSet<BlobId>,
+    pub mint_bans: BTreeSet<Principal>,
+}
+
+#[derive(Clone, CandidType, Deserialize, Debug)]
+pub enum TakedownAction {
+    // serves a 404 from /blobs and a placeholder from /assets/nfts
+    HideBlob(BlobId),
+    UnhideBlob(BlobId),
+    BanMinter(Principal),
+    UnbanMinter(Principal),
+    DismissReport(BlobId),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ReportError {
+    NotFound,
+    AlreadyReported,
+    AlreadyTakenDown,
+}
+
+impl ReportError {
+    /// Id of the message in the i18n catalog
+    pub fn message_id(
+        &self
+    ) -> &'static str {
+        match self {
+            ReportError::NotFound => "report.not_found",
+            ReportError::AlreadyReported => "report.already_reported",
+            ReportError::AlreadyTakenDown => "report.taken_down",
+        }
+    }
+}
+
+#[derive(Clone, CandidType, Deserialize)]
+pub struct AuditEntry {
+    pub id: AuditEntryId,
+    pub actor: Principal,
+    pub action: TakedownAction,
+    pub created_at: u64,
+}
+
+impl Storable for PostedMeme {
+    fn to_bytes(
+        &self
+    ) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(
+        bytes: std::borrow::Cow<[u8]>
+    ) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for Report {
+    fn to_bytes(
+        &self
+    ) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(
+        bytes: std::borrow::Cow<[u8]>
+    ) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for AuditEntry {
+    fn to_bytes(
+        &self
+    ) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(
+        bytes: std::borrow::Cow<[u8]>
+    ) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
\ No newline at end of file
@@ -0,0 +1,80 @@
+use std::{borrow::Cow, collections::BTreeSet, fmt};
+use candid::{CandidType, Decode, Encode, Principal};
+use ic_stable_structures::{storable::Bound, Storable};
+use serde::Deserialize;
+
+/// Roles of the bot administrators. The owner can do everything, including managing the roles
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, CandidType, Deserialize, Debug, clap::ValueEnum)]
+pub enum Role {
+    Owner,
+    // uploads meme templates and assets
+    Curator,
+    // reviews reports, takes content down and tunes the filters and rate limits
+    Moderator,
+    // manages the NFT collection and the funds
+    Treasurer,
+}
+
+impl Role {
+    pub fn name(
+        &self
+    ) -> &'static str {
+        match self {
+            Role::Owner => "owner",
+            Role::Curator => "curator",
+            Role::Moderator => "moderator",
+            Role::Treasurer => "treasurer",
+        }
+    }
+}
+
+#[derive(Clone, CandidType, Deserialize)]
+pub struct RoleGrant {
+    pub roles: BTreeSet<Role>,
+    pub updated_by: Principal,
+    pub updated_at: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RoleError {
+    LastOwner,
+}
+
+impl RoleError {
+    /// Id of the message in the i18n catalog
+    pub fn message_id(
+        &self
+    ) -> &'static str {
+        match self {
+            RoleError::LastOwner => "roles.last_owner",
+        }
+    }
+}
+
+impl fmt::Display for RoleError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>
+    ) -> fmt::Result {
+        match self {
+            RoleError::LastOwner =>
+                write!(f, "The last owner can't be revoked"),
+        }
+    }
+}
+
+impl Storable for RoleGrant {
+    fn to_bytes(
+        &self
+    ) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(
+        bytes: std::borrow::Cow<[u8]>
+    ) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
\ No newline at end of file
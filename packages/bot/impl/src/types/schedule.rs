@@ -0,0 +1,89 @@
+use std::borrow::Cow;
+use candid::{CandidType, Decode, Encode, Principal};
+use ic_stable_structures::{storable::Bound, Storable};
+use serde::Deserialize;
+use super::chat::ChatKey;
+
+pub const SCHEDULER_INTERVAL: u64 = 60; // every minute, in seconds
+pub const MAX_JOBS_PER_CHAT: usize = 10;
+pub const HOUR: u64 = 60 * 60 * 1_000_000_000; // in nanoseconds
+pub const DAY: u64 = 24 * HOUR;
+pub const WEEK: u64 = 7 * DAY;
+
+pub type JobId = u64;
+
+#[derive(Clone, CandidType, Deserialize, Debug)]
+pub enum JobKind {
+    MemeOfTheDay {
+        mood: String,
+        topic: String,
+    },
+    WeeklyLeaderboard,
+    Reminder {
+        text: String,
+    },
+}
+
+#[derive(Clone, CandidType, Deserialize)]
+pub struct Job {
+    pub chat: ChatKey,
+    pub kind: JobKind,
+    pub interval: u64,
+    pub next_run_at: u64,
+    pub created_by: Principal,
+    pub created_at: u64,
+    pub last_run_at: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+impl JobKind {
+    pub fn describe(
+        &self
+    ) -> String {
+        match self {
+            JobKind::MemeOfTheDay { mood, topic } => 
+                format!("meme of the day ({} about {})", mood, topic),
+            JobKind::WeeklyLeaderboard => 
+                "weekly leaderboard".to_string(),
+            JobKind::Reminder { text } => 
+                format!("reminder \"{}\"", text),
+        }
+    }
+}
+
+/// Next time (in nanoseconds) matching the hour and, optionally, the weekday (0 = Monday)
+pub fn next_occurrence(
+    now: u64,
+    hour: u8,
+    weekday: Option<u8>
+) -> u64 {
+    let today = now - now % DAY;
+    let mut next = today + hour as u64 * HOUR;
+    if next <= now {
+        next += DAY;
+    }
+
+    if let Some(weekday) = weekday {
+        // the unix epoch was on a Thursday
+        let current = ((next / DAY) + 3) % 7;
+        next += ((weekday as u64 + 7 - current) % 7) * DAY;
+    }
+
+    next
+}
+
+impl Storable for Job {
+    fn to_bytes(
+        &self
+    ) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(
+        bytes: std::borrow::Cow<[u8]>
+    ) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
\ No newline at end of file
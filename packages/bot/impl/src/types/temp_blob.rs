@@ -0,0 +1,102 @@
+use std::{borrow::Cow, fmt};
+use candid::{CandidType, Decode, Encode, Principal};
+use ic_stable_structures::{storable::Bound, Storable};
+use serde::Deserialize;
+use super::blob::{Blob, BlobHash, BlobId};
+
+pub const MAX_TEMP_BLOBS: u32 = 10_000;
+pub const TEMP_BLOB_TTL: u64 = 24 * 60 * 60 * 1_000_000_000; // 1 day, in nanoseconds
+// temp blob ids are (generation << 32 | slot), so any id below this limit is a temp one
+pub const TEMP_BLOB_ID_LIMIT: BlobId = 1 << 64;
+
+#[derive(CandidType, Deserialize)]
+pub struct TempBlob {
+    pub owner: Principal,
+    pub created_at: u64,
+    pub ttl: u64,
+    pub generation: u32,
+    pub blob: Blob,
+    // hash of the data, so the response can be certified
+    pub hash: BlobHash,
+}
+
+impl TempBlob {
+    pub fn id(
+        &self,
+        slot: u32
+    ) -> BlobId {
+        Self::make_id(slot, self.generation)
+    }
+
+    pub fn make_id(
+        slot: u32,
+        generation: u32
+    ) -> BlobId {
+        ((generation as BlobId) << 32) | slot as BlobId
+    }
+
+    pub fn split_id(
+        id: BlobId
+    ) -> (u32, u32) {
+        ((id & 0xffff_ffff) as u32, ((id >> 32) & 0xffff_ffff) as u32)
+    }
+
+    pub fn is_expired(
+        &self,
+        now: u64
+    ) -> bool {
+        now >= self.created_at.saturating_add(self.ttl)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TempBlobError {
+    NotFound,
+    Expired,
+    NotOwner,
+}
+
+impl TempBlobError {
+    /// Id of the message in the i18n catalog
+    pub fn message_id(
+        &self
+    ) -> &'static str {
+        match self {
+            TempBlobError::NotFound => "meme.not_found",
+            TempBlobError::Expired => "meme.expired",
+            TempBlobError::NotOwner => "meme.not_owner",
+        }
+    }
+}
+
+impl fmt::Display for TempBlobError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>
+    ) -> fmt::Result {
+        match self {
+            TempBlobError::NotFound =>
+                write!(f, "Meme image not found. Please regenerate it with '/meme gen' or '/meme suggest'"),
+            TempBlobError::Expired =>
+                write!(f, "This meme has expired. Please regenerate it with '/meme gen' or '/meme suggest'"),
+            TempBlobError::NotOwner =>
+                write!(f, "This meme was not generated by you"),
+        }
+    }
+}
+
+impl Storable for TempBlob {
+    fn to_bytes(
+        &self
+    ) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(
+        bytes: std::borrow::Cow<[u8]>
+    ) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
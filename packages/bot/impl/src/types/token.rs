@@ -0,0 +1,124 @@
+use candid::{CandidType, Principal};
+use icrc_ledger_types::icrc::generic_metadata_value::MetadataValue;
+use serde::Deserialize;
+use crate::utils::nat::nat_to_u128;
+
+pub const ICP_DECIMALS: u8 = 8;
+
+/// Tokens held in the Mementor wallet
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, CandidType, Deserialize, Debug, clap::ValueEnum)]
+pub enum TokenKind {
+    #[value(name = "ICP")]
+    Icp,
+    #[value(name = "MEME")]
+    Meme,
+}
+
+#[derive(Clone, CandidType, Deserialize, Debug)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub canister_id: Principal,
+    pub symbol: String,
+    pub decimals: u8,
+    pub fee: u128,
+}
+
+impl Token {
+    /// Builds the token from the ledger's icrc1_metadata
+    pub fn from_metadata(
+        kind: TokenKind,
+        canister_id: Principal,
+        metadata: Vec<(String, MetadataValue)>
+    ) -> Result<Self, String> {
+        let mut symbol = None;
+        let mut decimals = None;
+        let mut fee = None;
+
+        for (key, value) in metadata {
+            match (key.as_str(), value) {
+                ("icrc1:symbol", MetadataValue::Text(value)) => symbol = Some(value),
+                ("icrc1:decimals", MetadataValue::Nat(value)) => decimals = Some(nat_to_u128(value) as u8),
+                ("icrc1:fee", MetadataValue::Nat(value)) => fee = Some(nat_to_u128(value)),
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            kind,
+            canister_id,
+            symbol: symbol.ok_or_else(|| format!("Symbol of the token {} not found", canister_id))?,
+            decimals: decimals.ok_or_else(|| format!("Decimals of the token {} not found", canister_id))?,
+            fee: fee.ok_or_else(|| format!("Fee of the token {} not found", canister_id))?,
+        })
+    }
+
+    /// Formats an amount, in the token's smallest unit, with its decimals
+    pub fn format(
+        &self,
+        amount: u128
+    ) -> String {
+        format_decimal(amount, self.decimals)
+    }
+
+    /// Parses an amount in decimal format (eg: 1.25) to the token's smallest unit, without 
+    /// rounding. Fails on anything else, or on more decimal places than the token has
+    pub fn parse_amount(
+        &self,
+        amount: &str
+    ) -> Option<u128> {
+        parse_decimal(amount, self.decimals)
+    }
+}
+
+/// Formats an integer scaled by 10^decimals in decimal format, without rounding
+pub fn format_decimal(
+    amount: u128,
+    decimals: u8
+) -> String {
+    if decimals == 0 {
+        return amount.to_string();
+    }
+
+    let unit = 10u128.pow(decimals as u32);
+    format!(
+        "{}.{:0>width$}", 
+        amount / unit, 
+        amount % unit,
+        width = decimals as usize
+    )
+}
+
+/// Parses a number in decimal format (eg: 1.25) to an integer scaled by 10^decimals, without 
+/// rounding. Fails on anything else, or on more decimal places than allowed
+pub fn parse_decimal(
+    amount: &str,
+    decimals: u8
+) -> Option<u128> {
+    let (int, frac) = match amount.trim().split_once('.') {
+        Some((int, frac)) => (int, frac),
+        None => (amount.trim(), ""),
+    };
+
+    if int.is_empty() && frac.is_empty() || 
+        !int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit()) ||
+        frac.len() > decimals as usize {
+        return None;
+    }
+
+    let unit = 10u128.checked_pow(decimals as u32)?;
+    let int = if int.is_empty() {
+        0
+    }
+    else {
+        int.parse::<u128>().ok()?
+    };
+    let frac = format!("{:0<width$}", frac, width = decimals as usize);
+    let frac = if frac.is_empty() {
+        0
+    }
+    else {
+        frac.parse::<u128>().ok()?
+    };
+
+    int.checked_mul(unit)?.checked_add(frac)
+}
\ No newline at end of file
@@ -0,0 +1,56 @@
+use std::borrow::Cow;
+use bot_api::upload::UploadTarget;
+use candid::{CandidType, Decode, Encode, Principal};
+use ic_stable_structures::{storable::Bound, Storable};
+use serde::Deserialize;
+
+pub const MAX_UPLOAD_SIZE: u64 = 32 * 1024 * 1024; // 32MB
+pub const MAX_CHUNK_SIZE: u32 = 1024 * 1024; // 1MB, must fit in a single ingress message
+pub const UPLOAD_TTL: u64 = 24 * 60 * 60 * 1_000_000_000; // 1 day, in nanoseconds
+
+#[derive(Clone, CandidType, Deserialize)]
+pub struct UploadSession {
+    pub owner: Principal,
+    pub target: UploadTarget,
+    pub mime_type: String,
+    pub total_size: u64,
+    pub chunk_size: u32,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+impl UploadSession {
+    pub fn num_chunks(
+        &self
+    ) -> u32 {
+        self.total_size.div_ceil(self.chunk_size as u64) as u32
+    }
+
+    pub fn expected_chunk_size(
+        &self,
+        index: u32
+    ) -> u32 {
+        if index + 1 < self.num_chunks() {
+            self.chunk_size
+        }
+        else {
+            (self.total_size - (index as u64 * self.chunk_size as u64)) as u32
+        }
+    }
+}
+
+impl Storable for UploadSession {
+    fn to_bytes(
+        &self
+    ) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(
+        bytes: std::borrow::Cow<[u8]>
+    ) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
\ No newline at end of file
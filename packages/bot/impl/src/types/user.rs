@@ -5,7 +5,7 @@ use ic_stable_structures::{storable::Bound, Storable};
 use icrc_ledger_types::icrc1::account::Account;
 use oc_bots_sdk::types::{MessageId, MessageIndex, TimestampMillis};
 use serde::Deserialize;
-use super::{blob::BlobId, meme::MemeId, nft::NftId};
+use super::{blob::BlobId, meme::MemeId, nft::NftId, token::TokenKind};
 
 pub type UserId = Principal;
 
@@ -13,16 +13,20 @@ pub type UserId = Principal;
 pub struct UserMeme {
     pub meme_id: MemeId,
     pub tmp_blob_id: BlobId,
+    // kept so they can be moderated again before minting (None on legacy memes)
+    pub captions: Option<Vec<String>>,
 }
 
 impl UserMeme {
     pub fn new(
         meme_id: MemeId,
-        tmp_blob_id: BlobId
+        tmp_blob_id: BlobId,
+        captions: Vec<String>
     ) -> Self {
         Self { 
             meme_id,
             tmp_blob_id,
+            captions: Some(captions),
         }
     }
 }
@@ -39,6 +43,7 @@ pub struct UserPost {
     pub meme_id: MemeId,
     pub message_index: MessageIndex,
     pub message_id: MessageId,
+    pub captions: Option<Vec<String>>,
 }
 
 #[derive(Default, CandidType, Deserialize)]
@@ -60,6 +65,21 @@ pub struct UserMints {
     pub last: Option<MemeId>,
 }
 
+/// Why the balance of a user changed
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub enum TxReason {
+    // an incoming transfer found by the ledger scan, from an account id in hex format
+    Deposit {
+        from: String,
+    },
+    MintPayment {
+        meme_id: MemeId,
+    },
+    MintRefund {
+        meme_id: MemeId,
+    },
+}
+
 #[derive(Clone, Debug, CandidType, Deserialize)]
 pub enum UserTransaction {
     NftTransfer {
@@ -74,6 +94,43 @@ pub enum UserTransaction {
         block_num: u64,
         timestamp: u32,
     },
+    // withdraws of the ICRC-1 tokens
+    TokenWithdraw {
+        token: TokenKind,
+        amount: u128,
+        to: Account,
+        block_num: u128,
+        timestamp: u32,
+    },
+    Debit {
+        token: TokenKind,
+        amount: u128,
+        reason: TxReason,
+        block_num: u128,
+        timestamp: u32,
+    },
+    Credit {
+        token: TokenKind,
+        amount: u128,
+        reason: TxReason,
+        block_num: u128,
+        timestamp: u32,
+    },
+}
+
+impl UserTransaction {
+    /// Token moved by the transaction, None for the NFT transfers
+    pub fn token(
+        &self
+    ) -> Option<TokenKind> {
+        match self {
+            Self::NftTransfer { .. } => None,
+            Self::IcpWithdraw { .. } => Some(TokenKind::Icp),
+            Self::TokenWithdraw { token, .. } |
+            Self::Debit { token, .. } |
+            Self::Credit { token, .. } => Some(*token),
+        }
+    }
 }
 
 #[derive(Default, CandidType, Deserialize)]
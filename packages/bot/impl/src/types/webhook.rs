@@ -0,0 +1,120 @@
+use std::borrow::Cow;
+use candid::{CandidType, Decode, Encode};
+use ic_stable_structures::{storable::Bound, Storable};
+use serde::Deserialize;
+use super::chat::ChatKey;
+
+pub use bot_api::webhook::WebhookName;
+
+pub const MAX_WEBHOOK_DELIVERIES: u64 = 1_000;
+// signed requests older (or newer) than this are rejected, to prevent replays
+pub const WEBHOOK_MAX_CLOCK_SKEW: u64 = 5 * 60; // 5 minutes, in seconds
+pub const WEBHOOK_SECRET_SIZE: usize = 32;
+
+pub type WebhookDeliveryId = u64;
+
+#[derive(Clone, CandidType, Deserialize)]
+pub struct Webhook {
+    pub secret: Vec<u8>,
+    pub chats: Vec<ChatKey>,
+    pub enabled: bool,
+    pub created_at: u64,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct WebhookInfo {
+    pub name: WebhookName,
+    pub chats: Vec<ChatKey>,
+    pub enabled: bool,
+    pub created_at: u64,
+}
+
+/// Actions that can be triggered by a webhook, sent as a JSON body, e.g.:
+/// {"action": "post_text", "chat": "main", "text": "Contest winner: ..."}
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum WebhookAction {
+    PostText {
+        chat: ChatKey,
+        text: String,
+    },
+    PostMeme {
+        chat: ChatKey,
+        // random when not provided
+        template_id: Option<u32>,
+        // suggested by the LLM when not provided
+        captions: Option<Vec<String>>,
+        mood: Option<String>,
+        topic: Option<String>,
+        caption: Option<String>,
+    },
+}
+
+impl WebhookAction {
+    pub fn name(
+        &self
+    ) -> &'static str {
+        match self {
+            WebhookAction::PostText { .. } => "post_text",
+            WebhookAction::PostMeme { .. } => "post_meme",
+        }
+    }
+
+    pub fn chat(
+        &self
+    ) -> &ChatKey {
+        match self {
+            WebhookAction::PostText { chat, .. } => chat,
+            WebhookAction::PostMeme { chat, .. } => chat,
+        }
+    }
+}
+
+#[derive(Clone, CandidType, Deserialize)]
+pub enum WebhookDeliveryStatus {
+    Delivered,
+    Rejected(String),
+    Failed(String),
+}
+
+#[derive(Clone, CandidType, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: WebhookDeliveryId,
+    pub webhook: WebhookName,
+    pub received_at: u64,
+    pub action: Option<String>,
+    pub chat: Option<ChatKey>,
+    pub status: WebhookDeliveryStatus,
+}
+
+impl Storable for Webhook {
+    fn to_bytes(
+        &self
+    ) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(
+        bytes: std::borrow::Cow<[u8]>
+    ) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for WebhookDelivery {
+    fn to_bytes(
+        &self
+    ) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(
+        bytes: std::borrow::Cow<[u8]>
+    ) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
\ No newline at end of file
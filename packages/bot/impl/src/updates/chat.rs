@@ -0,0 +1,43 @@
+use bot_api::chat::{ChatKey, ChatRegisterRequest};
+use oc_bots_sdk::types::BotApiKeyContext;
+use oc_bots_sdk_canister::env;
+use crate::{
+    storage::{chat::ChatStorage, role::RoleStorage}, 
+    types::{chat::RegisteredChat, role::Role}
+};
+
+#[ic_cdk::update]
+fn register_chat(
+    args: ChatRegisterRequest
+) -> Result<(), String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Owner) {
+        return Err("Not authorized".to_string());
+    }
+
+    if args.key.trim().is_empty() {
+        return Err("Invalid chat key".to_string());
+    }
+
+    BotApiKeyContext::parse_api_key(args.api_key.clone())
+        .map_err(|err| format!("Invalid API key: {:?}", err))?;
+
+    ChatStorage::save(args.key, RegisteredChat {
+        api_key: args.api_key,
+        registered_at: ic_cdk::api::time(),
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+fn unregister_chat(
+    key: ChatKey
+) -> Result<(), String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Owner) {
+        return Err("Not authorized".to_string());
+    }
+
+    ChatStorage::remove(&key)
+        .map(|_| ())
+        .ok_or_else(|| "Chat not found".to_string())
+}
\ No newline at end of file
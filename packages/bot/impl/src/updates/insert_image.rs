@@ -5,8 +5,10 @@ use bot_api::insert_image::{
     ImageInsertRequest, ImageInsertResponse
 };
 use crate::{
-    services::meme, state::{self}, storage::{
+    services::meme, 
+    storage::{
         image::ImageStorage, 
+        role::RoleStorage, 
         thumb::ThumbStorage
     }, 
     types::{
@@ -14,6 +16,7 @@ use crate::{
             IMAGE_FORMAT, IMAGE_HEIGHT, 
             IMAGE_MAX_SIZE, IMAGE_WIDTH
         }, 
+        role::Role, 
         thumb::THUMB_MAX_SIZE
     }, 
     utils::{
@@ -27,16 +30,24 @@ use crate::{
 pub async fn insert_image(
     args: ImageInsertRequest
 ) -> ImageInsertResponse {
-    if state::read(|s| s.administrator()) != env::caller() {
+    if !RoleStorage::has_role(&env::caller(), Role::Curator) {
         return ImageInsertResponse::NotAuthorized;
     }
 
-    match load_image(&args.data, &args.mime_type) {
+    save_template_image(args.id, &args.data, &args.mime_type)
+}
+
+pub(crate) fn save_template_image(
+    id: u32,
+    data: &Vec<u8>,
+    mime_type: &String
+) -> ImageInsertResponse {
+    let Some(meme) = meme::read(|s| s.load(&id).cloned()) else {
+        return ImageInsertResponse::TemplateNotFound;
+    };
+
+    match load_image(data, mime_type) {
         Some(img) => {
-            let meme = meme::read(|s| {
-                s.load(&args.id).cloned().unwrap()
-            });
-    
             match OutlinedFont::roboto(|font| gen_thumb(&img, &meme, font)) {
                 Ok(buf) => {
                     if buf.len() as u32 > THUMB_MAX_SIZE {
@@ -44,7 +55,7 @@ pub async fn insert_image(
                     }
 
                     ThumbStorage::save(
-                        args.id.clone(),
+                        id,
                         buf
                     );
                 },
@@ -60,7 +71,7 @@ pub async fn insert_image(
                     }
         
                     let size = buf.len();
-                    ImageStorage::save(args.id, buf);
+                    ImageStorage::save(id, buf);
                     size
                 },
                 Err(_) => {
@@ -82,7 +93,7 @@ fn load_image(
 ) -> Option<RgbaImage> {
     match image::load_from_memory_with_format(
         &data, 
-        image::ImageFormat::from_mime_type(mime_type).unwrap()
+        image::ImageFormat::from_mime_type(mime_type)?
     ) {
         Ok(img) => {
             Some(img.to_rgba8())
@@ -0,0 +1,22 @@
+use oc_bots_sdk_canister::env;
+use crate::{
+    services::mint_op::mint_op::MintOpService, 
+    storage::role::RoleStorage, 
+    types::{
+        mint_op::{MintOpId, MintOpResolution}, 
+        role::Role
+    }
+};
+
+#[ic_cdk::update]
+async fn resolve_mint_op(
+    id: MintOpId,
+    resolution: MintOpResolution
+) -> Result<(), String> {
+    let caller = env::caller();
+    if !RoleStorage::has_role(&caller, Role::Treasurer) {
+        return Err("Not authorized".to_string());
+    }
+
+    MintOpService::resolve(id, resolution, caller).await
+}
\ No newline at end of file
@@ -1 +1,10 @@
-pub mod insert_image;
\ No newline at end of file
+pub mod insert_image;
+pub mod upload;
+pub mod chat;
+pub mod webhook;
+pub mod rate_limit;
+pub mod moderation;
+pub mod report;
+pub mod role;
+pub mod nft_config;
+pub mod mint_op;
\ No newline at end of file
@@ -0,0 +1,58 @@
+use oc_bots_sdk_canister::env;
+use crate::{
+    state, 
+    storage::role::RoleStorage, 
+    types::{
+        moderation::{normalize, MAX_DENYLIST_TERMS}, 
+        role::Role
+    }
+};
+
+#[ic_cdk::update]
+fn update_denylist(
+    add: Vec<String>,
+    remove: Vec<String>
+) -> Result<(), String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Moderator) {
+        return Err("Not authorized".to_string());
+    }
+
+    // terms are stored normalized, the same way the captions are
+    let normalized = |terms: Vec<String>| terms.iter()
+        .map(|term| normalize(term).join(" "))
+        .filter(|term| !term.is_empty())
+        .collect::<Vec<_>>();
+
+    let (add, remove) = (normalized(add), normalized(remove));
+
+    state::mutate(|s| {
+        let denylist = &mut s.moderation_mut().denylist;
+        for term in &remove {
+            denylist.remove(term);
+        }
+
+        let new_terms = add.iter()
+            .filter(|term| !denylist.contains(*term))
+            .count();
+        if denylist.len() + new_terms > MAX_DENYLIST_TERMS {
+            return Err(format!("The denylist can't have more than {} terms", MAX_DENYLIST_TERMS));
+        }
+
+        denylist.extend(add);
+
+        Ok(())
+    })
+}
+
+#[ic_cdk::update]
+fn set_moderation_llm_check(
+    enabled: bool
+) -> Result<(), String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Moderator) {
+        return Err("Not authorized".to_string());
+    }
+
+    state::mutate(|s| s.moderation_mut().llm_check = enabled);
+
+    Ok(())
+}
\ No newline at end of file
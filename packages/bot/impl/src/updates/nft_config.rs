@@ -0,0 +1,61 @@
+use candid::Principal;
+use oc_bots_sdk_canister::env;
+use crate::{
+    services::nft::NftService, 
+    storage::role::RoleStorage, 
+    types::{
+        nft_col::{MintPhase, NftCollectionConfig}, 
+        role::Role
+    }
+};
+
+#[ic_cdk::update]
+fn set_nft_config(
+    config: NftCollectionConfig
+) -> Result<(), String> {
+    let caller = env::caller();
+    if !RoleStorage::has_role(&caller, Role::Treasurer) {
+        return Err("Not authorized".to_string());
+    }
+
+    NftService::change_config(config, caller)
+}
+
+#[ic_cdk::update]
+fn set_minting_paused(
+    reason: Option<String>
+) -> Result<(), String> {
+    let caller = env::caller();
+    if !RoleStorage::has_role(&caller, Role::Treasurer) {
+        return Err("Not authorized".to_string());
+    }
+
+    NftService::pause_minting(reason, caller)
+}
+
+#[ic_cdk::update]
+fn set_mint_phases(
+    phases: Vec<MintPhase>
+) -> Result<(), String> {
+    let caller = env::caller();
+    if !RoleStorage::has_role(&caller, Role::Treasurer) {
+        return Err("Not authorized".to_string());
+    }
+
+    NftService::change_phases(phases, caller)
+}
+
+#[ic_cdk::update]
+fn update_mint_allowlist(
+    to_add: Vec<Principal>,
+    to_remove: Vec<Principal>
+) -> Result<(), String> {
+    let caller = env::caller();
+    if !RoleStorage::has_role(&caller, Role::Treasurer) {
+        return Err("Not authorized".to_string());
+    }
+
+    NftService::update_allowlist(to_add, to_remove, caller);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+use oc_bots_sdk_canister::env;
+use crate::{
+    state, 
+    storage::role::RoleStorage, 
+    types::{
+        rate_limit::{RateLimitConfig, RateLimitExemption}, 
+        role::Role
+    }
+};
+
+#[ic_cdk::update]
+fn set_rate_limits(
+    config: RateLimitConfig
+) -> Result<(), String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Moderator) {
+        return Err("Not authorized".to_string());
+    }
+
+    config.validate()?;
+
+    state::mutate(|s| s.rate_limits_mut().config = config);
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+fn set_rate_limit_exemption(
+    exemption: RateLimitExemption,
+    exempt: bool
+) -> Result<(), String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Moderator) {
+        return Err("Not authorized".to_string());
+    }
+
+    state::mutate(|s| {
+        let limits = s.rate_limits_mut();
+        match exemption {
+            RateLimitExemption::User(user_id) => {
+                if exempt {
+                    limits.exempt_users.insert(user_id);
+                }
+                else {
+                    limits.exempt_users.remove(&user_id);
+                }
+            },
+            RateLimitExemption::Chat(key) => {
+                if exempt {
+                    limits.exempt_chats.insert(key);
+                }
+                else {
+                    limits.exempt_chats.remove(&key);
+                }
+            },
+        }
+    });
+
+    Ok(())
+}
\ No newline at end of file
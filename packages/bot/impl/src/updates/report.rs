@@ -0,0 +1,18 @@
+use oc_bots_sdk_canister::env;
+use crate::{
+    services::moderation::moderation::ModerationService, 
+    storage::role::RoleStorage, 
+    types::{report::TakedownAction, role::Role}
+};
+
+#[ic_cdk::update]
+fn takedown(
+    action: TakedownAction
+) -> Result<(), String> {
+    let caller = env::caller();
+    if !RoleStorage::has_role(&caller, Role::Moderator) {
+        return Err("Not authorized".to_string());
+    }
+
+    ModerationService::takedown(action, caller)
+}
\ No newline at end of file
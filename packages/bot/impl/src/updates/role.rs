@@ -0,0 +1,35 @@
+use candid::Principal;
+use oc_bots_sdk_canister::env;
+use crate::{
+    storage::role::RoleStorage, 
+    types::role::Role
+};
+
+#[ic_cdk::update]
+fn grant_roles(
+    principal: Principal,
+    roles: Vec<Role>
+) -> Result<(), String> {
+    let caller = env::caller();
+    if !RoleStorage::has_role(&caller, Role::Owner) {
+        return Err("Not authorized".to_string());
+    }
+
+    RoleStorage::grant(principal, &roles, caller);
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+fn revoke_roles(
+    principal: Principal,
+    roles: Vec<Role>
+) -> Result<(), String> {
+    let caller = env::caller();
+    if !RoleStorage::has_role(&caller, Role::Owner) {
+        return Err("Not authorized".to_string());
+    }
+
+    RoleStorage::revoke(principal, &roles, caller)
+        .map_err(|err| err.to_string())
+}
\ No newline at end of file
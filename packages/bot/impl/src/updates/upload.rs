@@ -0,0 +1,152 @@
+use bot_api::{
+    insert_image::ImageInsertResponse, 
+    upload::{
+        UploadChunkRequest, UploadChunkResponse, 
+        UploadCommitRequest, UploadCommitResponse, 
+        UploadStartRequest, UploadStartResponse, UploadTarget
+    }
+};
+use ic_cdk::api::time;
+use oc_bots_sdk_canister::env;
+use sha2::{Digest, Sha256};
+use crate::{
+    services::meme, 
+    storage::{
+        asset::AssetStorage, 
+        blob::BlobStorage, 
+        role::RoleStorage, 
+        upload::UploadStorage
+    }, 
+    types::{
+        blob::{BlobHash, BlobRefKind}, 
+        role::Role, 
+        upload::{UploadSession, MAX_CHUNK_SIZE, MAX_UPLOAD_SIZE, UPLOAD_TTL}
+    }
+};
+use super::insert_image::save_template_image;
+
+#[ic_cdk::update]
+fn start_upload(
+    args: UploadStartRequest
+) -> UploadStartResponse {
+    let caller = env::caller();
+    if !RoleStorage::has_role(&caller, Role::Curator) {
+        return UploadStartResponse::NotAuthorized;
+    }
+
+    if args.total_size == 0 || args.total_size > MAX_UPLOAD_SIZE {
+        return UploadStartResponse::InvalidSize;
+    }
+
+    if args.chunk_size == 0 || args.chunk_size > MAX_CHUNK_SIZE {
+        return UploadStartResponse::InvalidChunkSize;
+    }
+
+    if let UploadTarget::TemplateImage { id } = &args.target {
+        if meme::read(|s| s.load(id).is_none()) {
+            return UploadStartResponse::TemplateNotFound;
+        }
+    }
+
+    let now = time();
+    let upload_id = UploadStorage::start(UploadSession {
+        owner: caller,
+        target: args.target,
+        mime_type: args.mime_type,
+        total_size: args.total_size,
+        chunk_size: args.chunk_size,
+        created_at: now,
+        expires_at: now + UPLOAD_TTL,
+    });
+
+    UploadStartResponse::Success(upload_id)
+}
+
+#[ic_cdk::update]
+fn put_chunk(
+    args: UploadChunkRequest
+) -> UploadChunkResponse {
+    let Some(session) = UploadStorage::load(args.upload_id) else {
+        return UploadChunkResponse::NotFound;
+    };
+
+    if session.owner != env::caller() {
+        return UploadChunkResponse::NotAuthorized;
+    }
+
+    if args.index >= session.num_chunks() {
+        return UploadChunkResponse::InvalidIndex;
+    }
+
+    if args.data.len() as u32 != session.expected_chunk_size(args.index) {
+        return UploadChunkResponse::InvalidChunkSize;
+    }
+
+    // re-sending a chunk just overwrites it, so interrupted uploads can be resumed
+    UploadStorage::save_chunk(args.upload_id, args.index, args.data);
+
+    UploadChunkResponse::Success
+}
+
+#[ic_cdk::update]
+fn commit_upload(
+    args: UploadCommitRequest
+) -> UploadCommitResponse {
+    let Some(session) = UploadStorage::load(args.upload_id) else {
+        return UploadCommitResponse::NotFound;
+    };
+
+    if session.owner != env::caller() {
+        return UploadCommitResponse::NotAuthorized;
+    }
+
+    let missing = UploadStorage::missing_chunks(args.upload_id, &session);
+    if missing.len() > 0 {
+        return UploadCommitResponse::Incomplete(missing);
+    }
+
+    // the session is only removed once saved, so a failed commit can be retried
+    let chunks = UploadStorage::load_chunks(args.upload_id, &session);
+
+    let mut hasher = Sha256::new();
+    chunks.iter().for_each(|chunk| hasher.update(chunk));
+    let hash: BlobHash = hasher.finalize().into();
+
+    if args.sha256 != hash {
+        return UploadCommitResponse::ChecksumMismatch;
+    }
+
+    match &session.target {
+        UploadTarget::TemplateImage { id } => {
+            let data = chunks.concat();
+            match save_template_image(*id, &data, &session.mime_type) {
+                ImageInsertResponse::Success(size) => {
+                    UploadStorage::remove(args.upload_id, &session);
+                    UploadCommitResponse::Success { 
+                        blob_id: None, 
+                        size: size as _,
+                    }
+                },
+                err => {
+                    UploadCommitResponse::ImageInsertFailed(err)
+                }
+            }
+        },
+        UploadTarget::Asset { path } => {
+            let blob_id = BlobStorage::save_chunked(
+                session.mime_type.clone(), 
+                hash, 
+                session.total_size, 
+                chunks.into_iter(), 
+                BlobRefKind::Asset
+            );
+            AssetStorage::save(path.clone(), blob_id);
+            UploadStorage::remove(args.upload_id, &session);
+            
+            UploadCommitResponse::Success { 
+                blob_id: Some(blob_id), 
+                size: session.total_size,
+            }
+        },
+    }
+}
\ No newline at end of file
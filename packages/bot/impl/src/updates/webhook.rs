@@ -0,0 +1,71 @@
+use bot_api::webhook::{WebhookName, WebhookRegisterRequest, WebhookRegisterResponse};
+use oc_bots_sdk_canister::env;
+use crate::{
+    storage::{chat::ChatStorage, role::RoleStorage, webhook::WebhookStorage}, 
+    types::{
+        role::Role, 
+        webhook::{Webhook, WEBHOOK_SECRET_SIZE}
+    }, 
+    utils::rng
+};
+
+#[ic_cdk::update]
+fn register_webhook(
+    args: WebhookRegisterRequest
+) -> WebhookRegisterResponse {
+    if !RoleStorage::has_role(&env::caller(), Role::Owner) {
+        return WebhookRegisterResponse::NotAuthorized;
+    }
+
+    // the name is used in the webhook url
+    if args.name.is_empty() || 
+        !args.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return WebhookRegisterResponse::InvalidName;
+    }
+
+    if let Some(key) = args.chats.iter().find(|key| ChatStorage::load(key).is_none()) {
+        return WebhookRegisterResponse::UnknownChat(key.clone());
+    }
+
+    let secret = args.secret
+        .unwrap_or_else(|| rng::gen::<[u8; WEBHOOK_SECRET_SIZE]>().to_vec());
+
+    WebhookStorage::save(args.name, Webhook {
+        secret: secret.clone(),
+        chats: args.chats,
+        enabled: true,
+        created_at: ic_cdk::api::time(),
+    });
+
+    WebhookRegisterResponse::Success(secret)
+}
+
+#[ic_cdk::update]
+fn set_webhook_enabled(
+    name: WebhookName,
+    enabled: bool
+) -> Result<(), String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Owner) {
+        return Err("Not authorized".to_string());
+    }
+
+    let mut webhook = WebhookStorage::load(&name)
+        .ok_or_else(|| "Webhook not found".to_string())?;
+    webhook.enabled = enabled;
+    WebhookStorage::save(name, webhook);
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+fn remove_webhook(
+    name: WebhookName
+) -> Result<(), String> {
+    if !RoleStorage::has_role(&env::caller(), Role::Owner) {
+        return Err("Not authorized".to_string());
+    }
+
+    WebhookStorage::remove(&name)
+        .map(|_| ())
+        .ok_or_else(|| "Webhook not found".to_string())
+}
\ No newline at end of file
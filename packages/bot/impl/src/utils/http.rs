@@ -0,0 +1,52 @@
+use ic_http_certification::HttpRequest;
+
+pub fn get_header<'a>(
+    request: &'a HttpRequest,
+    name: &str
+) -> Option<&'a str> {
+    request.headers.iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.as_str())
+}
+
+/// Parses a single "bytes=start-end" range. Multiple ranges are not supported
+pub fn parse_range(
+    value: &str,
+    size: u64
+) -> Option<(u64, u64)> {
+    let spec = value.trim().strip_prefix("bytes=")?;
+    if spec.contains(',') {
+        return None;
+    }
+
+    let (start, end) = spec.split_once('-')?;
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", suffix) => {
+            let len = suffix.parse::<u64>().ok()?.min(size);
+            (size - len, size.checked_sub(1)?)
+        },
+        (start, "") => {
+            (start.parse::<u64>().ok()?, size.checked_sub(1)?)
+        },
+        (start, end) => {
+            (start.parse::<u64>().ok()?, end.parse::<u64>().ok()?.min(size.checked_sub(1)?))
+        },
+    };
+
+    if start > end || start >= size {
+        return None;
+    }
+
+    Some((start, end))
+}
+
+pub fn get_query_param<'a>(
+    request: &'a HttpRequest,
+    name: &str
+) -> Option<&'a str> {
+    let (_, query) = request.url.split_once('?')?;
+    query.split('&')
+        .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+        .find(|(k, _)| *k == name)
+        .map(|(_, v)| v)
+}
\ No newline at end of file
@@ -0,0 +1,1028 @@
+use std::{collections::HashMap, fmt::Display, sync::LazyLock};
+use Locale::{En, Es, Pt};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    Pt,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [En, Es, Pt];
+
+    /// Accepts language codes or tags, e.g.: "es", "pt-BR" or "en_US"
+    pub fn from_code(
+        code: &str
+    ) -> Option<Self> {
+        let lang = code.split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        Self::ALL.into_iter()
+            .find(|locale| locale.code() == lang)
+    }
+
+    pub fn code(
+        &self
+    ) -> &'static str {
+        match self {
+            En => "en",
+            Es => "es",
+            Pt => "pt",
+        }
+    }
+}
+
+static MESSAGES: LazyLock<HashMap<(&'static str, Locale), &'static str>> = LazyLock::new(|| {
+    CATALOG.iter()
+        .flat_map(|(id, texts)|
+            texts.iter().map(move |(locale, text)| ((*id, *locale), *text))
+        )
+        .collect()
+});
+
+/// Returns the message in the locale requested, if there's a translation for it
+pub fn lookup(
+    locale: Locale,
+    id: &str
+) -> Option<&'static str> {
+    MESSAGES.get(&(id, locale)).copied()
+}
+
+/// Renders a message, replacing the placeholders {0}, {1}, etc by the args.
+/// Messages not translated fall back to english
+pub fn tr(
+    locale: Locale,
+    id: &str,
+    args: &[&dyn Display]
+) -> String {
+    let template = lookup(locale, id)
+        .or_else(|| lookup(En, id))
+        .unwrap_or(id);
+
+    // a single pass over the template, so placeholders inside the args are kept as is
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let arg = rest.find('}')
+            .and_then(|end| rest[1..end].parse::<usize>().ok().map(|i| (i, end)))
+            .and_then(|(i, end)| args.get(i).map(|arg| (arg, end)));
+
+        match arg {
+            Some((arg, end)) => {
+                text.push_str(&arg.to_string());
+                rest = &rest[end + 1..];
+            },
+            None => {
+                text.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+
+    text
+}
+
+/// Translates the about and the help of a clap command and of its args and subcommands.
+/// The keys are "help.<command path>[.<arg>]", e.g.: "help.meme.wallet.withdraw.amount",
+/// or "help.arg.<arg>" for args shared by many commands
+pub fn localize_command(
+    cmd: clap::Command,
+    locale: Locale,
+    path: &str
+) -> clap::Command {
+    if locale == En {
+        return cmd;
+    }
+
+    let mut cmd = cmd;
+    if let Some(about) = lookup(locale, &format!("help.{}", path)) {
+        cmd = cmd.about(about);
+    }
+    if let Some(heading) = lookup(locale, "help.heading.commands") {
+        cmd = cmd.subcommand_help_heading(heading);
+    }
+
+    let args = cmd.get_arguments()
+        .map(|arg| arg.get_id().to_string())
+        .collect::<Vec<_>>();
+    for arg in args {
+        if let Some(help) = lookup(locale, &format!("help.{}.{}", path, arg))
+            .or_else(|| lookup(locale, &format!("help.arg.{}", arg))) {
+            cmd = cmd.mut_arg(arg, |a| a.help(help));
+        }
+    }
+
+    let subcommands = cmd.get_subcommands()
+        .map(|sub| sub.get_name().to_string())
+        .collect::<Vec<_>>();
+    for sub in subcommands {
+        let sub_path = format!("{}.{}", path, sub);
+        cmd = cmd.mut_subcommand(sub, |s| localize_command(s, locale, &sub_path));
+    }
+
+    cmd
+}
+
+const CATALOG: &[(&str, &[(Locale, &str)])] = &[
+    // errors and replies
+    ("chat_only", &[
+        (En, "This command can only be used in a chat"),
+        (Es, "Este comando solo se puede usar en un chat"),
+        (Pt, "Este comando só pode ser usado em um chat"),
+    ]),
+    ("invalid_principal", &[
+        (En, "Invalid principal for 'to': {0}"),
+        (Es, "Principal inválido para 'to': {0}"),
+        (Pt, "Principal inválido para 'to': {0}"),
+    ]),
+    ("not_authorized", &[
+        (En, "Sorry, you are not authorized to run this command"),
+        (Es, "Lo sentimos, no estás autorizado a ejecutar este comando"),
+        (Pt, "Desculpe, você não está autorizado a executar este comando"),
+    ]),
+    ("command_disabled", &[
+        (En, "'/meme {0}' was disabled in this chat by its admins"),
+        (Es, "'/meme {0}' fue deshabilitado en este chat por sus administradores"),
+        (Pt, "'/meme {0}' foi desativado neste chat pelos seus administradores"),
+    ]),
+    ("rate_limited.user", &[
+        (En, "You're going too fast! Try again in {0} seconds"),
+        (Es, "¡Vas demasiado rápido! Inténtalo de nuevo en {0} segundos"),
+        (Pt, "Você está indo rápido demais! Tente novamente em {0} segundos"),
+    ]),
+    ("rate_limited.chat", &[
+        (En, "This chat is going too fast! Try again in {0} seconds"),
+        (Es, "¡Este chat va demasiado rápido! Inténtalo de nuevo en {0} segundos"),
+        (Pt, "Este chat está indo rápido demais! Tente novamente em {0} segundos"),
+    ]),
+    ("moderation.refused", &[
+        (En, "Sorry, these captions were refused by the moderation filter"),
+        (Es, "Lo sentimos, estos textos fueron rechazados por el filtro de moderación"),
+        (Pt, "Desculpe, estas legendas foram recusadas pelo filtro de moderação"),
+    ]),
+    ("page", &[
+        (En, "Page {0}/{1}"),
+        (Es, "Página {0}/{1}"),
+        (Pt, "Página {0}/{1}"),
+    ]),
+    ("search.not_found", &[
+        (En, "No memes found :/. Try again!"),
+        (Es, "No se encontraron memes :/. ¡Inténtalo de nuevo!"),
+        (Pt, "Nenhum meme encontrado :/. Tente novamente!"),
+    ]),
+    ("meme.id", &[
+        (En, "meme id: **{0}**"),
+        (Es, "id del meme: **{0}**"),
+        (Pt, "id do meme: **{0}**"),
+    ]),
+    ("meme.unknown", &[
+        (En, "Unknown meme :/"),
+        (Es, "Meme desconocido :/"),
+        (Pt, "Meme desconhecido :/"),
+    ]),
+    ("meme.not_found", &[
+        (En, "Meme image not found. Please regenerate it with '/meme gen' or '/meme suggest'"),
+        (Es, "Imagen del meme no encontrada. Por favor, vuelve a generarla con '/meme gen' o '/meme suggest'"),
+        (Pt, "Imagem do meme não encontrada. Por favor, gere-a novamente com '/meme gen' ou '/meme suggest'"),
+    ]),
+    ("meme.expired", &[
+        (En, "This meme has expired. Please regenerate it with '/meme gen' or '/meme suggest'"),
+        (Es, "Este meme ha expirado. Por favor, vuelve a generarlo con '/meme gen' o '/meme suggest'"),
+        (Pt, "Este meme expirou. Por favor, gere-o novamente com '/meme gen' ou '/meme suggest'"),
+    ]),
+    ("meme.not_owner", &[
+        (En, "This meme was not generated by you"),
+        (Es, "Este meme no fue generado por ti"),
+        (Pt, "Este meme não foi gerado por você"),
+    ]),
+    ("post.not_found", &[
+        (En, "No meme found!"),
+        (Es, "¡No se encontró ningún meme!"),
+        (Pt, "Nenhum meme encontrado!"),
+    ]),
+    ("post.no_memes", &[
+        (En, "No meme found. Use /meme_gen first!"),
+        (Es, "No se encontró ningún meme. ¡Usa /meme_gen primero!"),
+        (Pt, "Nenhum meme encontrado. Use /meme_gen primeiro!"),
+    ]),
+    ("report.sent", &[
+        (En, "Thanks! Your report was sent to the moderators"),
+        (Es, "¡Gracias! Tu denuncia fue enviada a los moderadores"),
+        (Pt, "Obrigado! Sua denúncia foi enviada aos moderadores"),
+    ]),
+    ("report.not_found", &[
+        (En, "No posted meme or MEME NFT found with this id"),
+        (Es, "No se encontró ningún meme publicado o MEME NFT con este id"),
+        (Pt, "Nenhum meme publicado ou MEME NFT encontrado com este id"),
+    ]),
+    ("report.already_reported", &[
+        (En, "You have already reported it"),
+        (Es, "Ya lo has denunciado"),
+        (Pt, "Você já o denunciou"),
+    ]),
+    ("report.taken_down", &[
+        (En, "It was already taken down by the moderators"),
+        (Es, "Ya fue retirado por los moderadores"),
+        (Pt, "Já foi removido pelos moderadores"),
+    ]),
+    ("wallet.balance", &[
+        (En, "Balance:  \n{1}: {0}  \n"),
+        (Es, "Saldo:  \n{1}: {0}  \n"),
+        (Pt, "Saldo:  \n{1}: {0}  \n"),
+    ]),
+    ("wallet.address", &[
+        (En, "Address:  \n{1}: {0}  \n"),
+        (Es, "Dirección:  \n{1}: {0}  \n"),
+        (Pt, "Endereço:  \n{1}: {0}  \n"),
+    ]),
+    ("wallet.withdrawn", &[
+        (En, "Withdrawn of **{0}** {3} to account **{1}** completed! At block index: **{2}**"),
+        (Es, "¡Retiro de **{0}** {3} a la cuenta **{1}** completado! En el bloque: **{2}**"),
+        (Pt, "Saque de **{0}** {3} para a conta **{1}** concluído! No bloco: **{2}**"),
+    ]),
+    ("wallet.invalid_amount", &[
+        (En, "Invalid amount: **{0}**. Use the decimal format (eg: 1.25), with up to {1} decimal places"),
+        (Es, "Cantidad inválida: **{0}**. Usa el formato decimal (p. ej.: 1.25), con hasta {1} decimales"),
+        (Pt, "Valor inválido: **{0}**. Use o formato decimal (ex.: 1.25), com até {1} casas decimais"),
+    ]),
+    ("wallet.no_amount", &[
+        (En, "Please provide the amount to withdraw, or use --all to withdraw the whole balance"),
+        (Es, "Por favor, indica la cantidad a retirar, o usa --all para retirar todo el saldo"),
+        (Pt, "Por favor, informe o valor a sacar, ou use --all para sacar todo o saldo"),
+    ]),
+    ("wallet.all_with_amount", &[
+        (En, "With --all, don't provide an amount, only the optional destination"),
+        (Es, "Con --all, no indiques una cantidad, solo el destino opcional"),
+        (Pt, "Com --all, não informe um valor, apenas o destino opcional"),
+    ]),
+    ("invalid_decimal", &[
+        (En, "Invalid value for --{0}: **{1}**. Use the decimal format (eg: 1.25), with up to {2} decimal places"),
+        (Es, "Valor inválido para --{0}: **{1}**. Usa el formato decimal (p. ej.: 1.25), con hasta {2} decimales"),
+        (Pt, "Valor inválido para --{0}: **{1}**. Use o formato decimal (ex.: 1.25), com até {2} casas decimais"),
+    ]),
+    ("invalid_value", &[
+        (En, "Invalid value for --{0}: **{1}**"),
+        (Es, "Valor inválido para --{0}: **{1}**"),
+        (Pt, "Valor inválido para --{0}: **{1}**"),
+    ]),
+    ("wallet.logs.no_deposits", &[
+        (En, "_{0} deposits are not listed, but are included in your balance_"),
+        (Es, "_Los depósitos de {0} no se listan, pero están incluidos en tu saldo_"),
+        (Pt, "_Os depósitos de {0} não são listados, mas estão incluídos no seu saldo_"),
+    ]),
+    ("wallet.invalid_destination", &[
+        (En, "Invalid destination for {1}: **{0}**"),
+        (Es, "Destino inválido para {1}: **{0}**"),
+        (Pt, "Destino inválido para {1}: **{0}**"),
+    ]),
+    ("wallet.unknown_username", &[
+        (En, "OC user not found: **{0}**"),
+        (Es, "Usuario de OC no encontrado: **{0}**"),
+        (Pt, "Usuário do OC não encontrado: **{0}**"),
+    ]),
+    ("wallet.insufficient_balance", &[
+        (En, "Your balance of **{0}** {2} doesn't cover the transfer fee of **{1}** {2}"),
+        (Es, "Tu saldo de **{0}** {2} no cubre la comisión de transferencia de **{1}** {2}"),
+        (Pt, "Seu saldo de **{0}** {2} não cobre a taxa de transferência de **{1}** {2}"),
+    ]),
+    ("wallet.log.withdraw", &[
+        (En, "{4} · Withdraw: -{0} {1} to account {2} (block {3})"),
+        (Es, "{4} · Retiro: -{0} {1} a la cuenta {2} (bloque {3})"),
+        (Pt, "{4} · Saque: -{0} {1} para a conta {2} (bloco {3})"),
+    ]),
+    ("wallet.log.deposit", &[
+        (En, "{4} · Deposit: +{0} {1} from account {2} (block {3})"),
+        (Es, "{4} · Depósito: +{0} {1} desde la cuenta {2} (bloque {3})"),
+        (Pt, "{4} · Depósito: +{0} {1} da conta {2} (bloco {3})"),
+    ]),
+    ("wallet.log.mint_payment", &[
+        (En, "{4} · Minting payment: -{0} {1} for the meme {2} (block {3})"),
+        (Es, "{4} · Pago de acuñación: -{0} {1} por el meme {2} (bloque {3})"),
+        (Pt, "{4} · Pagamento de cunhagem: -{0} {1} pelo meme {2} (bloco {3})"),
+    ]),
+    ("wallet.log.mint_refund", &[
+        (En, "{4} · Minting refund: +{0} {1} for the meme {2} (block {3})"),
+        (Es, "{4} · Reembolso de acuñación: +{0} {1} por el meme {2} (bloque {3})"),
+        (Pt, "{4} · Reembolso de cunhagem: +{0} {1} pelo meme {2} (bloco {3})"),
+    ]),
+    ("logs.empty", &[
+        (En, "No transactions found"),
+        (Es, "No se encontraron transacciones"),
+        (Pt, "Nenhuma transação encontrada"),
+    ]),
+    ("schedule.job", &[
+        (En, "**{0}**: {1}, next run at {2}{3}"),
+        (Es, "**{0}**: {1}, próxima ejecución el {2}{3}"),
+        (Pt, "**{0}**: {1}, próxima execução em {2}{3}"),
+    ]),
+    ("schedule.last_failed", &[
+        (En, " (last run failed: {0})"),
+        (Es, " (la última ejecución falló: {0})"),
+        (Pt, " (a última execução falhou: {0})"),
+    ]),
+    ("schedule.empty", &[
+        (En, "No jobs scheduled in this chat"),
+        (Es, "No hay tareas programadas en este chat"),
+        (Pt, "Nenhuma tarefa agendada neste chat"),
+    ]),
+    ("schedule.not_found", &[
+        (En, "Job {0} not found in this chat"),
+        (Es, "Tarea {0} no encontrada en este chat"),
+        (Pt, "Tarefa {0} não encontrada neste chat"),
+    ]),
+    ("schedule.cancelled", &[
+        (En, "Job **{0}** cancelled"),
+        (Es, "Tarea **{0}** cancelada"),
+        (Pt, "Tarefa **{0}** cancelada"),
+    ]),
+    ("schedule.invalid_hour", &[
+        (En, "Hour must be between 0 and 23"),
+        (Es, "La hora debe estar entre 0 y 23"),
+        (Pt, "A hora deve estar entre 0 e 23"),
+    ]),
+    ("schedule.invalid_weekday", &[
+        (En, "Weekday must be between 0 and 6 and hour between 0 and 23"),
+        (Es, "El día de la semana debe estar entre 0 y 6 y la hora entre 0 y 23"),
+        (Pt, "O dia da semana deve estar entre 0 e 6 e a hora entre 0 e 23"),
+    ]),
+    ("schedule.invalid_interval", &[
+        (En, "Interval must be between 1 and 720 hours"),
+        (Es, "El intervalo debe estar entre 1 y 720 horas"),
+        (Pt, "O intervalo deve estar entre 1 e 720 horas"),
+    ]),
+    ("schedule.not_autonomous", &[
+        (En, "Mementor can't post on its own in this chat yet. Please reinstall the bot, granting it permission to send messages autonomously"),
+        (Es, "Mementor aún no puede publicar por su cuenta en este chat. Por favor, reinstala el bot, otorgándole permiso para enviar mensajes de forma autónoma"),
+        (Pt, "O Mementor ainda não pode publicar por conta própria neste chat. Por favor, reinstale o bot, concedendo-lhe permissão para enviar mensagens de forma autônoma"),
+    ]),
+    ("schedule.too_many", &[
+        (En, "A chat can have at most {0} jobs scheduled"),
+        (Es, "Un chat puede tener como máximo {0} tareas programadas"),
+        (Pt, "Um chat pode ter no máximo {0} tarefas agendadas"),
+    ]),
+    ("schedule.scheduled", &[
+        (En, "Job **{0}** scheduled: {1}. Next run at {2}"),
+        (Es, "Tarea **{0}** programada: {1}. Próxima ejecución el {2}"),
+        (Pt, "Tarefa **{0}** agendada: {1}. Próxima execução em {2}"),
+    ]),
+    ("roles.entry", &[
+        (En, "{0}: **{1}**"),
+    ]),
+    ("roles.granted", &[
+        (En, "Roles granted to {0}"),
+        (Es, "Roles otorgados a {0}"),
+        (Pt, "Funções concedidas a {0}"),
+    ]),
+    ("roles.revoked", &[
+        (En, "Roles revoked from {0}"),
+        (Es, "Roles revocados de {0}"),
+        (Pt, "Funções revogadas de {0}"),
+    ]),
+    ("roles.last_owner", &[
+        (En, "The last owner can't be revoked"),
+        (Es, "El último propietario no puede ser revocado"),
+        (Pt, "O último proprietário não pode ser revogado"),
+    ]),
+    ("mint.no_posts", &[
+        (En, "Meme not found. Use /meme_post first!"),
+        (Es, "Meme no encontrado. ¡Usa /meme_post primero!"),
+        (Pt, "Meme não encontrado. Use /meme_post primeiro!"),
+    ]),
+    ("mint.banned", &[
+        (En, "Sorry, you were banned from minting MEME NFTs"),
+        (Es, "Lo sentimos, se te prohibió acuñar MEME NFTs"),
+        (Pt, "Desculpe, você foi banido de cunhar MEME NFTs"),
+    ]),
+    ("mint.paused", &[
+        (En, "Minting is paused: {0}"),
+        (Es, "La acuñación está en pausa: {0}"),
+        (Pt, "A cunhagem está pausada: {0}"),
+    ]),
+    ("mint.sold_out", &[
+        (En, "Sorry, all the MEME NFTs were minted!"),
+        (Es, "¡Lo sentimos, todos los MEME NFTs fueron acuñados!"),
+        (Pt, "Desculpe, todos os MEME NFTs foram cunhados!"),
+    ]),
+    ("mint.not_started", &[
+        (En, "Minting is closed. The next phase starts at {0}"),
+        (Es, "La acuñación está cerrada. La próxima fase comienza el {0}"),
+        (Pt, "A cunhagem está fechada. A próxima fase começa em {0}"),
+    ]),
+    ("mint.closed", &[
+        (En, "Minting is closed"),
+        (Es, "La acuñación está cerrada"),
+        (Pt, "A cunhagem está fechada"),
+    ]),
+    ("mint.not_allowlisted", &[
+        (En, "Sorry, only allowlisted users can mint during the **{0}** phase"),
+        (Es, "Lo sentimos, solo los usuarios de la lista de permitidos pueden acuñar durante la fase **{0}**"),
+        (Pt, "Desculpe, somente usuários da lista de permitidos podem cunhar durante a fase **{0}**"),
+    ]),
+    ("mint.user_cap", &[
+        (En, "You already minted the max of {0} MEME NFTs in this phase"),
+        (Es, "Ya acuñaste el máximo de {0} MEME NFTs en esta fase"),
+        (Pt, "Você já cunhou o máximo de {0} MEME NFTs nesta fase"),
+    ]),
+    ("mint.user_in_flight", &[
+        (En, "You already have a mint in progress. Please wait for it to finish"),
+        (Es, "Ya tienes una acuñación en curso. Por favor, espera a que termine"),
+        (Pt, "Você já tem uma cunhagem em andamento. Por favor, aguarde a conclusão"),
+    ]),
+    ("mint.meme_in_flight", &[
+        (En, "This meme is already being minted"),
+        (Es, "Este meme ya está siendo acuñado"),
+        (Pt, "Este meme já está sendo cunhado"),
+    ]),
+    ("mint.post_not_found", &[
+        (En, "Post not found!"),
+        (Es, "¡Publicación no encontrada!"),
+        (Pt, "Publicação não encontrada!"),
+    ]),
+    ("mint.taken_down", &[
+        (En, "This meme was taken down and can't be minted"),
+        (Es, "Este meme fue retirado y no se puede acuñar"),
+        (Pt, "Este meme foi removido e não pode ser cunhado"),
+    ]),
+    ("mint.already_minted", &[
+        (En, "Meme was already minted! Token id: **{0}**"),
+        (Es, "¡El meme ya fue acuñado! Id del token: **{0}**"),
+        (Pt, "O meme já foi cunhado! Id do token: **{0}**"),
+    ]),
+    ("mint.no_poh", &[
+        (En, "You haven't proven to be a unique individual yet. Please do that first on Open Chat"),
+        (Es, "Aún no has demostrado ser una persona única. Por favor, hazlo primero en Open Chat"),
+        (Pt, "Você ainda não provou ser um indivíduo único. Por favor, faça isso primeiro no Open Chat"),
+    ]),
+    ("mint.few_members", &[
+        (En, "The group/channel where the meme was posted has only {0} members. Expected at least {1}"),
+        (Es, "El grupo/canal donde se publicó el meme tiene solo {0} miembros. Se esperaban al menos {1}"),
+        (Pt, "O grupo/canal onde o meme foi publicado tem apenas {0} membros. Esperado pelo menos {1}"),
+    ]),
+    ("mint.few_reactions", &[
+        (En, "Post has only **{0}** reactions from different users created {1} days ago or earlier who have proven to be unique individuals. Expected at least **{2}** reactions. Try harder 😎"),
+        (Es, "La publicación tiene solo **{0}** reacciones de distintos usuarios, creados hace {1} días o más, que han demostrado ser personas únicas. Se esperaban al menos **{2}** reacciones. ¡Esfuérzate más! 😎"),
+        (Pt, "A publicação tem apenas **{0}** reações de usuários diferentes, criados há {1} dias ou mais, que provaram ser indivíduos únicos. Esperado pelo menos **{2}** reações. Esforce-se mais 😎"),
+    ]),
+    ("mint.low_balance", &[
+        (En, "Your Mementor wallet balance of **{0}** ICP is too low to cover the current minting cost of **{1}** ICP  \nPlease transfer enough ICP to this address: **{2}**  \nOr approve **{3}** ICP to the principal **{4}** from your own wallet (ICRC-2)"),
+        (Es, "Tu saldo de **{0}** ICP en la billetera de Mementor es insuficiente para cubrir el costo actual de acuñación de **{1}** ICP  \nPor favor, transfiere suficientes ICP a esta dirección: **{2}**  \nO aprueba **{3}** ICP al principal **{4}** desde tu propia billetera (ICRC-2)"),
+        (Pt, "Seu saldo de **{0}** ICP na carteira do Mementor é insuficiente para cobrir o custo atual de cunhagem de **{1}** ICP  \nPor favor, transfira ICP suficiente para este endereço: **{2}**  \nOu aprove **{3}** ICP para o principal **{4}** a partir da sua própria carteira (ICRC-2)"),
+    ]),
+    ("mint.debit_failed", &[
+        (En, "Failed to debit the minting cost: {0}."),
+        (Es, "No se pudo debitar el costo de acuñación: {0}."),
+        (Pt, "Falha ao debitar o custo de cunhagem: {0}."),
+    ]),
+    ("mint.minted", &[
+        (En, "NFT **{0}** has just been minted! 🎉🎉🎉"),
+        (Es, "¡El NFT **{0}** acaba de ser acuñado! 🎉🎉🎉"),
+        (Pt, "O NFT **{0}** acaba de ser cunhado! 🎉🎉🎉"),
+    ]),
+    ("nft.balance", &[
+        (En, "Balance: {0}"),
+        (Es, "Saldo: {0}"),
+        (Pt, "Saldo: {0}"),
+    ]),
+    ("nft.no_tokens", &[
+        (En, "No tokens found. Go and mint a NFT before it's too late! 😋"),
+        (Es, "No se encontraron tokens. ¡Ve y acuña un NFT antes de que sea demasiado tarde! 😋"),
+        (Pt, "Nenhum token encontrado. Vá e cunhe um NFT antes que seja tarde demais! 😋"),
+    ]),
+    ("nft.not_found", &[
+        (En, "NFT {0} not found"),
+        (Es, "NFT {0} no encontrado"),
+        (Pt, "NFT {0} não encontrado"),
+    ]),
+    ("nft.transferred", &[
+        (En, "Token {0} transferred to {1}! 🎉"),
+        (Es, "¡Token {0} transferido a {1}! 🎉"),
+        (Pt, "Token {0} transferido para {1}! 🎉"),
+    ]),
+    ("nft.log", &[
+        (En, "{3} · Transfer: token #{0} to principal {1} (tx {2})"),
+        (Es, "{3} · Transferencia: token #{0} al principal {1} (tx {2})"),
+        (Pt, "{3} · Transferência: token #{0} para o principal {1} (tx {2})"),
+    ]),
+    ("nft.status", &[
+        (En, "**MEME NFT Status**  \n- canister id: {0}  \n- minting cost: **{1} ICP**  \n- min reactions: {2}  \n- supply: {3}/{4}  \n- remaining: {5}  \n"),
+        (Es, "**Estado de los MEME NFT**  \n- id del canister: {0}  \n- costo de acuñación: **{1} ICP**  \n- reacciones mínimas: {2}  \n- suministro: {3}/{4}  \n- restantes: {5}  \n"),
+        (Pt, "**Status dos MEME NFT**  \n- id do canister: {0}  \n- custo de cunhagem: **{1} ICP**  \n- reações mínimas: {2}  \n- fornecimento: {3}/{4}  \n- restantes: {5}  \n"),
+    ]),
+    ("nft.status.paused", &[
+        (En, "- minting **paused**: {0}  \n"),
+        (Es, "- acuñación **en pausa**: {0}  \n"),
+        (Pt, "- cunhagem **pausada**: {0}  \n"),
+    ]),
+    ("nft.status.phase", &[
+        (En, "- phase: **{0}** ({1}), ends at: {2}  \n"),
+        (Es, "- fase: **{0}** ({1}), termina el: {2}  \n"),
+        (Pt, "- fase: **{0}** ({1}), termina em: {2}  \n"),
+    ]),
+    ("nft.status.closed", &[
+        (En, "- phase: {0}  \n"),
+        (Es, "- fase: {0}  \n"),
+        (Pt, "- fase: {0}  \n"),
+    ]),
+    ("nft.config", &[
+        (En, "**MEME NFT Config**  \n- reactions: {0} to {1}  \n- minting cost: {2} to {3} ICP  \n- min chat members: {4}  \n- min user age: {5} days  \n- team fee: {6}%  \n- cost curve: {7}  \n- reactions curve: {8}  \n"),
+        (Es, "**Configuración de los MEME NFT**  \n- reacciones: {0} a {1}  \n- costo de acuñación: {2} a {3} ICP  \n- miembros mínimos del chat: {4}  \n- antigüedad mínima del usuario: {5} días  \n- comisión del equipo: {6}%  \n- curva del costo: {7}  \n- curva de las reacciones: {8}  \n"),
+        (Pt, "**Configuração dos MEME NFT**  \n- reações: {0} a {1}  \n- custo de cunhagem: {2} a {3} ICP  \n- membros mínimos do chat: {4}  \n- idade mínima do usuário: {5} dias  \n- taxa da equipe: {6}%  \n- curva do custo: {7}  \n- curva das reações: {8}  \n"),
+    ]),
+    ("nft.preview", &[
+        (En, "supply {0}: minting cost **{1} ICP**, min reactions **{2}**"),
+        (Es, "suministro {0}: costo de acuñación **{1} ICP**, reacciones mínimas **{2}**"),
+        (Pt, "fornecimento {0}: custo de cunhagem **{1} ICP**, reações mínimas **{2}**"),
+    ]),
+    ("nft.config_changed", &[
+        (En, "MEME NFT config changed"),
+        (Es, "Configuración de los MEME NFT cambiada"),
+        (Pt, "Configuração dos MEME NFT alterada"),
+    ]),
+    ("nft.paused", &[
+        (En, "Minting paused"),
+        (Es, "Acuñación en pausa"),
+        (Pt, "Cunhagem pausada"),
+    ]),
+    ("nft.resumed", &[
+        (En, "Minting resumed"),
+        (Es, "Acuñación reanudada"),
+        (Pt, "Cunhagem retomada"),
+    ]),
+    ("nft.config_log.config", &[
+        (En, "{0}: config changed by {1}"),
+        (Es, "{0}: configuración cambiada por {1}"),
+        (Pt, "{0}: configuração alterada por {1}"),
+    ]),
+    ("nft.config_log.paused", &[
+        (En, "{0}: minting paused by {1}: {2}"),
+        (Es, "{0}: acuñación pausada por {1}: {2}"),
+        (Pt, "{0}: cunhagem pausada por {1}: {2}"),
+    ]),
+    ("nft.config_log.resumed", &[
+        (En, "{0}: minting resumed by {1}"),
+        (Es, "{0}: acuñación reanudada por {1}"),
+        (Pt, "{0}: cunhagem retomada por {1}"),
+    ]),
+    ("nft.config_log.phases", &[
+        (En, "{0}: {2} minting phases set by {1}"),
+        (Es, "{0}: {2} fases de acuñación definidas por {1}"),
+        (Pt, "{0}: {2} fases de cunhagem definidas por {1}"),
+    ]),
+    ("nft.config_log.allowlist", &[
+        (En, "{0}: allowlist changed by {1}: {2} added, {3} removed"),
+        (Es, "{0}: lista de permitidos cambiada por {1}: {2} agregados, {3} eliminados"),
+        (Pt, "{0}: lista de permitidos alterada por {1}: {2} adicionados, {3} removidos"),
+    ]),
+    ("settings.reset", &[
+        (En, "Settings restored to their defaults"),
+        (Es, "Configuración restablecida a sus valores por defecto"),
+        (Pt, "Configurações restauradas aos seus valores padrão"),
+    ]),
+    ("settings.mood_updated", &[
+        (En, "Default mood updated"),
+        (Es, "Estado de ánimo por defecto actualizado"),
+        (Pt, "Humor padrão atualizado"),
+    ]),
+    ("settings.topic_updated", &[
+        (En, "Default topic updated"),
+        (Es, "Tema por defecto actualizado"),
+        (Pt, "Tema padrão atualizado"),
+    ]),
+    ("settings.language_updated", &[
+        (En, "Captions language updated"),
+        (Es, "Idioma de los textos actualizado"),
+        (Pt, "Idioma das legendas atualizado"),
+    ]),
+    ("settings.locale_updated", &[
+        (En, "Responses locale updated"),
+        (Es, "Idioma de las respuestas actualizado"),
+        (Pt, "Idioma das respostas atualizado"),
+    ]),
+    ("settings.unsupported_locale", &[
+        (En, "Unsupported locale '{0}'. Supported: {1}"),
+        (Es, "Idioma '{0}' no soportado. Soportados: {1}"),
+        (Pt, "Idioma '{0}' não suportado. Suportados: {1}"),
+    ]),
+    ("settings.unknown_command", &[
+        (En, "Unknown command '{0}'"),
+        (Es, "Comando desconocido '{0}'"),
+        (Pt, "Comando desconhecido '{0}'"),
+    ]),
+    ("settings.command_disabled", &[
+        (En, "'/meme {0}' disabled"),
+        (Es, "'/meme {0}' deshabilitado"),
+        (Pt, "'/meme {0}' desativado"),
+    ]),
+    ("settings.command_enabled", &[
+        (En, "'/meme {0}' enabled"),
+        (Es, "'/meme {0}' habilitado"),
+        (Pt, "'/meme {0}' ativado"),
+    ]),
+    ("settings.not_disabled", &[
+        (En, "'/meme {0}' is not disabled (disabled: {1})"),
+        (Es, "'/meme {0}' no está deshabilitado (deshabilitados: {1})"),
+        (Pt, "'/meme {0}' não está desativado (desativados: {1})"),
+    ]),
+    ("settings.nsfw_updated", &[
+        (En, "NSFW strictness updated"),
+        (Es, "Rigor NSFW actualizado"),
+        (Pt, "Rigor NSFW atualizado"),
+    ]),
+    ("settings.show.mood", &[
+        (En, "Default mood: **{0}**"),
+        (Es, "Estado de ánimo por defecto: **{0}**"),
+        (Pt, "Humor padrão: **{0}**"),
+    ]),
+    ("settings.show.topic", &[
+        (En, "Default topic: **{0}**"),
+        (Es, "Tema por defecto: **{0}**"),
+        (Pt, "Tema padrão: **{0}**"),
+    ]),
+    ("settings.show.language", &[
+        (En, "Captions language: **{0}**"),
+        (Es, "Idioma de los textos: **{0}**"),
+        (Pt, "Idioma das legendas: **{0}**"),
+    ]),
+    ("settings.show.locale", &[
+        (En, "Responses locale: **{0}**"),
+        (Es, "Idioma de las respuestas: **{0}**"),
+        (Pt, "Idioma das respostas: **{0}**"),
+    ]),
+    ("settings.show.disabled", &[
+        (En, "Disabled commands: **{0}**"),
+        (Es, "Comandos deshabilitados: **{0}**"),
+        (Pt, "Comandos desativados: **{0}**"),
+    ]),
+    ("settings.show.nsfw", &[
+        (En, "NSFW strictness: **{0}**"),
+        (Es, "Rigor NSFW: **{0}**"),
+        (Pt, "Rigor NSFW: **{0}**"),
+    ]),
+    ("settings.show.updated", &[
+        (En, "Last updated by @UserId({0}) at {1}"),
+        (Es, "Última actualización por @UserId({0}) el {1}"),
+        (Pt, "Última atualização por @UserId({0}) em {1}"),
+    ]),
+    ("settings.default.locale", &[
+        (En, "user's language"),
+        (Es, "idioma del usuario"),
+        (Pt, "idioma do usuário"),
+    ]),
+    ("settings.default.disabled", &[
+        (En, "none"),
+        (Es, "ninguno"),
+        (Pt, "nenhum"),
+    ]),
+    ("arg.invalid_template", &[
+        (En, "Invalid template id"),
+        (Es, "Id de plantilla inválido"),
+        (Pt, "Id de modelo inválido"),
+    ]),
+    ("arg.invalid_token", &[
+        (En, "Invalid token: {0}"),
+        (Es, "Token inválido: {0}"),
+        (Pt, "Token inválido: {0}"),
+    ]),
+
+    // help of the '/meme' CLI (english is the text declared in types::cli)
+    ("help.heading.commands", &[
+        (Es, "Comandos"),
+        (Pt, "Comandos"),
+    ]),
+    ("help.arg.page", &[
+        (Es, "Número de página opcional (por defecto: 1)"),
+        (Pt, "Número de página opcional (padrão: 1)"),
+    ]),
+    ("help.arg.owner", &[
+        (Es, "Principal opcional del dueño (por defecto: tu id de usuario de OC)"),
+        (Pt, "Principal opcional do dono (padrão: seu id de usuário do OC)"),
+    ]),
+    ("help.arg.hour", &[
+        (Es, "Hora del día, en UTC (por defecto: 12)"),
+        (Pt, "Hora do dia, em UTC (padrão: 12)"),
+    ]),
+    ("help.arg.token", &[
+        (Es, "Token (por defecto: ICP)"),
+        (Pt, "Token (padrão: ICP)"),
+    ]),
+    ("help.arg.principal", &[
+        (Es, "Principal del administrador"),
+        (Pt, "Principal do administrador"),
+    ]),
+    ("help.meme", &[
+        (Es, "¡Crea memes, acúñalos como MEME NFTs exclusivos y gana monedas MEME a cambio!  \nMás información en https://mementor.fun"),
+        (Pt, "Crie memes, cunhe-os como MEME NFTs exclusivos e ganhe moedas MEME em troca!  \nSaiba mais em https://mementor.fun"),
+    ]),
+    ("help.meme.search", &[
+        (Es, "Buscar plantillas de memes"),
+        (Pt, "Pesquisar modelos de memes"),
+    ]),
+    ("help.meme.search.query", &[
+        (Es, "Consulta usada para buscar plantillas de memes"),
+        (Pt, "Consulta usada para pesquisar modelos de memes"),
+    ]),
+    ("help.meme.gen", &[
+        (Es, "Generar un meme a partir de una plantilla"),
+        (Pt, "Gerar um meme a partir de um modelo"),
+    ]),
+    ("help.meme.gen.id", &[
+        (Es, "Id numérico de la plantilla, devuelto por '/meme search'"),
+        (Pt, "Id numérico do modelo, retornado por '/meme search'"),
+    ]),
+    ("help.meme.gen.captions", &[
+        (Es, "Textos, separados por espacios, que se mostrarán en la imagen (usa comillas simples, p. ej. 'un texto', para conservar los espacios)"),
+        (Pt, "Legendas, separadas por espaços, a serem exibidas na imagem (use aspas simples, ex. 'uma legenda', para preservar os espaços)"),
+    ]),
+    ("help.meme.suggest", &[
+        (Es, "Usar IA para crear una historia y sugerir textos para generar un meme a partir de una plantilla"),
+        (Pt, "Usar IA para criar uma história e sugerir legendas para gerar um meme a partir de um modelo"),
+    ]),
+    ("help.meme.suggest.id", &[
+        (Es, "Id numérico de la plantilla, devuelto por '/meme search'"),
+        (Pt, "Id numérico do modelo, retornado por '/meme search'"),
+    ]),
+    ("help.meme.suggest.mood", &[
+        (Es, "El tono de la historia, p. ej.: happy, sad, funny, weird, silly, etc (por defecto funny)"),
+        (Pt, "O tom da história, ex.: happy, sad, funny, weird, silly, etc (padrão funny)"),
+    ]),
+    ("help.meme.suggest.topic", &[
+        (Es, "El tema de la historia, p. ej.: crypto, gatos, robots, etc (por defecto crypto)"),
+        (Pt, "O tema da história, ex.: crypto, gatos, robôs, etc (padrão crypto)"),
+    ]),
+    ("help.meme.post", &[
+        (Es, "Publicar un meme creado previamente con '/meme gen o suggest'"),
+        (Pt, "Publicar um meme criado anteriormente com '/meme gen ou suggest'"),
+    ]),
+    ("help.meme.post.id", &[
+        (Es, "Id alfanumérico opcional del meme devuelto por '/meme gen o suggest' (por defecto: el último meme generado)"),
+        (Pt, "Id alfanumérico opcional do meme retornado por '/meme gen ou suggest' (padrão: o último meme gerado)"),
+    ]),
+    ("help.meme.report", &[
+        (Es, "Denunciar un meme publicado o un MEME NFT a los moderadores"),
+        (Pt, "Denunciar um meme publicado ou um MEME NFT aos moderadores"),
+    ]),
+    ("help.meme.report.id", &[
+        (Es, "Id alfanumérico del meme o id numérico del MEME NFT"),
+        (Pt, "Id alfanumérico do meme ou id numérico do MEME NFT"),
+    ]),
+    ("help.meme.report.reason", &[
+        (Es, "Motivo opcional de la denuncia (usa comillas simples, p. ej. 'un motivo', para preservar los espacios)"),
+        (Pt, "Motivo opcional da denúncia (use aspas simples, ex. 'um motivo', para preservar os espaços)"),
+    ]),
+    ("help.meme.wallet", &[
+        (Es, "Subcomandos de la **Billetera Mementor**"),
+        (Pt, "Subcomandos da **Carteira Mementor**"),
+    ]),
+    ("help.meme.wallet.balance", &[
+        (Es, "Mostrar tu saldo en la Billetera Mementor"),
+        (Pt, "Exibir seu saldo na Carteira Mementor"),
+    ]),
+    ("help.meme.wallet.address", &[
+        (Es, "Mostrar tu dirección en la Billetera Mementor"),
+        (Pt, "Exibir seu endereço na Carteira Mementor"),
+    ]),
+    ("help.meme.wallet.withdraw", &[
+        (Es, "Retirar tokens de tu cuenta en la Billetera Mementor"),
+        (Pt, "Sacar tokens da sua conta na Carteira Mementor"),
+    ]),
+    ("help.meme.wallet.withdraw.amount", &[
+        (Es, "Cantidad a retirar en formato decimal (p. ej.: 1.25), omitida con --all"),
+        (Pt, "Valor a sacar em formato decimal (ex.: 1.25), omitido com --all"),
+    ]),
+    ("help.meme.wallet.withdraw.to", &[
+        (Es, "Destino opcional: una dirección de cuenta en formato hex (solo ICP), una cuenta ICRC-1 o un nombre de usuario de OC (por defecto: tu billetera de OC)"),
+        (Pt, "Destino opcional: um endereço de conta em formato hex (somente ICP), uma conta ICRC-1 ou um nome de usuário do OC (padrão: sua carteira do OC)"),
+    ]),
+    ("help.meme.wallet.withdraw.all", &[
+        (Es, "Retirar todo el saldo, descontando la comisión de transferencia"),
+        (Pt, "Sacar todo o saldo, descontando a taxa de transferência"),
+    ]),
+    ("help.meme.wallet.logs", &[
+        (Es, "Mostrar el historial de la billetera: depósitos, retiros, pagos y reembolsos de acuñación"),
+        (Pt, "Exibir o histórico da carteira: depósitos, saques, pagamentos e reembolsos de cunhagem"),
+    ]),
+    ("help.meme.nft", &[
+        (Es, "Subcomandos de la **colección MEME NFT**"),
+        (Pt, "Subcomandos da **coleção MEME NFT**"),
+    ]),
+    ("help.meme.nft.mint", &[
+        (Es, "Acuñar un MEME NFT, publicado previamente con '/meme post'"),
+        (Pt, "Cunhar um MEME NFT, publicado anteriormente com '/meme post'"),
+    ]),
+    ("help.meme.nft.mint.id", &[
+        (Es, "Id alfanumérico opcional del meme devuelto por '/meme gen o suggest' (por defecto: el último meme publicado)"),
+        (Pt, "Id alfanumérico opcional do meme retornado por '/meme gen ou suggest' (padrão: o último meme publicado)"),
+    ]),
+    ("help.meme.nft.balance", &[
+        (Es, "Mostrar el saldo de MEME NFT de un principal"),
+        (Pt, "Exibir o saldo de MEME NFT de um principal"),
+    ]),
+    ("help.meme.nft.tokens", &[
+        (Es, "Mostrar los MEME NFTs de un principal"),
+        (Pt, "Exibir os MEME NFTs de um principal"),
+    ]),
+    ("help.meme.nft.transfer", &[
+        (Es, "Transferir un MEME NFT a otro principal"),
+        (Pt, "Transferir um MEME NFT para outro principal"),
+    ]),
+    ("help.meme.nft.transfer.id", &[
+        (Es, "Id del token, devuelto por '/meme nft tokens'"),
+        (Pt, "Id do token, retornado por '/meme nft tokens'"),
+    ]),
+    ("help.meme.nft.transfer.to", &[
+        (Es, "Principal del destinatario"),
+        (Pt, "Principal do destinatário"),
+    ]),
+    ("help.meme.nft.logs", &[
+        (Es, "Mostrar el registro de transacciones de MEME NFT"),
+        (Pt, "Exibir o registro de transações de MEME NFT"),
+    ]),
+    ("help.meme.nft.status", &[
+        (Es, "Mostrar el estado de la colección MEME NFT"),
+        (Pt, "Exibir o status da coleção MEME NFT"),
+    ]),
+    ("help.meme.schedule", &[
+        (Es, "Programar publicaciones recurrentes en este chat"),
+        (Pt, "Agendar publicações recorrentes neste chat"),
+    ]),
+    ("help.meme.schedule.meme-of-the-day", &[
+        (Es, "Publicar un meme diario, generado a partir de una plantilla popular con textos de IA"),
+        (Pt, "Publicar um meme diário, gerado a partir de um modelo em alta com legendas de IA"),
+    ]),
+    ("help.meme.schedule.meme-of-the-day.mood", &[
+        (Es, "El tono de la historia (por defecto funny)"),
+        (Pt, "O tom da história (padrão funny)"),
+    ]),
+    ("help.meme.schedule.meme-of-the-day.topic", &[
+        (Es, "El tema de la historia (por defecto crypto)"),
+        (Pt, "O tema da história (padrão crypto)"),
+    ]),
+    ("help.meme.schedule.leaderboard", &[
+        (Es, "Publicar la clasificación semanal de quienes acuñaron MEME NFTs"),
+        (Pt, "Publicar o ranking semanal de quem cunhou MEME NFTs"),
+    ]),
+    ("help.meme.schedule.leaderboard.weekday", &[
+        (Es, "Día de la semana, de 0 (lunes) a 6 (domingo) (por defecto: 0)"),
+        (Pt, "Dia da semana, de 0 (segunda) a 6 (domingo) (padrão: 0)"),
+    ]),
+    ("help.meme.schedule.reminder", &[
+        (Es, "Publicar un recordatorio recurrente"),
+        (Pt, "Publicar um lembrete recorrente"),
+    ]),
+    ("help.meme.schedule.reminder.text", &[
+        (Es, "Texto del recordatorio (usa comillas simples, p. ej. 'un texto', para conservar los espacios)"),
+        (Pt, "Texto do lembrete (use aspas simples, ex. 'um texto', para preservar os espaços)"),
+    ]),
+    ("help.meme.schedule.reminder.every", &[
+        (Es, "Intervalo, en horas (por defecto: 24)"),
+        (Pt, "Intervalo, em horas (padrão: 24)"),
+    ]),
+    ("help.meme.schedule.list", &[
+        (Es, "Listar las tareas programadas en este chat"),
+        (Pt, "Listar as tarefas agendadas neste chat"),
+    ]),
+    ("help.meme.schedule.cancel", &[
+        (Es, "Cancelar una tarea programada"),
+        (Pt, "Cancelar uma tarefa agendada"),
+    ]),
+    ("help.meme.schedule.cancel.id", &[
+        (Es, "Id de la tarea, devuelto por '/meme schedule list'"),
+        (Pt, "Id da tarefa, retornado por '/meme schedule list'"),
+    ]),
+    ("help.meme.settings", &[
+        (Es, "Cambiar cómo se comporta Mementor en este chat (solo administradores)"),
+        (Pt, "Alterar como o Mementor se comporta neste chat (somente administradores)"),
+    ]),
+    ("help.meme.settings.show", &[
+        (Es, "Mostrar la configuración de este chat"),
+        (Pt, "Exibir as configurações deste chat"),
+    ]),
+    ("help.meme.settings.mood", &[
+        (Es, "Definir el tono de la historia usado por '/meme suggest'"),
+        (Pt, "Definir o tom da história usado por '/meme suggest'"),
+    ]),
+    ("help.meme.settings.topic", &[
+        (Es, "Definir el tema de la historia usado por '/meme suggest'"),
+        (Pt, "Definir o tema da história usado por '/meme suggest'"),
+    ]),
+    ("help.meme.settings.language", &[
+        (Es, "Definir el idioma de los textos sugeridos por la IA"),
+        (Pt, "Definir o idioma das legendas sugeridas pela IA"),
+    ]),
+    ("help.meme.settings.locale", &[
+        (Es, "Definir el idioma de las respuestas del bot"),
+        (Pt, "Definir o idioma das respostas do bot"),
+    ]),
+    ("help.meme.settings.disable", &[
+        (Es, "Deshabilitar un comando, o un grupo de comandos, en este chat"),
+        (Pt, "Desativar um comando, ou um grupo de comandos, neste chat"),
+    ]),
+    ("help.meme.settings.enable", &[
+        (Es, "Volver a habilitar un comando, o un grupo de comandos, en este chat"),
+        (Pt, "Reativar um comando, ou um grupo de comandos, neste chat"),
+    ]),
+    ("help.meme.settings.nsfw", &[
+        (Es, "Definir qué tan estricto es el filtro NSFW en este chat"),
+        (Pt, "Definir o quão rigoroso é o filtro NSFW neste chat"),
+    ]),
+    ("help.meme.settings.reset", &[
+        (Es, "Restaurar la configuración por defecto"),
+        (Pt, "Restaurar as configurações padrão"),
+    ]),
+    ("help.meme.admin", &[
+        (Es, "Subcomandos de los administradores del bot"),
+        (Pt, "Subcomandos dos administradores do bot"),
+    ]),
+    ("help.meme.admin.roles", &[
+        (Es, "Gestionar los roles de los administradores del bot"),
+        (Pt, "Gerenciar as funções dos administradores do bot"),
+    ]),
+    ("help.meme.admin.roles.list", &[
+        (Es, "Listar los administradores y sus roles"),
+        (Pt, "Listar os administradores e suas funções"),
+    ]),
+    ("help.meme.admin.roles.grant", &[
+        (Es, "Otorgar roles a un principal (solo propietarios)"),
+        (Pt, "Conceder funções a um principal (somente proprietários)"),
+    ]),
+    ("help.meme.admin.roles.grant.roles", &[
+        (Es, "Roles a otorgar"),
+        (Pt, "Funções a conceder"),
+    ]),
+    ("help.meme.admin.roles.revoke", &[
+        (Es, "Revocar roles de un principal (solo propietarios)"),
+        (Pt, "Revogar funções de um principal (somente proprietários)"),
+    ]),
+    ("help.meme.admin.roles.revoke.roles", &[
+        (Es, "Roles a revocar"),
+        (Pt, "Funções a revogar"),
+    ]),
+    ("help.meme.admin.nft", &[
+        (Es, "Gestionar la colección de MEME NFT (solo tesoreros)"),
+        (Pt, "Gerenciar a coleção de MEME NFT (somente tesoureiros)"),
+    ]),
+    ("help.meme.admin.nft.config", &[
+        (Es, "Mostrar o cambiar la configuración de acuñación (solo se cambian las opciones pasadas)"),
+        (Pt, "Exibir ou alterar a configuração de cunhagem (somente as opções passadas são alteradas)"),
+    ]),
+    ("help.meme.admin.nft.config.min_reactions", &[
+        (Es, "Número mínimo de reacciones requeridas para acuñar"),
+        (Pt, "Número mínimo de reações necessárias para cunhar"),
+    ]),
+    ("help.meme.admin.nft.config.max_reactions", &[
+        (Es, "Número máximo de reacciones requeridas para acuñar"),
+        (Pt, "Número máximo de reações necessárias para cunhar"),
+    ]),
+    ("help.meme.admin.nft.config.min_cost", &[
+        (Es, "Costo mínimo de acuñación, en ICP (ej: 0.5)"),
+        (Pt, "Custo mínimo de cunhagem, em ICP (ex: 0.5)"),
+    ]),
+    ("help.meme.admin.nft.config.max_cost", &[
+        (Es, "Costo máximo de acuñación, en ICP (ej: 2.5)"),
+        (Pt, "Custo máximo de cunhagem, em ICP (ex: 2.5)"),
+    ]),
+    ("help.meme.admin.nft.config.min_chat_members", &[
+        (Es, "Número mínimo de miembros del chat"),
+        (Pt, "Número mínimo de membros do chat"),
+    ]),
+    ("help.meme.admin.nft.config.min_user_age", &[
+        (Es, "Antigüedad mínima, en días, de las cuentas cuyas reacciones se cuentan"),
+        (Pt, "Idade mínima, em dias, das contas cujas reações são contadas"),
+    ]),
+    ("help.meme.admin.nft.config.team_fee", &[
+        (Es, "Comisión del equipo, en porcentaje del costo de acuñación (ej: 12.5)"),
+        (Pt, "Taxa da equipe, em porcentagem do custo de cunhagem (ex: 12.5)"),
+    ]),
+    ("help.meme.admin.nft.pause", &[
+        (Es, "Pausar la acuñación"),
+        (Pt, "Pausar a cunhagem"),
+    ]),
+    ("help.meme.admin.nft.pause.reason", &[
+        (Es, "Motivo mostrado a los usuarios (usa comillas simples, ej. 'un motivo', para preservar los espacios)"),
+        (Pt, "Motivo exibido aos usuários (use aspas simples, ex. 'um motivo', para preservar os espaços)"),
+    ]),
+    ("help.meme.admin.nft.resume", &[
+        (Es, "Reanudar la acuñación"),
+        (Pt, "Retomar a cunhagem"),
+    ]),
+    ("help.meme.admin.nft.logs", &[
+        (Es, "Mostrar el registro de cambios de configuración"),
+        (Pt, "Exibir o registro de alterações de configuração"),
+    ]),
+    ("help.meme.admin.nft.preview", &[
+        (Es, "Previsualizar el costo de acuñación y las reacciones mínimas en niveles de suministro"),
+        (Pt, "Pré-visualizar o custo de cunhagem e as reações mínimas em níveis de fornecimento"),
+    ]),
+    ("help.meme.admin.nft.preview.supplies", &[
+        (Es, "Niveles de suministro, separados por espacios (por defecto: el suministro actual)"),
+        (Pt, "Níveis de fornecimento, separados por espaços (padrão: o fornecimento atual)"),
+    ]),
+];
\ No newline at end of file
@@ -1,7 +1,10 @@
 use candid::Principal;
-use icrc_ledger_types::icrc1::{
-    account::{principal_to_subaccount, Account}, 
-    transfer::{TransferArg, TransferError}
+use icrc_ledger_types::{
+    icrc::generic_metadata_value::MetadataValue, 
+    icrc1::{
+        account::{principal_to_subaccount, Account}, 
+        transfer::{TransferArg, TransferError}
+    }
 };
 
 pub struct ICRC1;
@@ -46,6 +49,19 @@ impl ICRC1 {
         res.map_err(|e| e.to_string())
     }
 
+    pub async fn metadata(
+        canister_id: &Principal
+    ) -> Result<Vec<(String, MetadataValue)>, String> {
+
+        let (res, ): (Vec<(String, MetadataValue)>, ) = ic_cdk::call(
+            canister_id.clone(), 
+            "icrc1_metadata",
+            ()
+        ).await.map_err(|e| e.1)?;
+
+        Ok(res)
+    }
+
     pub async fn minting_account(
         canister_id: &Principal
     ) -> Result<Option<Account>, String> {
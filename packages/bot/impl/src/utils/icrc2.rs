@@ -0,0 +1,43 @@
+use candid::Principal;
+use icrc_ledger_types::icrc2::{
+    allowance::{Allowance, AllowanceArgs}, 
+    transfer_from::{TransferFromArgs, TransferFromError}
+};
+use super::nat::nat_to_u128;
+
+pub struct ICRC2;
+
+impl ICRC2 {
+    pub async fn allowance(
+        canister_id: &Principal,
+        args: AllowanceArgs
+    ) -> Result<Allowance, String> {
+
+        let (res, ): (Allowance, ) = ic_cdk::call(
+            canister_id.clone(), 
+            "icrc2_allowance",
+            (&args, )
+        ).await.map_err(|e| e.1)?;
+
+        Ok(res)
+    }
+
+    /// A transfer deduplicated by the ledger is treated as a success, as it was already made
+    pub async fn transfer_from(
+        canister_id: &Principal,
+        args: TransferFromArgs
+    ) -> Result<u128, String> {
+
+        let (res, ): (Result<u128, TransferFromError>, ) = ic_cdk::call(
+            canister_id.clone(), 
+            "icrc2_transfer_from",
+            (&args, )
+        ).await.map_err(|e| e.1)?;
+
+        match res {
+            Ok(block_num) => Ok(block_num),
+            Err(TransferFromError::Duplicate { duplicate_of }) => Ok(nat_to_u128(duplicate_of)),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+}
\ No newline at end of file
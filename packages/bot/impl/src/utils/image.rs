@@ -1,6 +1,10 @@
 use std::io::Cursor;
-use image::{imageops, ImageBuffer, ImageFormat, Rgb, RgbImage, RgbaImage};
+use image::{imageops, DynamicImage, ImageBuffer, ImageFormat, Rgb, RgbImage, RgbaImage};
 use oc_bots_sdk::types::ThumbnailData;
+use crate::types::{
+    blob::Blob, 
+    derived_blob::{ImageTransform, TransformFormat}
+};
 
 pub fn create_thumbnail(
     image: &[u8],
@@ -61,3 +65,42 @@ pub fn resize(
     }
 }
 
+/// Resizes (never upscaling, and keeping the aspect ratio) and re-encodes an image
+pub fn transform(
+    data: &[u8],
+    mime_type: &str,
+    transform: &ImageTransform
+) -> Result<Blob, String> {
+    let img = image::load_from_memory(data)
+        .map_err(|e| e.to_string())?;
+
+    let img = if transform.width.is_some() || transform.height.is_some() {
+        img.thumbnail(
+            transform.width.unwrap_or(u32::MAX).min(img.width()), 
+            transform.height.unwrap_or(u32::MAX).min(img.height())
+        )
+    }
+    else {
+        img
+    };
+
+    let format = transform.format
+        .unwrap_or(TransformFormat::from_mime_type(mime_type));
+
+    // jpeg has no alpha channel and webp only supports 8-bit images
+    let img = match format {
+        TransformFormat::Jpeg => DynamicImage::ImageRgb8(img.to_rgb8()),
+        TransformFormat::WebP => DynamicImage::ImageRgba8(img.to_rgba8()),
+        TransformFormat::Png => img,
+    };
+
+    let format = format.image_format();
+    let mut bytes: Vec<u8> = Vec::new();
+    img.write_to(&mut Cursor::new(&mut bytes), format)
+        .map_err(|e| e.to_string())?;
+
+    Ok(Blob {
+        mime_type: format.to_mime_type().to_string(),
+        data: bytes,
+    })
+}
\ No newline at end of file
@@ -10,4 +10,8 @@ pub mod out_font;
 pub mod msgpack;
 pub mod icrc1;
 pub mod nat;
-pub mod gz;
\ No newline at end of file
+pub mod gz;
+pub mod http;
+pub mod time;
+pub mod i18n;
+pub mod icrc2;
\ No newline at end of file
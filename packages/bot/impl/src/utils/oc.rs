@@ -110,4 +110,52 @@ pub async fn get_chat_user_profile(
     else {
         None
     }
+}
+
+/// Mainnet OpenChat user index
+const USER_INDEX_CANISTER_ID: &str = "4bkt6-4aaaa-aaaaf-aaaiq-cai";
+
+/// Resolves an OC username (with or without the leading '@') to the user's id
+pub async fn lookup_username(
+    username: &str
+) -> Option<Principal> {
+    use user_index_canister::user::{Args, Response};
+    let canister_id = Principal::from_text(USER_INDEX_CANISTER_ID).unwrap();
+
+    match call_raw(
+        canister_id,
+        "user_msgpack",
+        serialize_to_vec(Args{
+            user_id: None,
+            username: Some(username.trim_start_matches('@').to_string()),
+        }).unwrap(),
+        0
+    ).await {
+        Ok(buf) => {
+            let res: Response = rmp_serde::from_slice(&buf).ok()?;
+            match res {
+                Response::Success(user) => {
+                    Some(user.user_id.into())
+                },
+                Response::UserNotFound => {
+                    None
+                },
+            }
+        },
+        Err(err) => {
+            ic_cdk::println!("error: calling {}.user_msgpack: {:?}", canister_id, err);
+            None
+        },
+    }
+}
+
+/// Key used to refer to a chat in the storages
+pub fn chat_key(
+    chat: &Chat
+) -> String {
+    match chat {
+        Chat::Direct(canister_id) => format!("direct:{}", canister_id),
+        Chat::Group(canister_id) => format!("group:{}", canister_id),
+        Chat::Channel(canister_id, channel_id) => format!("channel:{}:{}", canister_id, channel_id),
+    }
 }
\ No newline at end of file
@@ -0,0 +1,33 @@
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// Formats a timestamp, in nanoseconds, as "YYYY-MM-DD HH:MM UTC"
+pub fn format_utc(
+    timestamp: u64
+) -> String {
+    let secs = timestamp / NANOS_PER_SEC;
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    let secs_of_day = secs % 86_400;
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC", 
+        year, month, day, 
+        secs_of_day / 3_600, (secs_of_day % 3_600) / 60
+    )
+}
+
+// see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(
+    days: i64
+) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
\ No newline at end of file
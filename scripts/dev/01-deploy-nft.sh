@@ -20,7 +20,7 @@ if [[ "$(dfx canister bot 2>&1 | grep Error)" != "" ]]; then
 fi
 
 BOT_CANISTER_ID=$(dfx canister id bot)
-LOGO_URL="http://$BOT_CANISTER_ID.raw.localhost:8080/assets/nft_logo.png"
+LOGO_URL="http://$BOT_CANISTER_ID.localhost:8080/assets/nft_logo.png"
 MAX_SUPPLY=10000
 
 dfx deploy nft -v --identity default --with-cycles 10000000000000 --argument "(record{
@@ -25,7 +25,7 @@ MINTER_CANISTER_ID=$(dfx canister id minter)
 NFT_COL_CANISTER_ID=$(dfx canister id nft)
 
 NFT_MAX_SUPPLY=10000
-NFT_COL_URL_TEMPLATE="http://$BOT_CANISTER_ID.raw.localhost:8080/assets/nfts/{}.jpg"
+NFT_COL_URL_TEMPLATE="http://$BOT_CANISTER_ID.localhost:8080/assets/nfts/{}.jpg"
 NFT_MIN_CHAT_MEMBERS=3 # chat where the meme was posted must have at least n members to a NFT be minted
 NFT_MIN_USER_CREATION_INTERVAL=$((1*24*60*60*1000)) # a reaction only counts if the user reacting was created at least n days ago
 NFT_MIN_NUM_REACTIONS=1
@@ -34,6 +34,21 @@ NFT_MIN_MINTING_COST=100000000 # 1.0 ICP in e8s
 NFT_MAX_MINTING_COST=10000000000 # 100.0 ICP in e8s
 NFT_TEAM_FEE=10000000 # 10% in e8s. 90% of the minting cost goes to treasury
 
+# the config changed at runtime with '/meme admin nft config' is kept on upgrades.
+# It's only sent on the first install, or when this script is run with --nft-config
+NFT_CONFIG=null
+if [[ "$(dfx canister info bot | grep 'Module hash: None')" != "" || "$1" = "--nft-config" ]]; then
+  NFT_CONFIG="opt record { 
+        min_num_reactions = $NFT_MIN_NUM_REACTIONS;
+        max_num_reactions = $NFT_MAX_NUM_REACTIONS;
+        min_minting_cost = $NFT_MIN_MINTING_COST;
+        max_minting_cost = $NFT_MAX_MINTING_COST;
+        min_chat_members = $NFT_MIN_CHAT_MEMBERS;
+        min_user_creation_interval = $NFT_MIN_USER_CREATION_INTERVAL;
+        team_fee_p = $NFT_TEAM_FEE;
+      }"
+fi
+
 INDEX_GZ=$(od -t x1 -v -w1048576 -A n $ASSETS_DATA_DIR/index.gz | sed "s/ /\\\/g")
 MEMES_GZ=$(od -t x1 -v -w1048576 -A n $ASSETS_DATA_DIR/memes.gz | sed "s/ /\\\/g")
 
@@ -56,15 +71,7 @@ dfx deploy bot -v --identity default --with-cycles 10000000000000 --argument-fil
         canister_id = principal \"$NFT_COL_CANISTER_ID\";
         url_template = \"$NFT_COL_URL_TEMPLATE\";
       };
-      meme_nft_config = record { 
-        min_num_reactions = $NFT_MIN_NUM_REACTIONS;
-        max_num_reactions = $NFT_MAX_NUM_REACTIONS;
-        min_minting_cost = $NFT_MIN_MINTING_COST;
-        max_minting_cost = $NFT_MAX_MINTING_COST;
-        min_chat_members = $NFT_MIN_CHAT_MEMBERS;
-        min_user_creation_interval = $NFT_MIN_USER_CREATION_INTERVAL;
-        team_fee_p = $NFT_TEAM_FEE;
-      };
+      meme_nft_config = $NFT_CONFIG;
     }
 )")
 
@@ -20,7 +20,7 @@ if [[ "$(dfx canister bot 2>&1 | grep Error)" != "" ]]; then
 fi
 
 BOT_CANISTER_ID=$(dfx canister id bot --ic)
-LOGO_URL="http://$BOT_CANISTER_ID.raw.icp0.io/assets/nft_logo.png"
+LOGO_URL="https://$BOT_CANISTER_ID.icp0.io/assets/nft_logo.png"
 MAX_SUPPLY=10000
 
 dfx deploy nft -v --ic --identity deployer --with-cycles 3000000000000 --argument "(record{